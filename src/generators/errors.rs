@@ -26,4 +26,22 @@ impl fmt::Display for NoLines {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "No lines available in path")
     }
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone)]
+pub struct TextNotSupported(pub String, pub String);
+impl Error for TextNotSupported {}
+impl fmt::Display for TextNotSupported {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Text is not supported by the {} generator, found text: {}", self.0, self.1)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EmptyCanvas;
+impl Error for EmptyCanvas {}
+impl fmt::Display for EmptyCanvas {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Nothing has been drawn, cannot create an empty canvas")
+    }
+}
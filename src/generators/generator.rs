@@ -2,7 +2,7 @@ use std::error::Error;
 
 use crate::interpreter::data_types::figurearray::FigureArray;
 
-use super::svggenerator::SvgGenerator;
+use super::{pnggenerator::PngGenerator, svggenerator::SvgGenerator};
 
 
 pub trait Generator {
@@ -12,6 +12,7 @@ pub trait Generator {
 pub fn get_generator(format: &str) -> Option<Box<dyn Generator>> {
     match format {
         "svg" => Some(Box::new(SvgGenerator::new())),
+        "png" => Some(Box::new(PngGenerator::new())),
         _ => None,
     }
 }
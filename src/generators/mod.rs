@@ -2,6 +2,7 @@ use crate::interpreter::{data_types::line::Line, value::Value};
 
 pub mod errors;
 pub mod generator;
+pub mod pnggenerator;
 pub mod svggenerator;
 mod tests;
 
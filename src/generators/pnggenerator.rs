@@ -0,0 +1,380 @@
+use std::{error::Error, f64::consts::PI, fs::File, io::BufWriter};
+
+use crate::interpreter::{
+    data_types::{
+        figure::Figure,
+        figurearray::FigureArray,
+        line::{bezier_point, Line},
+        point::Point,
+    },
+    value::Value,
+};
+
+use super::{errors, generator::Generator, svggenerator::SvgGenerator};
+
+//Small drawings are scaled up so the longest side has at least this many pixels
+const MIN_CANVAS_SIDE: f64 = 256.;
+//Large drawings are scaled down so the longest side has at most this many pixels
+const MAX_CANVAS_SIDE: f64 = 8192.;
+//Amount of sub-scanlines sampled per pixel row (vertical anti-aliasing)
+const SUBSAMPLES: usize = 8;
+//Approximate length in pixels of each segment when flattening curves
+const FLATTEN_STEP: f64 = 2.;
+//How far in pixels a flattened arc may stray from the real one
+const FLATTEN_TOLERANCE: f64 = 0.25;
+
+type Coord = (f64, f64);
+
+impl Generator for PngGenerator {
+    fn generate(
+        &mut self,
+        mut draw_array: FigureArray,
+        file_name: String,
+    ) -> Result<(), Box<dyn Error>> {
+        //Flips all y-values for the drawArray, same as the SVG generator
+        draw_array.flip_y();
+
+        self.calc_canvas(&draw_array)?;
+        for fig in draw_array.get_figures() {
+            self.draw_figure(fig)?;
+        }
+
+        let file = File::create(format!("{}.png", file_name))?;
+        let mut encoder = png::Encoder::new(BufWriter::new(file), self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()?
+            .write_image_data(&self.rgba_bytes())?;
+        Ok(())
+    }
+}
+
+pub struct PngGenerator {
+    origin: Coord,
+    scale: f64,
+    width: u32,
+    height: u32,
+    //Premultiplied rgba values in the range 0..1
+    pixels: Vec<[f64; 4]>,
+}
+
+impl Default for PngGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PngGenerator {
+    pub fn new() -> Self {
+        Self {
+            origin: (0., 0.),
+            scale: 1.,
+            width: 0,
+            height: 0,
+            pixels: Vec::new(),
+        }
+    }
+
+    pub fn get_width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn get_height(&self) -> u32 {
+        self.height
+    }
+
+    /// Sizes the canvas from the same bounds the SVG viewBox is calculated from.
+    /// One unit is one pixel, unless the drawing is very small or very large.
+    pub fn calc_canvas(&mut self, draw_array: &FigureArray) -> Result<(), Box<dyn Error>> {
+        let (x, y, width, height) = SvgGenerator::calc_bounds(draw_array);
+        if !(width.is_finite() && height.is_finite()) {
+            return Err(Box::new(errors::EmptyCanvas));
+        }
+
+        let longest_side = width.max(height).max(f64::EPSILON);
+        self.scale = (MIN_CANVAS_SIDE / longest_side)
+            .max(1.)
+            .min(MAX_CANVAS_SIDE / longest_side);
+        self.origin = (x, y);
+        self.width = ((width * self.scale).ceil() as u32).max(1);
+        self.height = ((height * self.scale).ceil() as u32).max(1);
+        self.pixels = vec![[0.; 4]; (self.width * self.height) as usize];
+        Ok(())
+    }
+
+    /// Paints a figure the way an SVG viewer would paint the corresponding path:
+    /// fill first (black when no fill is given), then the stroke if one is set.
+    /// Text needs a font to be drawn and gives a `TextNotSupported` error.
+    pub fn draw_figure(&mut self, fig: &Figure) -> Result<(), Box<dyn Error>> {
+        if let Some(text) = fig.get_text() {
+            return Err(errors::TextNotSupported("png".into(), text.into()).into());
+        }
+        let attributes = fig.get_attributes();
+        if attributes.contains_key("fill") && !fig.is_closed()? {
+            return Err(Box::new(errors::AttributeNotValid("fill".into())));
+        }
+        if let Some(attribute) = attributes
+            .keys()
+            .find(|attr| !matches!(attr.as_str(), "fill" | "stroke" | "thickness"))
+        {
+            return Err(Box::new(errors::AttributeNotValid(attribute.into())));
+        }
+
+        let polylines = self.flatten(fig)?;
+
+        let fill = match attributes.get("fill") {
+            Some(color) => PngGenerator::map_color(color)?,
+            None => [0., 0., 0., 1.],
+        };
+        self.fill_polygons(&polylines, fill);
+
+        if let Some(color) = attributes.get("stroke") {
+            let thickness = match attributes.get("thickness") {
+                Some(thickness) => thickness.get_int()? as f64,
+                None => 1.,
+            };
+            let stroke: Vec<Vec<Coord>> = polylines
+                .iter()
+                .flat_map(|polyline| {
+                    PngGenerator::stroke_polygons(polyline, thickness * self.scale / 2.)
+                })
+                .collect();
+            self.fill_polygons(&stroke, PngGenerator::map_color(color)?);
+        }
+        Ok(())
+    }
+
+    fn to_canvas(&self, point: &Point) -> Coord {
+        (
+            (point.get_x_f64() - self.origin.0) * self.scale,
+            (point.get_y_f64() - self.origin.1) * self.scale,
+        )
+    }
+
+    //Turns each contour of a figure into a polyline in canvas coordinates
+    fn flatten(&self, fig: &Figure) -> Result<Vec<Vec<Coord>>, Box<dyn Error>> {
+        if fig.get_lines().is_empty() {
+            return Err(Box::new(errors::NoLines));
+        }
+        fig.get_contours()
+            .into_iter()
+            .map(|contour| self.flatten_contour(contour))
+            .collect()
+    }
+
+    fn flatten_contour(&self, contour: &[Line]) -> Result<Vec<Coord>, Box<dyn Error>> {
+        let mut polyline = vec![self.to_canvas(contour[0].get_first_point()?)];
+        for line in contour.iter().flat_map(Line::get_segments) {
+            if let Line::Arc(points) = &line {
+                let canvas_points = points.iter().map(|p| self.to_canvas(p).into()).collect();
+                match Line::Arc(canvas_points).get_arc() {
+                    Some(arc) => {
+                        let segments = arc.segment_count(FLATTEN_TOLERANCE);
+                        polyline
+                            .extend((1..=segments).map(|i| arc.point(i as f64 / segments as f64)));
+                    }
+                    None => polyline.push(self.to_canvas(line.get_last_point()?)),
+                }
+                continue;
+            }
+            let start = *polyline.last().unwrap();
+            let controls: Vec<Coord> = std::iter::once(start)
+                .chain(line.get_points().iter().skip(1).map(|p| self.to_canvas(p)))
+                .collect();
+            match controls.len() {
+                2 => polyline.push(controls[1]),
+                3 | 4 => {
+                    let hull_length: f64 = controls
+                        .windows(2)
+                        .map(|w| distance(w[0], w[1]))
+                        .sum();
+                    let segments = (hull_length / FLATTEN_STEP).ceil().clamp(1., 1024.) as usize;
+                    polyline.extend(
+                        (1..=segments).map(|i| bezier_point(&controls, i as f64 / segments as f64)),
+                    );
+                }
+                amount => return Err(Box::new(errors::TooManyPoints(amount.to_string()))),
+            }
+        }
+        Ok(polyline)
+    }
+
+    //Builds the outline of a stroke as quads along each segment with round joins between them.
+    //All pieces get the same orientation, so filling them with the nonzero rule gives their union.
+    fn stroke_polygons(polyline: &[Coord], half_width: f64) -> Vec<Vec<Coord>> {
+        let mut polygons = Vec::new();
+        let segments: Vec<(Coord, Coord)> = polyline
+            .windows(2)
+            .map(|w| (w[0], w[1]))
+            .filter(|(p, q)| distance(*p, *q) > f64::EPSILON)
+            .collect();
+
+        for (p, q) in &segments {
+            let length = distance(*p, *q);
+            let normal = (
+                -(q.1 - p.1) / length * half_width,
+                (q.0 - p.0) / length * half_width,
+            );
+            polygons.push(vec![
+                (p.0 + normal.0, p.1 + normal.1),
+                (q.0 + normal.0, q.1 + normal.1),
+                (q.0 - normal.0, q.1 - normal.1),
+                (p.0 - normal.0, p.1 - normal.1),
+            ]);
+        }
+
+        let corners = (2. * PI * half_width / FLATTEN_STEP).ceil().clamp(8., 64.) as usize;
+        for (_, joint) in segments.iter().take(segments.len().saturating_sub(1)) {
+            polygons.push(
+                (0..corners)
+                    .map(|i| {
+                        let angle = 2. * PI * i as f64 / corners as f64;
+                        (
+                            joint.0 + half_width * angle.cos(),
+                            joint.1 + half_width * angle.sin(),
+                        )
+                    })
+                    .collect(),
+            );
+        }
+
+        for polygon in polygons.iter_mut() {
+            if signed_area(polygon) > 0. {
+                polygon.reverse();
+            }
+        }
+        polygons
+    }
+
+    //Scanline fill using the nonzero winding rule.
+    //Coverage is exact horizontally and sampled SUBSAMPLES times per pixel vertically.
+    fn fill_polygons(&mut self, polygons: &[Vec<Coord>], color: [f64; 4]) {
+        //Edges as (top, bottom, winding direction), skipping horizontal ones
+        let edges: Vec<(Coord, Coord, i32)> = polygons
+            .iter()
+            .filter(|polygon| polygon.len() > 1)
+            .flat_map(|polygon| {
+                polygon
+                    .iter()
+                    .zip(polygon.iter().cycle().skip(1))
+                    .map(|(p, q)| (*p, *q))
+            })
+            .filter(|(p, q)| p.1 != q.1)
+            .map(|(p, q)| if p.1 < q.1 { (p, q, 1) } else { (q, p, -1) })
+            .collect();
+        if edges.is_empty() {
+            return;
+        }
+
+        let y_min = edges.iter().map(|(top, _, _)| top.1).fold(f64::MAX, f64::min);
+        let y_max = edges.iter().map(|(_, bottom, _)| bottom.1).fold(f64::MIN, f64::max);
+        let first_row = y_min.floor().max(0.) as usize;
+        let last_row = (y_max.ceil().max(0.) as usize).min(self.height as usize);
+
+        let width = self.width as usize;
+        let mut coverage = vec![0.; width];
+        let mut crossings: Vec<(f64, i32)> = Vec::new();
+        for row in first_row..last_row {
+            coverage.iter_mut().for_each(|c| *c = 0.);
+            for sample in 0..SUBSAMPLES {
+                let sample_y = row as f64 + (sample as f64 + 0.5) / SUBSAMPLES as f64;
+                crossings.clear();
+                crossings.extend(
+                    edges
+                        .iter()
+                        .filter(|(top, bottom, _)| top.1 <= sample_y && sample_y < bottom.1)
+                        .map(|(top, bottom, dir)| {
+                            let t = (sample_y - top.1) / (bottom.1 - top.1);
+                            (top.0 + t * (bottom.0 - top.0), *dir)
+                        }),
+                );
+                crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+                let mut winding = 0;
+                let mut span_start = 0.;
+                for (x, dir) in &crossings {
+                    if winding == 0 {
+                        span_start = *x;
+                    }
+                    winding += dir;
+                    if winding == 0 {
+                        add_span(&mut coverage, span_start, *x, 1. / SUBSAMPLES as f64);
+                    }
+                }
+            }
+
+            for (column, cov) in coverage.iter().enumerate() {
+                if *cov > 0. {
+                    self.blend(row * width + column, color, cov.min(1.));
+                }
+            }
+        }
+    }
+
+    //Source-over compositing of a straight alpha color onto the premultiplied canvas
+    fn blend(&mut self, idx: usize, color: [f64; 4], coverage: f64) {
+        let alpha = color[3] * coverage;
+        let pixel = &mut self.pixels[idx];
+        for channel in 0..3 {
+            pixel[channel] = color[channel] * alpha + pixel[channel] * (1. - alpha);
+        }
+        pixel[3] = alpha + pixel[3] * (1. - alpha);
+    }
+
+    pub fn rgba_bytes(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|[r, g, b, a]| {
+                let unmultiply = |c: f64| if *a > 0. { c / a } else { 0. };
+                [unmultiply(*r), unmultiply(*g), unmultiply(*b), *a]
+            })
+            .map(|c| (c.clamp(0., 1.) * 255.).round() as u8)
+            .collect()
+    }
+
+    fn map_color(color: &Value) -> Result<[f64; 4], Box<dyn Error>> {
+        match color {
+            Value::Color(r, g, b, a) => Ok([
+                r.get_int()? as f64 / 255.,
+                g.get_int()? as f64 / 255.,
+                b.get_int()? as f64 / 255.,
+                a.get_int()? as f64 / 255.,
+            ]),
+            _ => unreachable!(),
+        }
+    }
+}
+
+fn distance(p: Coord, q: Coord) -> f64 {
+    (q.0 - p.0).hypot(q.1 - p.1)
+}
+
+fn signed_area(polygon: &[Coord]) -> f64 {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(p, q)| p.0 * q.1 - q.0 * p.1)
+        .sum::<f64>()
+        / 2.
+}
+
+//Adds the horizontal coverage of the span [x_start, x_end) to the pixels it touches
+fn add_span(coverage: &mut [f64], x_start: f64, x_end: f64, weight: f64) {
+    let x_start = x_start.max(0.);
+    let x_end = x_end.min(coverage.len() as f64);
+    if x_start >= x_end {
+        return;
+    }
+    let first_column = x_start.floor() as usize;
+    let last_column = x_end.ceil() as usize;
+    for (column, cov) in coverage
+        .iter_mut()
+        .enumerate()
+        .take(last_column)
+        .skip(first_column)
+    {
+        let overlap = x_end.min(column as f64 + 1.) - x_start.max(column as f64);
+        *cov += overlap * weight;
+    }
+}
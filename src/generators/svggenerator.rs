@@ -1,7 +1,12 @@
 use std::{error::Error, fs::File, io::Write};
 
 use crate::interpreter::{
-    data_types::{figure::Figure, figurearray::FigureArray},
+    data_types::{
+        figure::{Figure, FigureKind, ELLIPSE_KAPPA},
+        figurearray::FigureArray,
+        line::Line,
+        point::Point,
+    },
     value::Value,
 };
 
@@ -42,6 +47,17 @@ impl SvgGenerator {
     }
 
     pub fn calc_viewbox(&mut self, draw_array: &FigureArray) -> Result<(), Box<dyn Error>> {
+        let (view_box_x, view_box_y, width, height) = SvgGenerator::calc_bounds(draw_array);
+
+        //Format viewbox
+        self.view_box = format!("{} {} {} {}", view_box_x, view_box_y, width, height);
+        Ok(())
+    }
+
+    /// Calculates the area covered by the drawing as (x, y, width, height),
+    /// padded by half of the thickest line so strokes are not clipped.
+    /// Other generators use this to size their canvas the same way as the viewBox.
+    pub fn calc_bounds(draw_array: &FigureArray) -> (f64, f64, f64, f64) {
         //Calc Max_Line_Width
         let line_thickness_max = draw_array.get_figures().iter()
         .fold(1, |max_line, fig| 
@@ -53,12 +69,12 @@ impl SvgGenerator {
                 .max(max_line)
         ) as f64;
 
-        //Get all points in the form (x, y)
+        //Get the points bounding every line in the form (x, y), curves are measured where they turn
         let x_y_cords = draw_array
             .get_figures()
             .iter()
             .flat_map(|fig| fig.get_lines())
-            .flat_map(|line| line.get_points())
+            .flat_map(|line| line.get_extreme_points())
             .map(|point| (point.get_x_f64(), point.get_y_f64()));
 
         //Calc maxX, minX, maxY, minY
@@ -73,19 +89,17 @@ impl SvgGenerator {
             y_max = y_max.max(y_val);
         }
 
-        //Format viewbox and does calculations for:
+        //Calculations for:
         //viewBoxX
         //viewBoxY
         //width
         //height
-        self.view_box = format!(
-            "{} {} {} {}",
+        (
             x_min - line_thickness_max / 2.,
             y_min - line_thickness_max / 2.,
             (x_max - x_min).abs() + line_thickness_max,
-            (y_max - y_min).abs() + line_thickness_max
-        );
-        Ok(())
+            (y_max - y_min).abs() + line_thickness_max,
+        )
     }
 
     pub fn calc_paths(&mut self, draw_array: &FigureArray) -> Result<(), Box<dyn Error>> {
@@ -108,6 +122,14 @@ impl SvgGenerator {
     }
 
     fn map_path(fig: &Figure) -> Result<String, Box<dyn Error>> {
+        if let Some(content) = fig.get_text() {
+            return SvgGenerator::map_text(fig, content);
+        }
+        if let Some(element) = SvgGenerator::map_primitive(fig) {
+            let attr_str = SvgGenerator::map_all_attributes(fig)?;
+            return Ok(format!("<{} {}/>", element, attr_str));
+        }
+
         //linesToPath Operation
         let path_str = SvgGenerator::map_points(fig)?;
         let attr_str = SvgGenerator::map_all_attributes(fig)?;
@@ -115,24 +137,163 @@ impl SvgGenerator {
         Ok(format!("<path d=\"{}\" {}/>", path_str, attr_str))
     }
 
-    fn map_points(fig: &Figure) -> Result<String, Box<dyn Error>> {
-        let line = fig
-            .get_lines()
-            .first()
-            .ok_or_else(|| Box::new(errors::NoLines))?;
+    /// Lays out text from the corners of its box, see `Figure::text`.
+    /// The box may have been scaled or rotated, so the font size and angle are measured from it,
+    /// and textLength makes the rendered text fill exactly the width that was estimated.
+    fn map_text(fig: &Figure, content: &str) -> Result<String, Box<dyn Error>> {
+        let corner = |idx: usize| -> Result<(f64, f64), Box<dyn Error>> {
+            let point = fig
+                .get_lines()
+                .get(idx)
+                .ok_or_else(|| Box::new(errors::NoLines))?
+                .get_first_point()?;
+            //Adding zero turns the -0 left by flipping y into 0
+            Ok((point.get_x_f64() + 0., point.get_y_f64() + 0.))
+        };
+        let (start, end, top) = (corner(0)?, corner(1)?, corner(3)?);
 
-        //This will be the string all points are concatenated with, starts with call to M from design
-        let mut path_str = format!("M{}", line.get_first_point()?.svg_format());
+        let length = (end.0 - start.0).hypot(end.1 - start.1);
+        let size = (top.0 - start.0).hypot(top.1 - start.1);
+        let angle = (end.1 - start.1).atan2(end.0 - start.0).to_degrees();
 
-        let lines_points = fig
-            .get_lines()
+        let mut attr_str = format!(
+            "x=\"{}\" y=\"{}\" font-family=\"monospace\" font-size=\"{}\" textLength=\"{}\" lengthAdjust=\"spacingAndGlyphs\" ",
+            start.0, start.1, size, length
+        );
+        if angle.abs() > 1e-9 {
+            attr_str.push_str(&format!("transform=\"rotate({} {} {})\" ", angle, start.0, start.1));
+        }
+        if let Some(fill) = fig.get_attributes().get("fill") {
+            attr_str.push_str(&SvgGenerator::map_attribute((&"fill".into(), fill), true)?);
+        }
+
+        Ok(format!("<text {}>{}</text>", attr_str, SvgGenerator::escape(content)))
+    }
+
+    /// An exact element for a figure made by `Figure::ellipse` or `Figure::rect`, as the element name and its geometry.
+    /// Moving, scaling, mirroring and rotating by right angles keep the figure an axis-aligned ellipse or rectangle,
+    /// anything else that changed its shape gives `None` and the figure is drawn as a path.
+    fn map_primitive(fig: &Figure) -> Option<String> {
+        let lines = fig.get_lines();
+        if fig.get_contours().len() != 1 || !fig.is_closed().ok()? {
+            return None;
+        }
+        let (curves, sides): (Vec<&Line>, Vec<&Line>) = lines
+            .iter()
+            .partition(|line| matches!(line, Line::Curved(_)));
+        let radii = curves
             .iter()
-            .map(|line| line.get_points().as_slice());
+            .map(|curve| SvgGenerator::quarter_radii(curve))
+            .collect::<Option<Vec<(f64, f64)>>>()?;
+        let (rx, ry) = radii.first().copied().unwrap_or((0., 0.));
+        if radii.iter().any(|r| !approx(r.0, rx) || !approx(r.1, ry)) {
+            return None;
+        }
+
+        let (min_x, min_y, max_x, max_y) = fig.get_control_hull();
+        let (x, y) = (min_x.get_float().ok()? + 0., min_y.get_float().ok()? + 0.);
+        let (width, height) = (max_x.get_float().ok()? - x, max_y.get_float().ok()? - y);
+        match fig.get_kind() {
+            FigureKind::Ellipse if curves.len() == 4 && sides.is_empty() => {
+                if !approx(width, 2. * rx) || !approx(height, 2. * ry) {
+                    return None;
+                }
+                let (cx, cy) = (x + rx, y + ry);
+                Some(if approx(rx, ry) {
+                    format!("circle cx=\"{}\" cy=\"{}\" r=\"{}\"", cx, cy, rx)
+                } else {
+                    format!(
+                        "ellipse cx=\"{}\" cy=\"{}\" rx=\"{}\" ry=\"{}\"",
+                        cx, cy, rx, ry
+                    )
+                })
+            }
+            FigureKind::Rect
+                if (curves.is_empty() && sides.len() == 4)
+                    || (curves.len() == 4 && sides.len() <= 4) =>
+            {
+                let axis_aligned = sides.iter().all(|side| match side.get_points().as_slice() {
+                    [a, b] => {
+                        approx(a.get_x_f64(), b.get_x_f64()) || approx(a.get_y_f64(), b.get_y_f64())
+                    }
+                    _ => false,
+                });
+                if !axis_aligned {
+                    return None;
+                }
+                let mut element = format!(
+                    "rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"",
+                    x, y, width, height
+                );
+                if !curves.is_empty() {
+                    element.push_str(&format!(" rx=\"{}\" ry=\"{}\"", rx, ry));
+                }
+                Some(element)
+            }
+            _ => None,
+        }
+    }
 
+    /// The radii along x and y of a quarter ellipse with its tangents along the axes, as `Figure` makes them
+    fn quarter_radii(line: &Line) -> Option<(f64, f64)> {
+        let [start, c1, c2, end] = line.get_points().as_slice() else {
+            return None;
+        };
+        let xy = |p: &Point| (p.get_x_f64(), p.get_y_f64());
+        let (p0, p1, p2, p3) = (xy(start), xy(c1), xy(c2), xy(end));
+        let (rx, ry) = ((p3.0 - p0.0).abs(), (p3.1 - p0.1).abs());
+        //The control points lie towards the corner of the box around the quarter, which corner depends on the direction
+        let towards = |p: (f64, f64), c: (f64, f64), corner: (f64, f64)| {
+            approx(c.0, p.0 + ELLIPSE_KAPPA * (corner.0 - p.0))
+                && approx(c.1, p.1 + ELLIPSE_KAPPA * (corner.1 - p.1))
+        };
+        let is_quarter = [(p3.0, p0.1), (p0.0, p3.1)]
+            .into_iter()
+            .any(|corner| towards(p0, p1, corner) && towards(p3, p2, corner));
+        (is_quarter && rx > 0. && ry > 0.).then_some((rx, ry))
+    }
+
+    fn escape(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    }
+
+    fn map_points(fig: &Figure) -> Result<String, Box<dyn Error>> {
+        if fig.get_lines().is_empty() {
+            return Err(Box::new(errors::NoLines));
+        }
+
+        //This will be the string all points are concatenated with, each contour starts with a call to M
+        let mut path_str = String::new();
+        for contour in fig.get_contours() {
+            path_str.push_str(&format!("M{}", contour[0].get_first_point()?.svg_format()));
+            SvgGenerator::map_contour(contour, &mut path_str)?;
+        }
+        Ok(path_str)
+    }
+
+    fn map_contour(contour: &[Line], path_str: &mut String) -> Result<(), Box<dyn Error>> {
         //addPoints function from design with the three cases illustrated (Contains error handling)
-        for points in lines_points {
+        for line in contour.iter().flat_map(Line::get_segments) {
+            let points = line.get_points().as_slice();
             path_str.push_str(
                 match points {
+                    [.., end] if matches!(line, Line::Arc(_)) => match line.get_arc() {
+                        Some(arc) => {
+                            let (rx, ry, rotation) = arc.axes();
+                            format!(
+                                "A{} {} {} {} {} {}",
+                                rx,
+                                ry,
+                                rotation,
+                                arc.is_large() as u8,
+                                arc.is_positive() as u8,
+                                end.svg_format()
+                            )
+                        }
+                        None => format!("L{}", end.svg_format()),
+                    },
                     [_, p2] => format!("L{}", p2.svg_format()),
                     [_, p2, p3] => format!("Q{} {}", p2.svg_format(), p3.svg_format()),
                     [_, p2, p3, p4] => format!(
@@ -146,7 +307,7 @@ impl SvgGenerator {
                 .as_str(),
             );
         }
-        Ok(path_str)
+        Ok(())
     }
 
     fn map_all_attributes(fig: &Figure) -> Result<String, Box<dyn Error>> {
@@ -205,3 +366,8 @@ impl SvgGenerator {
         }
     }
 }
+
+/// Equal up to rounding, relative to the size of the numbers
+fn approx(a: f64, b: f64) -> bool {
+    (a - b).abs() <= 1e-9 * a.abs().max(b.abs()).max(1.)
+}
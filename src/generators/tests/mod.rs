@@ -1,2 +1,4 @@
 #[cfg(test)]
 mod svggenerator;
+#[cfg(test)]
+mod pnggenerator;
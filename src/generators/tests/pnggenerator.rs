@@ -0,0 +1,113 @@
+use std::fs::{self, File};
+use crate::{
+    generators::{
+        basic_line, basic_line_with_fill, basic_line_with_stroke, errors, generator::Generator,
+        pnggenerator::PngGenerator,
+    },
+    interpreter::{data_types::{figure::Figure, figurearray::FigureArray, line::Line}, value::Value},
+};
+
+//Reads a generated png and returns (width, height, rgba bytes)
+fn read_png(file_name: &str) -> (u32, u32, Vec<u8>) {
+    let decoder = png::Decoder::new(File::open(file_name).expect("Failed to open the generated PNG file"));
+    let mut reader = decoder.read_info().unwrap();
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).unwrap();
+    buf.truncate(info.buffer_size());
+    (info.width, info.height, buf)
+}
+
+fn pixel(bytes: &[u8], width: u32, x: u32, y: u32) -> [u8; 4] {
+    let idx = ((y * width + x) * 4) as usize;
+    [bytes[idx], bytes[idx + 1], bytes[idx + 2], bytes[idx + 3]]
+}
+
+#[test]
+fn canvas_size_from_viewbox() {
+    //viewBox is "-0.5 -0.5 2 1", small drawings are scaled up to 256 pixels
+    let i1 = basic_line().get_shape().unwrap();
+
+    let mut gen = PngGenerator::new();
+    gen.generate(i1, "pngLineTest".into()).unwrap();
+    let (width, height, _) = read_png("pngLineTest.png");
+
+    assert_eq!((width, height), (256, 128));
+    assert_eq!((gen.get_width(), gen.get_height()), (256, 128));
+
+    fs::remove_file("pngLineTest.png").expect("Failed to delete the generated PNG file");
+}
+
+#[test]
+fn fill() {
+    let i1 = basic_line_with_fill().get_shape().unwrap();
+
+    let mut gen = PngGenerator::new();
+    gen.generate(i1, "pngFillTest".into()).unwrap();
+    let (width, height, bytes) = read_png("pngFillTest.png");
+
+    //Inside the curve
+    assert_eq!(pixel(&bytes, width, width / 2, height - 40), [255, 255, 255, 255]);
+    //Top left corner is outside the figure
+    assert_eq!(pixel(&bytes, width, 2, 2)[3], 0);
+    //Some pixels along the curve are only partially covered
+    assert!(bytes.chunks(4).any(|px| px[3] > 0 && px[3] < 255));
+
+    fs::remove_file("pngFillTest.png").expect("Failed to delete the generated PNG file");
+}
+
+#[test]
+fn stroke() {
+    let i1 = basic_line_with_stroke().get_shape().unwrap();
+
+    let mut gen = PngGenerator::new();
+    gen.generate(i1, "pngStrokeTest".into()).unwrap();
+    let (width, _, bytes) = read_png("pngStrokeTest.png");
+
+    //The curve starts horizontally along the bottom of the canvas
+    assert_eq!(pixel(&bytes, width, 30, 249), [255, 255, 255, 255]);
+
+    fs::remove_file("pngStrokeTest.png").expect("Failed to delete the generated PNG file");
+}
+
+#[test]
+fn empty_draw_array() {
+    let mut gen = PngGenerator::new();
+    assert!(gen.generate(FigureArray::new(), "pngEmptyTest".into()).is_err());
+}
+
+#[test]
+fn curve_through_many_points() {
+    let i1 = Value::Shape(
+        vec![(
+            vec![Line::Curved(
+                (0..5)
+                    .map(|i| (Value::Integer(i), Value::Integer(i * i)).into())
+                    .collect(),
+            )],
+            vec![].into_iter().collect(),
+        )
+            .into()]
+        .into(),
+    )
+    .get_shape()
+    .unwrap();
+
+    //Five points are drawn as a spline through all of them
+    let mut gen = PngGenerator::new();
+    gen.generate(i1, "pngSplineTest".into()).unwrap();
+    let (width, height, _) = read_png("pngSplineTest.png");
+    assert!(width > 0 && height > 0);
+
+    fs::remove_file("pngSplineTest.png").expect("Failed to delete the generated PNG file");
+}
+
+#[test]
+fn text_is_an_error() {
+    let i1 = FigureArray::from(vec![Figure::text("ab".into(), 10)]);
+
+    let mut gen = PngGenerator::new();
+    let err = gen.generate(i1, "pngTextTest".into()).unwrap_err();
+
+    assert!(err.downcast_ref::<errors::TextNotSupported>().is_some());
+    assert!(fs::metadata("pngTextTest.png").is_err());
+}
@@ -3,6 +3,11 @@ use crate::{
     generators::{
         basic_c, basic_line, basic_line_with_fill, basic_line_with_stroke, basic_line_with_thickness, basic_q, generator::Generator, svggenerator::SvgGenerator
     },
+    interpreter::{
+        data_types::{figure::Figure, figurearray::FigureArray, line::Line},
+        utils::manipulation::{rotate, translate},
+        value::Value,
+    },
 };
 
 #[test]
@@ -142,3 +147,157 @@ fn fill() {
 
     fs::remove_file("fillTest.svg").expect("Failed to delete the generated SVG file");
 }
+
+#[test]
+fn text() {
+    let i1 = FigureArray::from(vec![Figure::text("a<b".into(), 10)]);
+    let expected_content = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="-0.5 -10.5 19 11">
+<text x="0" y="0" font-family="monospace" font-size="10" textLength="18" lengthAdjust="spacingAndGlyphs" >a&lt;b</text>
+</svg>"#;
+
+    let mut gen = SvgGenerator::new();
+    let _ = gen.generate(i1, "textTest".into());
+
+    // Read the generated file
+    let actual_content =
+        fs::read_to_string("textTest.svg").expect("Failed to read the generated SVG file");
+
+    assert_eq!(
+        actual_content.trim(),
+        expected_content.trim(),
+        "Generated SVG does not match expected output"
+    );
+
+    fs::remove_file("textTest.svg").expect("Failed to delete the generated SVG file");
+}
+
+#[test]
+fn rotated_text() {
+    let i1 = rotate(
+        FigureArray::from(vec![Figure::text("ab".into(), 10)]),
+        Value::Integer(90),
+        None,
+    );
+
+    let expected_content = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0.5 -11.5 11 13">
+<text x="1" y="-11" font-family="monospace" font-size="10" textLength="12" lengthAdjust="spacingAndGlyphs" transform="rotate(90 1 -11)" >ab</text>
+</svg>"#;
+
+    let mut gen = SvgGenerator::new();
+    let _ = gen.generate(i1, "rotatedTextTest".into());
+
+    // Read the generated file
+    let actual_content = fs::read_to_string("rotatedTextTest.svg")
+        .expect("Failed to read the generated SVG file");
+
+    assert_eq!(
+        actual_content.trim(),
+        expected_content.trim(),
+        "Generated SVG does not match expected output"
+    );
+
+    fs::remove_file("rotatedTextTest.svg").expect("Failed to delete the generated SVG file");
+}
+
+#[test]
+fn contours() {
+    //An outline with a hole, each contour gets its own M
+    let corners = |points: [(i64, i64); 3]| {
+        (0..3).map(move |idx| Line::Straight(vec![points[idx].into(), points[(idx + 1) % 3].into()]))
+    };
+    let lines = corners([(0, 0), (4, 0), (0, 4)]).chain(corners([(1, 1), (1, 2), (2, 1)]));
+    let i1: FigureArray = vec![Figure::from(lines.collect::<Vec<Line>>())].into();
+    let expected_content = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="-0.5 -4.5 5 5">
+<path d="M0,0L4,0L0,-4L0,0M1,-1L1,-2L2,-1L1,-1" />
+</svg>"#;
+
+    let mut gen = SvgGenerator::new();
+    let _ = gen.generate(i1, "contoursTest".into());
+
+    // Read the generated file
+    let actual_content =
+        fs::read_to_string("contoursTest.svg").expect("Failed to read the generated SVG file");
+
+    assert_eq!(
+        actual_content.trim(),
+        expected_content.trim(),
+        "Generated SVG does not match expected output"
+    );
+
+    fs::remove_file("contoursTest.svg").expect("Failed to delete the generated SVG file");
+}
+
+#[test]
+fn tight_bounds_of_curves() {
+    //The control point of the arch is at height 20, the curve only reaches 10
+    let arch: FigureArray = vec![Figure::from(vec![
+        Line::Curved(vec![(0, 0).into(), (10, 20).into(), (20, 0).into()]),
+        Line::Straight(vec![(20, 0).into(), (0, 0).into()]),
+    ])]
+    .into();
+    assert_eq!(SvgGenerator::calc_bounds(&arch), (-0.5, -0.5, 21., 11.));
+}
+
+#[test]
+fn primitives_are_exact_until_distorted() {
+    let mut rect = Figure::rect(4., 2., 0.5);
+    let channel = |v: i64| Box::new(Value::Integer(v));
+    rect.set_attribute(("fill".into(), Value::Color(channel(255), channel(0), channel(0), channel(255))));
+    let shapes: Vec<FigureArray> = vec![
+        vec![Figure::ellipse(1., 1.)].into(),
+        translate(vec![Figure::ellipse(3., 2.)].into(), &(10, 0).into()),
+        vec![rect].into(),
+        //Rotating by a right angle keeps the rectangle, any other angle does not
+        rotate(vec![Figure::rect(4., 2., 0.)].into(), 90.into(), None),
+        rotate(vec![Figure::rect(4., 2., 0.)].into(), 30.into(), None),
+    ];
+    let mut draw_array = FigureArray::new();
+    shapes.into_iter().for_each(|shape| draw_array.extend(shape));
+    let mut gen = SvgGenerator::new();
+    gen.calc_paths(&draw_array).unwrap();
+    let elements: Vec<String> = gen.svg_string().lines().map(str::to_owned).collect();
+
+    assert_eq!(elements[1], r#"<circle cx="0" cy="0" r="1" />"#);
+    assert_eq!(elements[2], r#"<ellipse cx="10" cy="0" rx="3" ry="2" />"#);
+    assert_eq!(elements[3], r#"<rect x="0" y="0" width="4" height="2" rx="0.5" ry="0.5" fill="rgba(255,0,0,1)" />"#);
+    assert_eq!(elements[4], r#"<rect x="1" y="-1" width="2" height="4" />"#);
+    assert!(elements[5].starts_with("<path d=\"M"));
+}
+
+#[test]
+fn arc_command() {
+    //Clockwise over the top, which stays clockwise on screen once y points down
+    let dome: FigureArray = vec![Figure::from(vec![
+        Line::arc((0, 0).into(), (4, 0).into(), (2., 2.), false, true),
+        Line::Straight(vec![(4, 0).into(), (0, 0).into()]),
+    ])]
+    .into();
+    let tilted = rotate(vec![Figure::from(vec![Line::arc((0, 0).into(), (4, 0).into(), (3., 1.), true, false)])].into(), 90.into(), Some((2, 0).into()));
+    let mut draw_array = FigureArray::new();
+    draw_array.extend(dome);
+    draw_array.extend(tilted);
+    draw_array.flip_y();
+    let mut gen = SvgGenerator::new();
+    gen.calc_paths(&draw_array).unwrap();
+    let elements: Vec<String> = gen.svg_string().lines().map(str::to_owned).collect();
+
+    assert_eq!(elements[1], r#"<path d="M0,0A2 2 0 0 1 4,0L0,0" />"#);
+    assert_eq!(elements[2], r#"<path d="M2,-2A3 1 90 1 0 2,2" />"#);
+}
+
+#[test]
+fn curve_through_many_points() {
+    //(0,0)~~(1,2)~~(3,1)~~(4,4)~~(6,0) is drawn as one cubic Bézier between each pair of points
+    let curve: FigureArray = vec![Figure::from(vec![Line::Curved(vec![
+        (0, 0).into(), (1, 2).into(), (3, 1).into(), (4, 4).into(), (6, 0).into(),
+    ])])]
+    .into();
+    let mut gen = SvgGenerator::new();
+    gen.calc_paths(&curve).unwrap();
+    let path = gen.svg_string().lines().nth(1).unwrap().to_owned();
+
+    assert_eq!(path.matches('C').count(), 4);
+    for point in [" 1,2C", " 3,1C", " 4,4C", " 6,0\""] {
+        assert!(path.contains(point), "{} is not in {}", point, path);
+    }
+}
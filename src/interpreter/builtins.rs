@@ -0,0 +1,327 @@
+use std::{collections::HashMap, error::Error, f64::consts};
+
+use super::{
+    data_types::{
+        figure::Figure,
+        transform::{snap, Transform},
+    },
+    errors,
+    utils::{geometry, layout},
+    value::Value,
+};
+
+//The typechecker owns the list of builtins, the interpreter only needs their values
+pub use crate::typechecker::builtins::{is_primitive_shape, lookup, Builtin};
+
+/// Calls a builtin function of the kind `builtin`, the arguments are already typechecked,
+/// see `Builtin::call_type`. Geometry functions flatten curves to within `tolerance`.
+pub fn call(
+    builtin: Builtin,
+    name: &str,
+    args: &[Value],
+    tolerance: f64,
+) -> Result<Value, Box<dyn Error>> {
+    match builtin {
+        Builtin::Math => call_math(name, args),
+        Builtin::Array => call_array(name, args),
+        Builtin::Transform => call_transform(name, args),
+        Builtin::Layout => call_layout(name, args),
+        Builtin::Geometry => call_geometry(name, args, tolerance),
+    }
+}
+
+/// Value of a builtin constant, see `typechecker::builtins::constant_type`.
+pub fn constant(name: &str) -> Option<Value> {
+    match name {
+        "PI" => Some(Value::Float(consts::PI)),
+        "E" => Some(Value::Float(consts::E)),
+        _ => None,
+    }
+}
+
+/// Calls a builtin math function, the arguments are already typechecked,
+/// see `typechecker::builtins::math_call_type` for the result types.
+/// Angles are in degrees, `int` rounds towards zero. The results of `sin`, `cos` and `tan`
+/// are snapped to whole numbers like rotations are, and `tan` of an odd multiple of 90 is an error.
+pub fn call_math(name: &str, args: &[Value]) -> Result<Value, Box<dyn Error>> {
+    let all_int = args.iter().all(|arg| matches!(arg, Value::Integer(_)));
+    if all_int {
+        let ints = args
+            .iter()
+            .map(Value::get_int)
+            .collect::<Result<Vec<i64>, _>>()?;
+        if let Some(value) = call_int(name, &ints)? {
+            return Ok(value);
+        }
+    }
+
+    let floats = args
+        .iter()
+        .map(Value::get_float)
+        .collect::<Result<Vec<f64>, _>>()?;
+    let float = match (name, floats.as_slice()) {
+        ("sqrt", [x]) if *x < 0.0 => {
+            return Err(errors::MathError(format!("sqrt of negative number {}", x)).into())
+        }
+        ("sqrt", [x]) => x.sqrt(),
+        ("pow", [x, y]) => x.powf(*y),
+        ("abs", [x]) => x.abs(),
+        ("min", [x, y]) => x.min(*y),
+        ("max", [x, y]) => x.max(*y),
+        ("int", [x]) => return to_int(name, x.trunc()),
+        ("float", [x]) => *x,
+        ("floor", [x]) => return to_int(name, x.floor()),
+        ("ceil", [x]) => return to_int(name, x.ceil()),
+        ("round", [x]) => return to_int(name, x.round()),
+        ("sin", [x]) => snap(x.to_radians().sin()),
+        ("cos", [x]) => snap(x.to_radians().cos()),
+        ("tan", [x]) if snap((x - 90.) / 180.).fract() == 0. => {
+            return Err(errors::MathError(format!("tan of {} is undefined", x)).into())
+        }
+        ("tan", [x]) => snap(x.to_radians().tan()),
+        ("atan2", [y, x]) => y.atan2(*x).to_degrees(),
+        ("hypot", [x, y]) => x.hypot(*y),
+        ("clamp", [_, low, high]) if low > high || low.is_nan() || high.is_nan() => {
+            return Err(errors::MathError(format!(
+                "clamp with lower bound {} above upper bound {}",
+                low, high
+            ))
+            .into())
+        }
+        ("clamp", [x, low, high]) => x.clamp(*low, *high),
+        ("lerp", [a, b, t]) => a + (b - a) * t,
+        _ => return Err(errors::MathError(format!("unknown function {}", name)).into()),
+    };
+
+    if float.is_finite() {
+        Ok(Value::Float(float))
+    } else {
+        Err(errors::MathError(format!("{} gave {}", name, float)).into())
+    }
+}
+
+/// The functions that stay ints when given only ints, `None` for the rest
+fn call_int(name: &str, ints: &[i64]) -> Result<Option<Value>, Box<dyn Error>> {
+    let int = match (name, ints) {
+        ("abs", [x]) => x.checked_abs(),
+        ("min", [x, y]) => Some(*x.min(y)),
+        ("max", [x, y]) => Some(*x.max(y)),
+        ("clamp", [_, low, high]) if low > high => {
+            return Err(errors::MathError(format!(
+                "clamp with lower bound {} above upper bound {}",
+                low, high
+            ))
+            .into())
+        }
+        ("clamp", [x, low, high]) => Some(*x.clamp(low, high)),
+        ("pow", [_, y]) if *y < 0 => {
+            return Err(errors::MathError(format!(
+                "pow of int with negative exponent {}, use a float",
+                y
+            ))
+            .into())
+        }
+        ("pow", [x, y]) => u32::try_from(*y).ok().and_then(|y| x.checked_pow(y)),
+        ("int" | "floor" | "ceil" | "round", [x]) => Some(*x),
+        _ => return Ok(None),
+    };
+
+    int.map(|int| Some(Value::Integer(int)))
+        .ok_or_else(|| errors::MathError(format!("{} overflowed", name)).into())
+}
+
+fn to_int(name: &str, float: f64) -> Result<Value, Box<dyn Error>> {
+    if float.is_finite() && float >= i64::MIN as f64 && float < i64::MAX as f64 {
+        Ok(Value::Integer(float as i64))
+    } else {
+        Err(errors::MathError(format!("{} of {} does not fit in an int", name, float)).into())
+    }
+}
+
+/// Calls a builtin transform function, the arguments are already typechecked,
+/// see `typechecker::builtins::transform_call_type`. Angles are in degrees.
+pub fn call_transform(name: &str, args: &[Value]) -> Result<Value, Box<dyn Error>> {
+    let transform = match (name, args) {
+        ("translation", [Value::Point(p)]) => Transform::translate(p.get_x_f64(), p.get_y_f64()),
+        ("rotation", [degrees]) => Transform::rotate(degrees.get_float()?),
+        ("scaling", [Value::Point(p)]) => Transform::scale(p.get_x_f64(), p.get_y_f64()),
+        ("scaling", [factor]) => Transform::scale(factor.get_float()?, factor.get_float()?),
+        ("skew", [x, y]) => {
+            let (x, y) = (x.get_float()?, y.get_float()?);
+            //The tangent of a right angle is infinite
+            if [x, y]
+                .iter()
+                .any(|v| (v.rem_euclid(180.) - 90.).abs() < 1e-9)
+            {
+                return Err(errors::MathError(format!("skew by ({}, {}) degrees", x, y)).into());
+            }
+            Transform::skew(x, y)
+        }
+        _ => return Err(errors::MathError(format!("unknown function {}", name)).into()),
+    };
+
+    if transform.is_finite() {
+        Ok(Value::Transform(transform))
+    } else {
+        Err(errors::MathError(format!("{} gave {}", name, transform)).into())
+    }
+}
+
+/// Calls a builtin layout function, the arguments are already typechecked,
+/// see `typechecker::builtins::layout_call_type`.
+pub fn call_layout(name: &str, args: &[Value]) -> Result<Value, Box<dyn Error>> {
+    let shapes = match args.first() {
+        Some(shapes) => shapes
+            .get_array()?
+            .into_iter()
+            .map(Value::get_shape)
+            .collect::<Result<Vec<_>, _>>()?,
+        None => Vec::new(),
+    };
+    let shape = match (name, args) {
+        ("row", [_, gap, align]) => layout::row(shapes, gap.get_float()?, align.get_string()?)?,
+        ("column", [_, gap, align]) => {
+            layout::column(shapes, gap.get_float()?, align.get_string()?)?
+        }
+        ("grid", [_, columns, gap]) => layout::grid(shapes, columns.get_int()?, gap.get_float()?)?,
+        ("distribute", [_, width]) => layout::distribute(shapes, width.get_float()?)?,
+        _ => return Err(errors::MathError(format!("unknown function {}", name)).into()),
+    };
+    Ok(Value::Shape(shape))
+}
+
+/// Calls a builtin geometry function, the arguments are already typechecked,
+/// see `typechecker::builtins::geometry_call_type`. Curves are flattened to within `tolerance`.
+pub fn call_geometry(name: &str, args: &[Value], tolerance: f64) -> Result<Value, Box<dyn Error>> {
+    match (name, args) {
+        ("contains", [Value::Shape(shape), Value::Point(point)]) => {
+            Ok(Value::Boolean(geometry::contains(shape, point, tolerance)))
+        }
+        ("contains", _) => call_array(name, args),
+        ("overlaps", [Value::Shape(a), Value::Shape(b)]) => {
+            Ok(Value::Boolean(geometry::overlaps(a, b, tolerance)))
+        }
+        ("distance", [Value::Point(p1), Value::Point(p2)]) => {
+            Ok(Value::Float(geometry::distance(p1, p2)))
+        }
+        _ => Err(errors::MathError(format!("unknown function {}", name)).into()),
+    }
+}
+
+/// Makes a builtin shape from the arguments of its shape call, they are already typechecked,
+/// see `typechecker::builtins::primitive_parameters`. Sizes must be positive.
+pub fn call_primitive(name: &str, args: &HashMap<String, Value>) -> Result<Value, Box<dyn Error>> {
+    let size = |parameter: &str| -> Result<f64, Box<dyn Error>> {
+        let value = args
+            .get(parameter)
+            .map_or(Ok(0.), |value| value.get_float())?;
+        if value > 0. || (parameter == "corner_radius" && value == 0.) {
+            Ok(value)
+        } else {
+            Err(errors::PrimitiveSizeNotPositive(name.into(), parameter.into(), value).into())
+        }
+    };
+    let mut fig = match name {
+        "ellipse" => Figure::ellipse(size("rx")?, size("ry")?),
+        "circle" => Figure::ellipse(size("radius")?, size("radius")?),
+        "rect" => Figure::rect(size("width")?, size("height")?, size("corner_radius")?),
+        "regular_polygon" => {
+            let sides = args["sides"].get_int()?;
+            if sides < 3 {
+                return Err(errors::PrimitiveSidesInvalid(sides).into());
+            }
+            Figure::regular_polygon(sides, size("radius")?)
+        }
+        _ => return Err(errors::MathError(format!("unknown shape {}", name)).into()),
+    };
+    for attribute in ["fill", "stroke", "thickness"] {
+        if let Some(value) = args.get(attribute) {
+            fig.set_attribute((attribute.into(), value.clone()));
+        }
+    }
+    Ok(Value::Shape(vec![fig].into()))
+}
+
+/// Longest array `fill` makes, so a huge count is an error instead of running out of memory
+const ARRAY_LENGTH_LIMIT: usize = 10_000_000;
+
+/// Calls a builtin array function, the arguments are already typechecked,
+/// see `typechecker::builtins::array_call_type`.
+/// Indices outside the array give an `ArrayOutOfBoundsWithNumbers` error.
+pub fn call_array(name: &str, args: &[Value]) -> Result<Value, Box<dyn Error>> {
+    let array = match (name, args) {
+        ("push", [array, value]) => {
+            let mut array = array.get_array()?;
+            array.push(value.clone());
+            array
+        }
+        ("remove", [array, index]) => {
+            let mut array = array.get_array()?;
+            let index = checked_index(&array, index.get_int()?, array.len())?;
+            array.remove(index);
+            array
+        }
+        ("insert", [array, index, value]) => {
+            let mut array = array.get_array()?;
+            let index = checked_index(&array, index.get_int()?, array.len() + 1)?;
+            array.insert(index, value.clone());
+            array
+        }
+        ("concat", [lhs, rhs]) => {
+            let mut array = lhs.get_array()?;
+            array.extend(rhs.get_array()?);
+            array
+        }
+        ("slice", [array, from, to]) => {
+            let array = array.get_array()?;
+            let to = checked_index(&array, to.get_int()?, array.len() + 1)?;
+            let from = checked_index(&array, from.get_int()?, to + 1)?;
+            array[from..to].to_vec()
+        }
+        ("reverse", [array]) => {
+            let mut array = array.get_array()?;
+            array.reverse();
+            array
+        }
+        ("contains", [array, value]) => {
+            return Ok(Value::Boolean(array.get_array()?.contains(value)))
+        }
+        ("index_of", [array, value]) => {
+            let index = array
+                .get_array()?
+                .iter()
+                .position(|element| element == value);
+            return Ok(Value::Integer(index.map_or(-1, |index| index as i64)));
+        }
+        ("sort", [array]) => {
+            let mut array = array.get_array()?;
+            array.sort_by(|lhs, rhs| match (lhs, rhs) {
+                (Value::Integer(lhs), Value::Integer(rhs)) => lhs.cmp(rhs),
+                _ => lhs
+                    .get_float()
+                    .unwrap_or_default()
+                    .total_cmp(&rhs.get_float().unwrap_or_default()),
+            });
+            array
+        }
+        ("fill", [count, value]) => {
+            let count = count.get_int()?;
+            let length = usize::try_from(count)
+                .map_err(|_| errors::ArrayOutOfBoundsWithNumbers(0, count))?;
+            if length > ARRAY_LENGTH_LIMIT {
+                return Err(errors::ArrayLengthLimitReached(count, ARRAY_LENGTH_LIMIT).into());
+            }
+            vec![value.clone(); length]
+        }
+        _ => return Err(errors::InvalidArrayAccess(name.to_owned()).into()),
+    };
+    Ok(Value::Array(array))
+}
+
+/// `index` as a position in `array`, it must be below `end`
+fn checked_index(array: &[Value], index: i64, end: usize) -> Result<usize, Box<dyn Error>> {
+    usize::try_from(index)
+        .ok()
+        .filter(|index| *index < end)
+        .ok_or_else(|| errors::ArrayOutOfBoundsWithNumbers(array.len(), index).into())
+}
@@ -1,12 +1,32 @@
 use crate::interpreter::errors;
-use std::{collections::HashMap, error::Error};
+use std::{collections::HashMap, error::Error, f64::consts};
 use super::{line::Line, point::Point};
 use crate::interpreter::value::Value;
 
+/// Average glyph width relative to the font size, matching monospace fonts
+const TEXT_CHAR_WIDTH: f64 = 0.6;
+pub const TEXT_DEFAULT_SIZE: i64 = 16;
+/// How far along the tangents the control points of a quarter ellipse are, 4/3 * (sqrt(2) - 1)
+pub const ELLIPSE_KAPPA: f64 = 0.552_284_749_830_793_6;
+
+/// What a figure was made as. Generators can draw some kinds better than by following the lines,
+/// the lines are always kept up to date so every kind can still be measured and manipulated as a path.
+#[derive(Debug, PartialEq, Clone)]
+pub enum FigureKind {
+    Path,
+    /// Text with the estimated box of `Figure::text` as its lines
+    Text(String),
+    /// An ellipse made by `Figure::ellipse`
+    Ellipse,
+    /// A rectangle made by `Figure::rect`
+    Rect,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Figure {
     lines: Vec<Line>,
     attributes: HashMap<String, Value>,
+    kind: FigureKind,
 }
 
 impl From<Vec<Line>> for Figure {
@@ -31,9 +51,131 @@ impl Figure {
         Self {
             lines: Vec::new(),
             attributes: HashMap::new(),
+            kind: FigureKind::Path,
+        }
+    }
+
+    /// A text figure. Text cannot be measured before it is rendered, so its lines are an estimated box:
+    /// along the baseline from the start of the text, up the right side, back along the top and down the left side.
+    /// Generators lay out the text from this box, so it follows the figure when it is placed, scaled or rotated.
+    pub fn text(content: String, size: i64) -> Self {
+        let width = content.chars().count() as f64 * size as f64 * TEXT_CHAR_WIDTH;
+        let height = size as f64;
+        let corners: Vec<Point> = vec![
+            (0., 0.).into(),
+            (width, 0.).into(),
+            (width, height).into(),
+            (0., height).into(),
+        ];
+
+        let mut fig = Figure::new();
+        for (idx, corner) in corners.iter().enumerate() {
+            let next = corners[(idx + 1) % corners.len()].clone();
+            fig.push_line_after(Line::Straight(vec![corner.clone(), next]));
+        }
+        fig.kind = FigureKind::Text(content);
+        fig
+    }
+
+    pub fn get_text(&self) -> Option<&str> {
+        match &self.kind {
+            FigureKind::Text(content) => Some(content),
+            _ => None,
         }
     }
 
+    pub fn get_kind(&self) -> &FigureKind {
+        &self.kind
+    }
+
+    /// An ellipse centered at the origin, made of four quarter ellipses starting on the positive x-axis.
+    /// It is marked as a primitive so generators can draw it exactly while it keeps its shape.
+    pub fn ellipse(rx: f64, ry: f64) -> Self {
+        let anchors: Vec<Point> = vec![
+            (rx, 0.).into(),
+            (0., ry).into(),
+            (-rx, 0.).into(),
+            (0., -ry).into(),
+        ];
+
+        let mut fig = Figure::new();
+        for (idx, start) in anchors.iter().enumerate() {
+            let end = &anchors[(idx + 1) % anchors.len()];
+            let corner = (
+                start.get_x_f64() + end.get_x_f64(),
+                start.get_y_f64() + end.get_y_f64(),
+            );
+            fig.push_line_after(Figure::quarter_ellipse(start, end, corner));
+        }
+        fig.kind = FigureKind::Ellipse;
+        fig
+    }
+
+    /// A rectangle from the origin to (width, height), with corners rounded by `corner_radius`.
+    /// Like SVG the radius is at most half the width and half the height.
+    /// It is marked as a primitive so generators can draw it exactly while it keeps its shape.
+    pub fn rect(width: f64, height: f64, corner_radius: f64) -> Self {
+        let r = corner_radius.min(width / 2.).min(height / 2.);
+        let corners = [(width, 0.), (width, height), (0., height), (0., 0.)];
+        //Where each side starts and ends, the corners are rounded between them
+        let sides: Vec<(Point, Point)> = vec![
+            ((r, 0.).into(), (width - r, 0.).into()),
+            ((width, r).into(), (width, height - r).into()),
+            ((width - r, height).into(), (r, height).into()),
+            ((0., height - r).into(), (0., r).into()),
+        ];
+
+        let mut fig = Figure::new();
+        for (idx, (start, end)) in sides.iter().enumerate() {
+            if start != end {
+                fig.push_line_after(Line::Straight(vec![start.clone(), end.clone()]));
+            }
+            if r > 0. {
+                let next = &sides[(idx + 1) % sides.len()].0;
+                fig.push_line_after(Figure::quarter_ellipse(end, next, corners[idx]));
+            }
+        }
+        fig.kind = FigureKind::Rect;
+        fig
+    }
+
+    /// A polygon with `sides` equal sides and its corners `radius` from the origin, the first corner at the top
+    pub fn regular_polygon(sides: i64, radius: f64) -> Self {
+        //Corners on the axes should be exactly on them
+        let snap = |v: f64| if v.abs() < 1e-12 { 0. } else { v };
+        let corners: Vec<Point> = (0..sides)
+            .map(|idx| {
+                let angle = consts::FRAC_PI_2 + consts::TAU * idx as f64 / sides as f64;
+                (radius * snap(angle.cos()), radius * snap(angle.sin())).into()
+            })
+            .collect();
+
+        let mut fig = Figure::new();
+        for (idx, corner) in corners.iter().enumerate() {
+            let next = corners[(idx + 1) % corners.len()].clone();
+            fig.push_line_after(Line::Straight(vec![corner.clone(), next]));
+        }
+        fig
+    }
+
+    /// The quarter ellipse from `start` to `end` bulging towards `corner`
+    fn quarter_ellipse(start: &Point, end: &Point, corner: (f64, f64)) -> Line {
+        let towards_corner = |p: &Point| -> Point {
+            let (x, y) = (p.get_x_f64(), p.get_y_f64());
+            (
+                x + ELLIPSE_KAPPA * (corner.0 - x),
+                y + ELLIPSE_KAPPA * (corner.1 - y),
+            )
+                .into()
+        };
+        Line::Curved(vec![
+            start.clone(),
+            towards_corner(start),
+            towards_corner(end),
+            end.clone(),
+        ])
+    }
+
     pub fn set_attribute(&mut self, attribute: (String, Value)) {
         self.attributes.insert(attribute.0, attribute.1);
     }
@@ -69,37 +211,51 @@ impl Figure {
         &mut self.lines
     }
 
-    pub fn get_max_x(&self) -> Value { 
-        self.lines.iter().flat_map(
-            |line| line.get_points().iter().map(|p| p.get_x())
-        ).max().unwrap_or(&Value::Integer(0)).clone()
+    /// Tight bounds of the figure, curves are measured where they turn instead of at their control points.
+    pub fn get_max_x(&self) -> Value {
+        self.extreme_points().map(|p| p.get_x().clone()).max().unwrap_or(Value::Integer(0))
+    }
+
+    pub fn get_min_x(&self) -> Value {
+        self.extreme_points().map(|p| p.get_x().clone()).min().unwrap_or(Value::Integer(0))
     }
 
+    pub fn get_max_y(&self) -> Value {
+        self.extreme_points().map(|p| p.get_y().clone()).max().unwrap_or(Value::Integer(0))
+    }
 
-    pub fn get_min_x(&self) -> Value { 
-        self.lines.iter().flat_map(
-            |line| line.get_points().iter().map(|p| p.get_x())
-        ).min().unwrap_or(&Value::Integer(0)).clone()
+    pub fn get_min_y(&self) -> Value {
+        self.extreme_points().map(|p| p.get_y().clone()).min().unwrap_or(Value::Integer(0))
     }
 
-    pub fn get_max_y(&self) -> Value { 
-        self.lines.iter().flat_map(
-            |line| line.get_points().iter().map(|p| p.get_y())
-        ).max().unwrap_or(&Value::Integer(0)).clone()
+    fn extreme_points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.lines.iter().flat_map(|line| line.get_extreme_points())
     }
 
-    pub fn get_min_y(&self) -> Value { 
-        self.lines.iter().flat_map(
-            |line| line.get_points().iter().map(|p| p.get_y())
-        ).min().unwrap_or(&Value::Integer(0)).clone()
+    /// Bounds of every stored point including the control points of curves, as (min_x, min_y, max_x, max_y).
+    /// Looser than `get_min_x` and the others, but never smaller.
+    pub fn get_control_hull(&self) -> (Value, Value, Value, Value) {
+        let points: Vec<Point> = self
+            .lines
+            .iter()
+            .flat_map(|line| line.get_hull_points())
+            .collect();
+        let points = || points.iter();
+        let bound = |values: Option<&Value>| values.cloned().unwrap_or(Value::Integer(0));
+        (
+            bound(points().map(|p| p.get_x()).min()),
+            bound(points().map(|p| p.get_y()).min()),
+            bound(points().map(|p| p.get_x()).max()),
+            bound(points().map(|p| p.get_y()).max()),
+        )
     }
 
     pub fn get_height(&self) -> Value {
-        self.get_max_x() - self.get_min_x()
+        self.get_max_y() - self.get_min_y()
     }
 
     pub fn get_width(&self) -> Value {
-        self.get_max_y() - self.get_min_y()
+        self.get_max_x() - self.get_min_x()
     }
 
     pub fn get_last_line(&mut self) -> Result<&mut Line, Box<dyn Error>> {
@@ -138,8 +294,40 @@ impl Figure {
         .get_last_point()
     }
 
+    /// True if every contour ends where it starts
     pub fn is_closed(&self) -> Result<bool, Box<dyn Error>> {
-        Ok(self.get_first_point()? ==  self.get_last_point()? )
+        if self.lines.is_empty() {
+            return Err(errors::NoLinesInFigure.into());
+        }
+        for contour in self.get_contours() {
+            let (first, last) = (&contour[0], &contour[contour.len() - 1]);
+            if first.get_first_point()? != last.get_last_point()? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// The lines split into contours, a new contour starts where a line does not continue from the previous one.
+    /// Most figures are a single contour, figures made by `union`, `subtract` and the other shape operations
+    /// can have more, such as an outline and a hole.
+    pub fn get_contours(&self) -> Vec<&[Line]> {
+        let mut contours = Vec::new();
+        let mut start = 0;
+        for idx in 1..self.lines.len() {
+            let continues = match (self.lines[idx - 1].get_last_point(), self.lines[idx].get_first_point()) {
+                (Ok(last), Ok(first)) => last.approx_eq(first, 1e-9),
+                _ => true,
+            };
+            if !continues {
+                contours.push(&self.lines[start..idx]);
+                start = idx;
+            }
+        }
+        if start < self.lines.len() {
+            contours.push(&self.lines[start..]);
+        }
+        contours
     }
     
 }
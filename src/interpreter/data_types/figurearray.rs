@@ -56,6 +56,18 @@ impl FigureArray {
             .unwrap_or(0.into())
     }
 
+    /// Bounds including the control points of curves, see `Figure::get_control_hull`
+    pub fn control_hull(&self) -> (Value, Value, Value, Value) {
+        let hulls: Vec<_> = self.0.iter().map(|fig| fig.get_control_hull()).collect();
+        let bound = |value: Option<&Value>| value.cloned().unwrap_or(0.into());
+        (
+            bound(hulls.iter().map(|hull| &hull.0).min()),
+            bound(hulls.iter().map(|hull| &hull.1).min()),
+            bound(hulls.iter().map(|hull| &hull.2).max()),
+            bound(hulls.iter().map(|hull| &hull.3).max()),
+        )
+    }
+
     pub fn flip_y(&mut self) {
         self.0.iter_mut().for_each(|fig| {
             fig.get_mut_lines().iter_mut().for_each(|l| {
@@ -1,4 +1,7 @@
-use std::error::Error;
+use std::{
+    error::Error,
+    f64::consts::{PI, TAU},
+};
 
 use crate::interpreter::errors;
 
@@ -7,50 +10,357 @@ use super::point::Point;
 #[derive(Debug, PartialEq, Clone)]
 pub enum Line {
     Straight(Vec<Point>),
-    Curved(Vec<Point>)
+    Curved(Vec<Point>),
+    /// An elliptical arc as `[start, center, u, v, end]`, see `EllipseArc`.
+    /// Its points are never extended, the path operators start a new line after an arc.
+    Arc(Vec<Point>),
 }
 impl Line {
     pub fn get_points(&self) -> &Vec<Point> { match self {
         Line::Straight(points) | 
-        Line::Curved(points) => &points,
+        Line::Curved(points) |
+        Line::Arc(points) => &points,
     }  }
 
     pub fn push_point(&mut self, val: Point) { 
         match self {
             Line::Straight(points) | 
-            Line::Curved(points) => points.push(val),
+            Line::Curved(points) |
+            Line::Arc(points) => points.push(val),
         } 
     }
     pub fn get_last_point(&self) -> Result<&Point, Box<dyn Error>> {
         match self {
             Line::Straight(points) | 
-            Line::Curved(points) => points.last().ok_or_else(|| errors::NoLinesInFigure.into())
+            Line::Curved(points) |
+            Line::Arc(points) => points.last().ok_or_else(|| errors::NoLinesInFigure.into())
         }
     }
     pub fn get_first_point(&self) -> Result<&Point, Box<dyn Error>> {
         match self {
             Line::Straight(points) | 
-            Line::Curved(points) => points.first().ok_or_else(|| errors::NoLinesInFigure.into())
+            Line::Curved(points) |
+            Line::Arc(points) => points.first().ok_or_else(|| errors::NoLinesInFigure.into())
         }
     }
     pub fn insert_point_first(&mut self, p: Point) {
         match self {
             Line::Straight(points) | 
-            Line::Curved(points) => points.insert(0, p),
+            Line::Curved(points) |
+            Line::Arc(points) => points.insert(0, p),
         }
     }
     pub fn insert_point_last(&mut self, p: Point) {
         match self {
             Line::Straight(points) | 
-            Line::Curved(points) => points.push(p)
+            Line::Curved(points) |
+            Line::Arc(points) => points.push(p)
         }
     }
 
     pub fn get_mut_points(&mut self) -> &mut Vec<Point> {
         match self {
             Line::Straight(points) |
-            Line::Curved(points) => points,
+            Line::Curved(points) |
+            Line::Arc(points) => points,
         }
     }
+
+    /// The arc from `start` to `end` on an axis-aligned ellipse with the radii `radii`, picked the way SVG's `A` command does:
+    /// `large` takes the longer of the two possible arcs and `clockwise` the direction it is drawn in.
+    /// Radii too small to reach `end` are scaled up and a zero radius gives a straight line, also like SVG.
+    pub fn arc(start: Point, end: Point, radii: (f64, f64), large: bool, clockwise: bool) -> Line {
+        let (p0, p1) = ((start.get_x_f64(), start.get_y_f64()), (end.get_x_f64(), end.get_y_f64()));
+        let (mut rx, mut ry) = (radii.0.abs(), radii.1.abs());
+        if rx < 1e-12 || ry < 1e-12 || p0 == p1 {
+            return Line::Straight(vec![start, end]);
+        }
+
+        //SVG's conversion from end points to center, y points up here so clockwise turns towards negative angles
+        let (hx, hy) = ((p0.0 - p1.0) / 2., (p0.1 - p1.1) / 2.);
+        let reach = (hx / rx).powi(2) + (hy / ry).powi(2);
+        if reach > 1. {
+            rx *= reach.sqrt();
+            ry *= reach.sqrt();
+        }
+        let (rx2, ry2) = (rx * rx, ry * ry);
+        let sign = if large == clockwise { 1. } else { -1. };
+        let coef = sign
+            * ((rx2 * ry2 - rx2 * hy * hy - ry2 * hx * hx) / (rx2 * hy * hy + ry2 * hx * hx))
+                .max(0.)
+                .sqrt();
+        let center = (
+            coef * rx * hy / ry + (p0.0 + p1.0) / 2.,
+            -coef * ry * hx / rx + (p0.1 + p1.1) / 2.,
+        );
+        let v = if clockwise { -ry } else { ry };
+
+        Line::Arc(vec![
+            start,
+            center.into(),
+            (center.0 + rx, center.1).into(),
+            (center.0, center.1 + v).into(),
+            end,
+        ])
+    }
+
+    /// The ellipse and angles of an arc, `None` for other lines
+    pub fn get_arc(&self) -> Option<EllipseArc> {
+        match self {
+            Line::Arc(points) => EllipseArc::new(points),
+            _ => None,
+        }
+    }
+
+    /// The line as pieces that are drawn directly. A curve through more than four points is a
+    /// Catmull-Rom spline through all of them, returned as one cubic Bézier per pair of neighbouring points,
+    /// every other line is returned as it is. The control points are fixed sums of the points of the curve,
+    /// so moving, rotating or scaling the curve and then converting it gives the same Béziers as the other way round.
+    pub fn get_segments(&self) -> Vec<Line> {
+        match self {
+            Line::Curved(points) if points.len() > 4 => catmull_rom(points),
+            _ => vec![self.clone()],
+        }
+    }
+
+    /// Points whose bounds are never smaller than the line: the points of straight lines and curves,
+    /// and for an arc its end points and the corners of the parallelogram around its ellipse.
+    pub fn get_hull_points(&self) -> Vec<Point> {
+        match self.get_arc() {
+            Some(arc) => {
+                let (c, a, b) = (arc.center, arc.a, arc.b);
+                let mut points: Vec<Point> = [(1., 1.), (1., -1.), (-1., -1.), (-1., 1.)]
+                    .iter()
+                    .map(|(i, j)| (c.0 + i * a.0 + j * b.0, c.1 + i * a.1 + j * b.1).into())
+                    .collect();
+                points.push(arc.point(0.).into());
+                points.push(arc.point(1.).into());
+                points
+            }
+            None => self
+                .get_segments()
+                .iter()
+                .flat_map(|segment| segment.get_points().clone())
+                .collect(),
+        }
+    }
+
+    /// The points that decide the tight bounds of the line: its end points and, for quadratic and cubic curves,
+    /// the points where the curve turns in x or y. The control points of a curve are usually outside of it,
+    /// `get_hull_points` gives those for the looser control hull bounds. Arcs turn where their ellipse does
+    /// and longer curves where their Béziers do.
+    pub fn get_extreme_points(&self) -> Vec<Point> {
+        let points = match self {
+            Line::Curved(points) if matches!(points.len(), 3 | 4) => points,
+            Line::Curved(points) if points.len() > 4 => {
+                return self
+                    .get_segments()
+                    .iter()
+                    .flat_map(|segment| segment.get_extreme_points())
+                    .collect()
+            }
+            Line::Arc(points) => {
+                return match self.get_arc() {
+                    Some(arc) => arc.extreme_points().into_iter().map(Point::from).collect(),
+                    None => points.clone(),
+                }
+            }
+            Line::Straight(points) | Line::Curved(points) => return points.clone(),
+        };
+        let controls: Vec<(f64, f64)> = points.iter().map(|p| (p.get_x_f64(), p.get_y_f64())).collect();
+        let xs: Vec<f64> = controls.iter().map(|c| c.0).collect();
+        let ys: Vec<f64> = controls.iter().map(|c| c.1).collect();
+
+        let mut extremes = vec![points[0].clone(), points[points.len() - 1].clone()];
+        for t in turning_points(&xs).into_iter().chain(turning_points(&ys)) {
+            extremes.push(bezier_point(&controls, t).into());
+        }
+        extremes
+    }
 }
 
+/// The parameters in (0, 1) where the derivative of a quadratic or cubic Bézier coordinate is zero
+fn turning_points(c: &[f64]) -> Vec<f64> {
+    //The derivative as a polynomial a*t^2 + b*t + c, up to a constant factor
+    let (a, b, c) = match c {
+        [p0, p1, p2] => (0., p0 - 2. * p1 + p2, p1 - p0),
+        [p0, p1, p2, p3] => {
+            let (d0, d1, d2) = (p1 - p0, p2 - p1, p3 - p2);
+            (d0 - 2. * d1 + d2, 2. * (d1 - d0), d0)
+        }
+        _ => return Vec::new(),
+    };
+    let roots = if a.abs() < 1e-12 {
+        if b.abs() < 1e-12 {
+            Vec::new()
+        } else {
+            vec![-c / b]
+        }
+    } else {
+        let discriminant = b * b - 4. * a * c;
+        if discriminant < 0. {
+            Vec::new()
+        } else {
+            let root = discriminant.sqrt();
+            vec![(-b + root) / (2. * a), (-b - root) / (2. * a)]
+        }
+    };
+    roots.into_iter().filter(|t| *t > 0. && *t < 1.).collect()
+}
+
+/// Cubic Béziers through every point of a uniform Catmull-Rom spline. The tangent at a point is half of the
+/// step between its neighbours, the ends use themselves as missing neighbour unless the curve is closed,
+/// then the tangent wraps around so the curve is smooth where it meets itself.
+fn catmull_rom(points: &[Point]) -> Vec<Line> {
+    let xy: Vec<(f64, f64)> = points
+        .iter()
+        .map(|p| (p.get_x_f64(), p.get_y_f64()))
+        .collect();
+    let last = xy.len() - 1;
+    let closed = points[0].approx_eq(&points[last], 1e-9);
+    let neighbour = |idx: isize| -> (f64, f64) {
+        match idx {
+            -1 if closed => xy[last - 1],
+            -1 => xy[0],
+            idx if idx as usize > last && closed => xy[1],
+            idx => xy[(idx as usize).min(last)],
+        }
+    };
+    let tangent = |idx: usize| {
+        let (before, after) = (neighbour(idx as isize - 1), neighbour(idx as isize + 1));
+        ((after.0 - before.0) / 6., (after.1 - before.1) / 6.)
+    };
+    (0..last)
+        .map(|idx| {
+            let (p1, p2) = (xy[idx], xy[idx + 1]);
+            let (t1, t2) = (tangent(idx), tangent(idx + 1));
+            Line::Curved(vec![
+                points[idx].clone(),
+                (p1.0 + t1.0, p1.1 + t1.1).into(),
+                (p2.0 - t2.0, p2.1 - t2.1).into(),
+                points[idx + 1].clone(),
+            ])
+        })
+        .collect()
+}
+
+/// The point at `t` on a bezier curve of any degree, from 0 at the first control point to 1 at the last.
+/// Uses De Casteljau's algorithm.
+pub(crate) fn bezier_point(controls: &[(f64, f64)], t: f64) -> (f64, f64) {
+    let mut points = controls.to_vec();
+    while points.len() > 1 {
+        points = points
+            .windows(2)
+            .map(|pair| {
+                (
+                    pair[0].0 + (pair[1].0 - pair[0].0) * t,
+                    pair[0].1 + (pair[1].1 - pair[0].1) * t,
+                )
+            })
+            .collect();
+    }
+    points[0]
+}
+
+/// An elliptical arc, the ellipse is `center + cos(t) * a + sin(t) * b` and the arc runs from the angle `start`
+/// through `span` radians as t grows. `a` and `b` are any two conjugate half diameters, after a rotation or a skew
+/// they are no longer the axes of the ellipse.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct EllipseArc {
+    center: (f64, f64),
+    a: (f64, f64),
+    b: (f64, f64),
+    start: f64,
+    span: f64,
+}
+
+impl EllipseArc {
+    /// Reads the arc from `[start, center, u, v, end]` with `a = u - center` and `b = v - center`
+    fn new(points: &[Point]) -> Option<Self> {
+        let [start, center, u, v, end] = points else {
+            return None;
+        };
+        let xy = |p: &Point| (p.get_x_f64(), p.get_y_f64());
+        let center = xy(center);
+        let (a, b) = (
+            (u.get_x_f64() - center.0, u.get_y_f64() - center.1),
+            (v.get_x_f64() - center.0, v.get_y_f64() - center.1),
+        );
+        let det = a.0 * b.1 - b.0 * a.1;
+        if det.abs() < 1e-12 {
+            return None;
+        }
+        //The angle of a point is found by undoing a and b
+        let angle = |p: (f64, f64)| {
+            let (dx, dy) = (p.0 - center.0, p.1 - center.1);
+            ((a.0 * dy - a.1 * dx) / det).atan2((b.1 * dx - b.0 * dy) / det)
+        };
+        let start = angle(xy(start));
+        let span = (angle(xy(end)) - start).rem_euclid(TAU);
+        Some(Self {
+            center,
+            a,
+            b,
+            start,
+            span,
+        })
+    }
+
+    /// The point a fraction `t` along the arc, 0 is the start and 1 the end
+    pub fn point(&self, t: f64) -> (f64, f64) {
+        self.at_angle(self.start + t * self.span)
+    }
+
+    fn at_angle(&self, angle: f64) -> (f64, f64) {
+        let (cos, sin) = (angle.cos(), angle.sin());
+        (
+            self.center.0 + cos * self.a.0 + sin * self.b.0,
+            self.center.1 + cos * self.a.1 + sin * self.b.1,
+        )
+    }
+
+    /// The radii along the axes of the ellipse and the angle of its first axis in degrees, like SVG's `A` command.
+    /// Found from the singular values of the matrix with `a` and `b` as columns.
+    pub fn axes(&self) -> (f64, f64, f64) {
+        let (a, b) = (self.a, self.b);
+        let (e, f) = ((a.0 + b.1) / 2., (a.0 - b.1) / 2.);
+        let (g, h) = ((a.1 + b.0) / 2., (a.1 - b.0) / 2.);
+        let (q, r) = (e.hypot(h), f.hypot(g));
+        let rotation = (g.atan2(f) + h.atan2(e)) / 2.;
+        (q + r, (q - r).abs(), rotation.to_degrees())
+    }
+
+    /// True if the arc is more than half of its ellipse, SVG's large arc flag
+    pub fn is_large(&self) -> bool {
+        self.span > PI
+    }
+
+    /// True if the arc turns from the x-axis towards the y-axis, SVG's sweep flag
+    pub fn is_positive(&self) -> bool {
+        self.a.0 * self.b.1 - self.b.0 * self.a.1 > 0.
+    }
+
+    /// How many straight segments keep the arc within `tolerance`, from the sagitta of the widest radius
+    pub fn segment_count(&self, tolerance: f64) -> usize {
+        let radius = self.axes().0;
+        let step = 2. * (1. - (tolerance / radius).min(1.)).acos();
+        let segments = (self.span / step).ceil();
+        if segments.is_nan() {
+            1
+        } else {
+            segments.clamp(1., 1024.) as usize
+        }
+    }
+
+    /// The end points and the points where the arc turns in x or y
+    fn extreme_points(&self) -> Vec<(f64, f64)> {
+        let mut extremes = vec![self.point(0.), self.point(1.)];
+        let turns = [self.b.0.atan2(self.a.0), self.b.1.atan2(self.a.1)];
+        for angle in turns.iter().flat_map(|turn| [*turn, turn + PI]) {
+            if (angle - self.start).rem_euclid(TAU) < self.span {
+                extremes.push(self.at_angle(angle));
+            }
+        }
+        extremes
+    }
+}
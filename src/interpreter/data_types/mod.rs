@@ -2,4 +2,5 @@ pub mod figure;
 pub mod point;
 pub mod figurearray;
 pub mod line;
-pub mod direction;
\ No newline at end of file
+pub mod direction;
+pub mod transform;
@@ -0,0 +1,102 @@
+use crate::interpreter::data_types::point::Point;
+use std::{fmt, ops::Mul};
+
+/// A 2D affine transformation, the matrix
+/// ```text
+/// | a c e |
+/// | b d f |
+/// | 0 0 1 |
+/// ```
+/// so a point (x, y) goes to (a*x + c*y + e, b*x + d*y + f), the same order as SVG's `matrix(a b c d e f)`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Transform {
+    a: f64,
+    b: f64,
+    c: f64,
+    d: f64,
+    e: f64,
+    f: f64,
+}
+
+impl Transform {
+    pub fn identity() -> Self {
+        Self::new(1., 0., 0., 1., 0., 0.)
+    }
+
+    pub fn new(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Self {
+        Self { a, b, c, d, e, f }
+    }
+
+    pub fn translate(dx: f64, dy: f64) -> Self {
+        Self::new(1., 0., 0., 1., dx, dy)
+    }
+
+    /// Rotates around the origin by degrees, in the same direction as the `rotate` manipulation
+    pub fn rotate(degrees: f64) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        let (sin, cos) = (snap(sin), snap(cos));
+        Self::new(cos, -sin, sin, cos, 0., 0.)
+    }
+
+    pub fn scale(sx: f64, sy: f64) -> Self {
+        Self::new(sx, 0., 0., sy, 0., 0.)
+    }
+
+    /// Shears x by the angle `x_degrees` and y by the angle `y_degrees`
+    pub fn skew(x_degrees: f64, y_degrees: f64) -> Self {
+        let (tan_x, tan_y) = (x_degrees.to_radians().tan(), y_degrees.to_radians().tan());
+        Self::new(1., snap(tan_y), snap(tan_x), 1., 0., 0.)
+    }
+
+    pub fn is_finite(&self) -> bool {
+        [self.a, self.b, self.c, self.d, self.e, self.f]
+            .iter()
+            .all(|v| v.is_finite())
+    }
+
+    pub fn apply(&self, p: &Point) -> Point {
+        let (x, y) = (p.get_x_f64(), p.get_y_f64());
+        (
+            self.a * x + self.c * y + self.e,
+            self.b * x + self.d * y + self.f,
+        )
+            .into()
+    }
+}
+
+/// `t1 * t2` applies `t2` first and then `t1`
+impl Mul for Transform {
+    type Output = Transform;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new(
+            self.a * rhs.a + self.c * rhs.b,
+            self.b * rhs.a + self.d * rhs.b,
+            self.a * rhs.c + self.c * rhs.d,
+            self.b * rhs.c + self.d * rhs.d,
+            self.a * rhs.e + self.c * rhs.f + self.e,
+            self.b * rhs.e + self.d * rhs.f + self.f,
+        )
+    }
+}
+
+impl fmt::Display for Transform {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "matrix({} {} {} {} {} {})",
+            self.a, self.b, self.c, self.d, self.e, self.f
+        )
+    }
+}
+
+/// Like `manipulation::snap_zero`, but for every whole number since tan(45) should be 1.
+/// The math builtins snap their trigonometry the same way.
+pub(crate) fn snap(v: f64) -> f64 {
+    if (v - v.round()).abs() < 1e-10 {
+        //Adding zero turns -0 into 0
+        v.round() + 0.
+    } else {
+        v
+    }
+}
@@ -3,15 +3,32 @@ use std::collections::HashMap;
 use super::data_types::figurearray::FigureArray;
 use super::stack::Stack;
 use super::value::Value;
-use crate::program::{program::Program, statement::Stmt};
+use crate::program::{imports::ImportResolver, program::Program, span::Spanned, statement::Stmt};
+
+/// Most iterations a single while loop may run before it is stopped with an error
+pub const DEFAULT_ITERATION_LIMIT: u64 = 1_000_000;
+
+/// Furthest a curve may be from the straight segments replacing it in shape operations such as `union`
+pub const DEFAULT_FLATTEN_TOLERANCE: f64 = 0.1;
+
+/// Set by `break` and `continue`, the remaining statements of the loop body are skipped until the loop handles it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LoopControl {
+    Break,
+    Continue,
+}
 
 #[derive(Debug, Clone)]
 pub struct IEnvironment {
     v_table: Stack<Value>,
-    f_table: Stack<(Vec<Stmt>, Vec<String>)>,
+    f_table: Stack<(Vec<Spanned<Stmt>>, Vec<String>)>,
     s_table: HashMap<String, Program>,
     d_array: FigureArray,
     r_value: Option<Value>,
+    l_control: Option<LoopControl>,
+    iteration_limit: u64,
+    flatten_tolerance: f64,
+    imports: ImportResolver,
 }
 
 impl IEnvironment {
@@ -22,6 +39,10 @@ impl IEnvironment {
             s_table: HashMap::new(),
             d_array: FigureArray::new(),
             r_value: None,
+            l_control: None,
+            iteration_limit: DEFAULT_ITERATION_LIMIT,
+            flatten_tolerance: DEFAULT_FLATTEN_TOLERANCE,
+            imports: ImportResolver::default(),
         }
     }
 
@@ -46,13 +67,13 @@ impl IEnvironment {
     pub fn ftable_push(
         &mut self,
         identifier: String,
-        statements: Vec<Stmt>,
+        statements: Vec<Spanned<Stmt>>,
         parameters: Vec<String>,
     ) {
         self.f_table.push(identifier, (statements, parameters));
     }
 
-    pub fn ftable_find(&mut self, identifier: String) -> Option<&mut (Vec<Stmt>, Vec<String>)> {
+    pub fn ftable_find(&mut self, identifier: String) -> Option<&mut (Vec<Spanned<Stmt>>, Vec<String>)> {
         self.f_table.find(identifier)
     }
 
@@ -64,6 +85,14 @@ impl IEnvironment {
         self.s_table.get_mut(&identifier)
     }
 
+    pub fn imports_get(&self) -> &ImportResolver {
+        &self.imports
+    }
+
+    pub fn imports_set(&mut self, imports: ImportResolver) {
+        self.imports = imports;
+    }
+
     pub fn darray_get(&self) -> &FigureArray {
         &self.d_array
     }
@@ -117,5 +146,33 @@ impl IEnvironment {
         self.r_value.as_ref()
     }
 
+    pub fn lcontrol_set(&mut self, control: LoopControl) {
+        self.l_control = Some(control);
+    }
+
+    pub fn lcontrol_get(&self) -> Option<LoopControl> {
+        self.l_control
+    }
+
+    pub fn lcontrol_take(&mut self) -> Option<LoopControl> {
+        self.l_control.take()
+    }
+
+    pub fn iteration_limit_set(&mut self, limit: u64) {
+        self.iteration_limit = limit;
+    }
+
+    pub fn iteration_limit_get(&self) -> u64 {
+        self.iteration_limit
+    }
+
+    pub fn flatten_tolerance_set(&mut self, tolerance: f64) {
+        self.flatten_tolerance = tolerance;
+    }
+
+    pub fn flatten_tolerance_get(&self) -> f64 {
+        self.flatten_tolerance
+    }
+
 
 }
@@ -112,7 +112,7 @@ pub struct ArrayOutOfBoundsWithNumbers(pub usize, pub i64);
 impl Error for ArrayOutOfBoundsWithNumbers {}
 impl fmt::Display for ArrayOutOfBoundsWithNumbers {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f,"Array indexing with {} not possible on array size {}", self.0, self.1)
+        write!(f,"Array indexing with {} not possible on array size {}", self.1, self.0)
     }
 }
 
@@ -132,4 +132,84 @@ impl fmt::Display for NoPointsFound {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f,"No points found in either first or last line")
     }
-}
\ No newline at end of file
+}
+#[derive(Debug, Clone)]
+pub struct TextSizeNotPositive(pub i64);
+impl Error for TextSizeNotPositive {}
+impl fmt::Display for TextSizeNotPositive {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f,"Text size must be positive, got: {}", self.0)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MathError(pub String);
+impl Error for MathError {}
+impl fmt::Display for MathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f,"Math error: {}", self.0)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IterationLimitReached(pub u64);
+impl Error for IterationLimitReached {}
+impl fmt::Display for IterationLimitReached {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f,"While loop did not finish within the iteration limit of {}", self.0)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ArrayLengthLimitReached(pub i64, pub usize);
+impl Error for ArrayLengthLimitReached {}
+impl fmt::Display for ArrayLengthLimitReached {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f,"Array of length {} is over the length limit of {}", self.0, self.1)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MirrorLineNotALine;
+impl Error for MirrorLineNotALine {}
+impl fmt::Display for MirrorLineNotALine {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f,"Can't mirror across a line through two equal points")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LayoutAlignInvalid(pub String, pub String);
+impl Error for LayoutAlignInvalid {}
+impl fmt::Display for LayoutAlignInvalid {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f,"Can't align shapes by {:?}, expected one of: {}", self.0, self.1)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LayoutColumnsInvalid(pub i64);
+impl Error for LayoutColumnsInvalid {}
+impl fmt::Display for LayoutColumnsInvalid {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f,"A grid needs at least one column, got {}", self.0)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PrimitiveSizeNotPositive(pub String, pub String, pub f64);
+impl Error for PrimitiveSizeNotPositive {}
+impl fmt::Display for PrimitiveSizeNotPositive {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f,"The {} of {} must be positive, got {}", self.1, self.0, self.2)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PrimitiveSidesInvalid(pub i64);
+impl Error for PrimitiveSidesInvalid {}
+impl fmt::Display for PrimitiveSidesInvalid {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f,"A regular polygon needs at least 3 sides, got {}", self.0)
+    }
+}
@@ -1,12 +1,26 @@
+use std::collections::HashMap;
+
 use crate::{
     interpreter::{data_types::line::Line, InterpretS},
-    program::expression::Expr,
+    program::{
+        expression::{Anchor, Expr, MirrorAxis},
+        span::Spanned,
+    },
+    terminal::status,
 };
 
 use super::{
-    data_types::{figurearray::FigureArray, point::Point},
-    errors,
-    utils::manipulation::{place, rotate, scale},
+    data_types::{
+        figure::{Figure, TEXT_DEFAULT_SIZE},
+        figurearray::FigureArray,
+        point::Point,
+    },
+    builtins, errors,
+    utils::{
+        clipping::combine,
+        geometry,
+        manipulation::{apply, mirror, place, place_shape_at, rotate, scale, translate},
+    },
     value::Value,
     InterpretE, InterpretP,
 };
@@ -16,6 +30,17 @@ use crate::program::operators::{
     unaryoperator::UnaryOperator,
 };
 
+impl InterpretE for Spanned<Expr> {
+    fn interpret(
+        &self,
+        environment: &mut super::environment::IEnvironment,
+    ) -> Result<Value, Box<dyn std::error::Error>> {
+        self.node
+            .interpret(environment)
+            .map_err(|err| self.span.locate(err))
+    }
+}
+
 impl InterpretE for Expr {
     fn interpret(
         &self,
@@ -25,6 +50,7 @@ impl InterpretE for Expr {
             Expr::Integer(val) => Value::Integer(*val),
             Expr::Boolean(val) => Value::Boolean(*val),
             Expr::Float(val) => Value::Float(*val),
+            Expr::String(val) => Value::String(val.clone()),
             Expr::Color(r, g, b, a) => Value::Color(
                 Box::new(r.interpret(environment)?),
                 Box::new(g.interpret(environment)?),
@@ -35,70 +61,37 @@ impl InterpretE for Expr {
                 x.interpret(environment)?,
                 y.interpret(environment)?,
             ))),
-            Expr::Variable(identifier) => environment.vtable_find(identifier.to_owned()).unwrap().clone(),
+            Expr::Variable(identifier) => match environment.vtable_find(identifier.to_owned()) {
+                Some(value) => value.clone(),
+                None => builtins::constant(identifier).unwrap(),
+            },
             Expr::BinaryOperation { lhs, rhs, operator } => {
                 let i1 = lhs.interpret(environment)?;
                 let i2 = rhs.interpret(environment)?;
 
+                //Numbers are promoted the same way as in the typechecker, see `Type::promote`
                 match operator {
-                    BinaryOperator::Add => i1 + i2,
-                    BinaryOperator::Subtract => i1 - i2,
-                    BinaryOperator::Multiply =>  &i1 * &i2,
-                    BinaryOperator::Divide => {
-                        if i2 == Value::Integer(0) || i2 == Value::Float(0.0) {
-                            return Err(errors::DivideByZero.into());
-                        }
-                        &i1 / &i2
+                    BinaryOperator::Add
+                        if matches!((&i1, &i2), (Value::String(_), Value::String(_))) =>
+                    {
+                        i1 + i2
                     }
-                    BinaryOperator::Modulus => match (i1, i2) {
-                        (Value::Integer(v1), Value::Integer(v2)) => Value::Integer(v1 % v2),
-                        (Value::Float(v1), Value::Float(v2)) => Value::Float(v1 % v2),
-                        (Value::Float(v1), Value::Integer(v2)) => Value::Float(v1 % v2 as f64),
-                        (Value::Integer(v1), Value::Float(v2)) => Value::Float(v1 as f64 % v2),
-                        _ => unreachable!(),
-                    },
-                    BinaryOperator::GreaterThanOrEquals => match (i1, i2) {
-                        (Value::Integer(v1), Value::Integer(v2)) => Value::Boolean(v1 >= v2),
-                        (Value::Float(v1), Value::Float(v2)) => Value::Boolean(v1 >= v2),
-                        (Value::Float(v1), Value::Integer(v2)) => Value::Boolean(v1 >= v2 as f64),
-                        (Value::Integer(v1), Value::Float(v2)) => Value::Boolean(v1 as f64 >= v2),
-                        _ => unreachable!(),
-                    },
-                    BinaryOperator::LessThanOrEquals => match (i1, i2) {
-                        (Value::Integer(v1), Value::Integer(v2)) => Value::Boolean(v1 <= v2),
-                        (Value::Float(v1), Value::Float(v2)) => Value::Boolean(v1 <= v2),
-                        (Value::Float(v1), Value::Integer(v2)) => Value::Boolean(v1 <= v2 as f64),
-                        (Value::Integer(v1), Value::Float(v2)) => Value::Boolean(v1 as f64 <= v2),
-                        _ => unreachable!(),
-                    },
-                    BinaryOperator::LessThan => match (i1, i2) {
-                        (Value::Integer(v1), Value::Integer(v2)) => Value::Boolean(v1 < v2),
-                        (Value::Float(v1), Value::Float(v2)) => Value::Boolean(v1 < v2),
-                        (Value::Float(v1), Value::Integer(v2)) => Value::Boolean(v1 < v2 as f64),
-                        (Value::Integer(v1), Value::Float(v2)) => Value::Boolean((v1 as f64) < v2),
-                        _ => unreachable!(),
-                    },
-                    BinaryOperator::GreaterThan => match (i1, i2) {
-                        (Value::Integer(v1), Value::Integer(v2)) => Value::Boolean(v1 > v2),
-                        (Value::Float(v1), Value::Float(v2)) => Value::Boolean(v1 > v2),
-                        (Value::Float(v1), Value::Integer(v2)) => Value::Boolean(v1 > v2 as f64),
-                        (Value::Integer(v1), Value::Float(v2)) => Value::Boolean(v1 as f64 > v2),
-                        _ => unreachable!(),
-                    },
-                    BinaryOperator::Equals => match (i1, i2) {
-                        (Value::Integer(v1), Value::Integer(v2)) => Value::Boolean(v1 == v2),
-                        (Value::Float(v1), Value::Float(v2)) => Value::Boolean(v1 == v2),
-                        (Value::Float(v1), Value::Integer(v2)) => Value::Boolean(v1 == v2 as f64),
-                        (Value::Integer(v1), Value::Float(v2)) => Value::Boolean(v1 as f64 == v2),
-                        _ => unreachable!(),
-                    },
-                    BinaryOperator::NotEquals => match (i1, i2) {
-                        (Value::Integer(v1), Value::Integer(v2)) => Value::Boolean(v1 != v2),
-                        (Value::Float(v1), Value::Float(v2)) => Value::Boolean(v1 != v2),
-                        (Value::Float(v1), Value::Integer(v2)) => Value::Boolean(v1 != v2 as f64),
-                        (Value::Integer(v1), Value::Float(v2)) => Value::Boolean(v1 as f64 != v2),
-                        _ => unreachable!(),
-                    },
+                    BinaryOperator::Multiply
+                        if matches!((&i1, &i2), (Value::Transform(_), Value::Transform(_))) =>
+                    {
+                        Value::Transform(i1.get_transform()? * i2.get_transform()?)
+                    }
+                    BinaryOperator::Add
+                    | BinaryOperator::Subtract
+                    | BinaryOperator::Multiply
+                    | BinaryOperator::Divide
+                    | BinaryOperator::Modulus => i1.arithmetic(*operator, &i2)?,
+                    BinaryOperator::GreaterThanOrEquals => Value::Boolean(i1.compare(&i2)?.is_ge()),
+                    BinaryOperator::LessThanOrEquals => Value::Boolean(i1.compare(&i2)?.is_le()),
+                    BinaryOperator::LessThan => Value::Boolean(i1.compare(&i2)?.is_lt()),
+                    BinaryOperator::GreaterThan => Value::Boolean(i1.compare(&i2)?.is_gt()),
+                    BinaryOperator::Equals => Value::Boolean(i1.compare(&i2)?.is_eq()),
+                    BinaryOperator::NotEquals => Value::Boolean(i1.compare(&i2)?.is_ne()),
                     BinaryOperator::LogicalAnd => Value::Boolean(i1.get_bool()? && i2.get_bool()?),
                     BinaryOperator::LogicalOr => Value::Boolean(i1.get_bool()? || i2.get_bool()?),
                 }
@@ -111,21 +104,14 @@ impl InterpretE for Expr {
                 }
             }
             Expr::FCall { name, args } => {
-                match name.as_str() {
-                    "push" => {
-                        let i1 = args[0].interpret(environment)?;
-                        let i2 = args[1].interpret(environment)?;
-                        let mut array = i1.get_array()?;
-                        array.push(i2);
-                        Value::Array(array)
-                    }
-                    "remove" => {
-                        let i1 = args[0].interpret(environment)?;
-                        let i2 = args[1].interpret(environment)?;
-                        let index_to_remove = i2.get_int()? as usize;
-                        let mut array = i1.get_array()?;
-                        array.remove(index_to_remove);
-                        Value::Array(array)
+                match builtins::lookup(name) {
+                    Some(builtin) if environment.ftable_find(name.into()).is_none() => {
+                        let values = args
+                            .iter()
+                            .map(|arg| arg.interpret(environment))
+                            .collect::<Result<Vec<Value>, _>>()?;
+                        let tolerance = environment.flatten_tolerance_get();
+                        builtins::call(builtin, name, &values, tolerance)?
                     }
                     _ => {
                         let mut params = Vec::new();
@@ -165,6 +151,29 @@ impl InterpretE for Expr {
                 let i1 = lhs.interpret(environment)?;
                 let i2 = rhs.interpret(environment)?;
 
+                //`--` and `arc` add a line between the paths, `~~` a straight one when there is no curve to extend
+                let arc = match operator {
+                    PathOperator::Arc {
+                        radius,
+                        large,
+                        sweep,
+                    } => Some((
+                        match radius.interpret(environment)? {
+                            Value::Point(radii) => (radii.get_x_f64(), radii.get_y_f64()),
+                            radius => (radius.get_float()?, radius.get_float()?),
+                        },
+                        large.interpret(environment)?.get_bool()?,
+                        sweep.interpret(environment)?.get_bool()?,
+                    )),
+                    _ => None,
+                };
+                let joint = |from: &Point, to: &Point| match arc {
+                    Some((radii, large, sweep)) => {
+                        Line::arc(from.clone(), to.clone(), radii, large, sweep)
+                    }
+                    None => Line::Straight(vec![from.clone(), to.clone()]),
+                };
+
                 match (i1, i2) {
                     //Path-path
                     (Value::Figure(mut fig1), Value::Figure(mut fig2)) => {
@@ -172,17 +181,25 @@ impl InterpretE for Expr {
                         let fig2_first_line = fig2.get_first_line()?;
 
                         match (operator, &fig1_last_line, &fig2_first_line) {
-                            (PathOperator::Line, _, _)
-                            | (PathOperator::Curve, Line::Straight(_), Line::Straight(_)) => {
+                            (PathOperator::Line | PathOperator::Arc { .. }, _, _)
+                            | (
+                                PathOperator::Curve,
+                                Line::Straight(_) | Line::Arc(_),
+                                Line::Straight(_) | Line::Arc(_),
+                            ) => {
                                 let fig1_last_line = fig1_last_line.clone();
-                                fig1.push_line_after(Line::Straight(vec![
-                                    fig1_last_line.get_last_point()?.clone(),
-                                    fig2_first_line.get_first_point()?.clone(),
-                                ]));
+                                fig1.push_line_after(joint(
+                                    fig1_last_line.get_last_point()?,
+                                    fig2_first_line.get_first_point()?,
+                                ));
                                 fig1.push_lines(fig2.get_lines().clone());
                                 Value::Figure(fig1)
                             }
-                            (PathOperator::Curve, Line::Straight(_), Line::Curved(_)) => {
+                            (
+                                PathOperator::Curve,
+                                Line::Straight(_) | Line::Arc(_),
+                                Line::Curved(_),
+                            ) => {
                                 fig2_first_line
                                     .insert_point_first(fig1_last_line.get_last_point()?.clone());
 
@@ -205,7 +222,11 @@ impl InterpretE for Expr {
                                 fig1.push_lines(fig2.get_lines().clone());
                                 Value::Figure(fig1)
                             }
-                            (PathOperator::Curve, Line::Curved(_), Line::Straight(_)) => {
+                            (
+                                PathOperator::Curve,
+                                Line::Curved(_),
+                                Line::Straight(_) | Line::Arc(_),
+                            ) => {
                                 fig1_last_line
                                     .insert_point_last(fig2_first_line.get_first_point()?.clone());
 
@@ -218,19 +239,16 @@ impl InterpretE for Expr {
                     (Value::Point(p), Value::Figure(mut fig)) => {
                         let line_first = fig.get_first_line()?;
                         match (operator, &line_first) {
-                            (PathOperator::Line, _) => {
+                            (PathOperator::Line | PathOperator::Arc { .. }, _) => {
                                 let line_first = line_first.clone();
-                                fig.push_line_before(Line::Straight(vec![
-                                    p.clone(),
-                                    line_first.get_first_point()?.clone(),
-                                ]));
+                                fig.push_line_before(joint(&p, line_first.get_first_point()?));
                                 Value::Figure(fig)
                             }
                             (PathOperator::Curve, Line::Curved(_)) => {
                                 line_first.insert_point_first(p);
                                 Value::Figure(fig)
                             }
-                            (PathOperator::Curve, Line::Straight(_)) => {
+                            (PathOperator::Curve, Line::Straight(_) | Line::Arc(_)) => {
                                 let line_first = line_first.clone();
                                 fig.push_line_before(Line::Curved(vec![
                                     p.clone(),
@@ -244,19 +262,16 @@ impl InterpretE for Expr {
                     (Value::Figure(mut fig), Value::Point(p)) => {
                         let line_last = fig.get_last_line()?;
                         match (operator, &line_last) {
-                            (PathOperator::Line, _) => {
+                            (PathOperator::Line | PathOperator::Arc { .. }, _) => {
                                 let line_last = line_last.clone();
-                                fig.push_line_after(Line::Straight(vec![
-                                    line_last.get_last_point()?.clone(),
-                                    p.clone(),
-                                ]));
+                                fig.push_line_after(joint(line_last.get_last_point()?, &p));
                                 Value::Figure(fig)
                             }
                             (PathOperator::Curve, Line::Curved(_)) => {
                                 line_last.insert_point_last(p);
                                 Value::Figure(fig)
                             }
-                            (PathOperator::Curve, Line::Straight(_)) => {
+                            (PathOperator::Curve, Line::Straight(_) | Line::Arc(_)) => {
                                 let line_last = line_last.clone();
                                 fig.push_line_after(Line::Curved(vec![
                                     line_last.get_last_point()?.clone(),
@@ -274,6 +289,7 @@ impl InterpretE for Expr {
                         PathOperator::Curve => {
                             Value::Figure(vec![Line::Curved(vec![p1.clone(), p2.clone()])].into())
                         }
+                        PathOperator::Arc { .. } => Value::Figure(vec![joint(&p1, &p2)].into()),
                     },
                     _ => unreachable!(),
                 }
@@ -289,12 +305,12 @@ impl InterpretE for Expr {
                 let line_last = fig.get_last_line()?;
 
                 match (operator, &line_first, &line_last) {
-                    (PolyOperator::Curved, Line::Straight(_), Line::Curved(_)) => {
+                    (PolyOperator::Curved, Line::Straight(_) | Line::Arc(_), Line::Curved(_)) => {
                         // Case 1
                         line_last.insert_point_last(line_first.get_first_point()?.clone());
                         Value::Figure(fig)
                     }
-                    (PolyOperator::Curved, Line::Curved(_), Line::Straight(_)) => {
+                    (PolyOperator::Curved, Line::Curved(_), Line::Straight(_) | Line::Arc(_)) => {
                         // NY CASE: istedet for at have den ved case 2 hvor e.g. (a,b)~~(c,d)--(e,f)~~* => (c,d)--(e,f) ville blive konverteret til (c,d)~~(e,f)
                         let mut line_first = fig.pop_first_line()?;
                         line_first
@@ -324,7 +340,11 @@ impl InterpretE for Expr {
                         Value::Figure(fig)
                     }
                     (PolyOperator::Straight, _, _)
-                    | (PolyOperator::Curved, Line::Straight(_), Line::Straight(_)) => {
+                    | (
+                        PolyOperator::Curved,
+                        Line::Straight(_) | Line::Arc(_),
+                        Line::Straight(_) | Line::Arc(_),
+                    ) => {
                         // Case 3
                         let line_last = line_last.clone();
                         fig.push_line_after(Line::Straight(vec![
@@ -348,6 +368,35 @@ impl InterpretE for Expr {
                 path_poly,
             } => {
                 match (name, path_poly) {
+                    (Some(name), None)
+                        if name == "text" && environment.stable_find(name.clone()).is_none() =>
+                    {
+                        //Built-in text constructor, the typechecker ensures content is given
+                        let content = args["content"].interpret(environment)?.get_string()?.to_owned();
+                        let size = match args.get("size") {
+                            Some(size) => size.interpret(environment)?.get_int()?,
+                            None => TEXT_DEFAULT_SIZE,
+                        };
+                        if size <= 0 {
+                            return Err(errors::TextSizeNotPositive(size).into());
+                        }
+
+                        let mut fig = Figure::text(content, size);
+                        if let Some(fill) = args.get("fill") {
+                            fig.set_attribute(("fill".into(), fill.interpret(environment)?));
+                        }
+                        Value::Shape(FigureArray::from(vec![fig]))
+                    }
+                    (Some(name), None)
+                        if environment.stable_find(name.clone()).is_none()
+                            && builtins::is_primitive_shape(name) =>
+                    {
+                        let mut values = HashMap::new();
+                        for (arg_name, expr) in args.iter() {
+                            values.insert(arg_name.clone(), expr.interpret(environment)?);
+                        }
+                        builtins::call_primitive(name, &values)?
+                    }
                     (Some(_), _) => {
                         let mut interpreted_args = Vec::new();
                         for (arg_name, expr) in args.iter() {
@@ -363,20 +412,26 @@ impl InterpretE for Expr {
                         for (arg_name, value) in interpreted_args {
                             program.ienvironment.vtable_push(arg_name.clone(), value);
                         }
+                        program
+                            .ienvironment
+                            .iteration_limit_set(environment.iteration_limit_get());
+                        program
+                            .ienvironment
+                            .flatten_tolerance_set(environment.flatten_tolerance_get());
 
                         let draw_array = match program.interpret() {
                             Ok(draw_array) => {
-                                println!(
+                                status::print(format!(
                                     "[Interpreter] Constructor Call: {} - OK",
                                     name.clone().unwrap()
-                                );
+                                ));
                                 draw_array
                             }
                             Err(err) => {
-                                println!(
+                                status::print(format!(
                                     "[Interpreter] Constructor Call: {} - ERROR",
                                     name.clone().unwrap()
-                                );
+                                ));
                                 return Err(err);
                             }
                         };
@@ -423,17 +478,27 @@ impl InterpretE for Expr {
                         "width" => figures.width(),
                         "x" => figures.get_top_left().get_x().clone(),
                         "y" => figures.get_top_left().get_y().clone(),
-                        _ => unreachable!(),
+                        member => {
+                            shape_member(&figures, member, environment.flatten_tolerance_get())
+                        }
                     },
                     Value::Figure(figure) => match member_access.as_str() {
                         "height" => figure.get_height(),
                         "width" => figure.get_width(),
-                        _ => unreachable!(),
+                        member => shape_member(
+                            &vec![figure].into(),
+                            member,
+                            environment.flatten_tolerance_get(),
+                        ),
                     },
                     Value::Array(array) => match member_access.as_str() {
                         "size" => Value::Integer(array.len() as i64),
                         _ => unreachable!(),
                     },
+                    Value::String(string) => match member_access.as_str() {
+                        "size" => Value::Integer(string.chars().count() as i64),
+                        _ => unreachable!(),
+                    },
                     _ => unreachable!(),
                 }
             }
@@ -457,25 +522,87 @@ impl InterpretE for Expr {
 
                 Value::Shape(v)
             }
-            Expr::Scale { base_shape, factor } => {
+            Expr::Scale {
+                base_shape,
+                factor,
+                anchor,
+            } => {
                 let Value::Shape(shape) = base_shape.interpret(environment)? else {
                     unreachable!()
                 };
                 let factor = factor.interpret(environment)?;
-                let scaled_shape = scale(shape, factor)?;
+                let anchor = anchor_point(anchor, &shape, environment)?;
+                let scaled_shape = scale(shape, factor, anchor)?;
 
                 Value::Shape(scaled_shape)
             }
-            Expr::Rotate { base_shape, factor } => {
+            Expr::Rotate {
+                base_shape,
+                factor,
+                anchor,
+            } => {
                 let s = base_shape.interpret(environment)?.get_shape()?;
                 let i1 = factor.interpret(environment)?;
-                let v = rotate(s, i1);
+                let anchor = anchor_point(anchor, &s, environment)?;
+                let v = rotate(s, i1, anchor);
+                Value::Shape(v)
+            }
+            Expr::Combine {
+                base_shape,
+                second_shape,
+                operator,
+            } => {
+                let s1 = base_shape.interpret(environment)?.get_shape()?;
+                let s2 = second_shape.interpret(environment)?.get_shape()?;
+                let v = combine(&s1, &s2, *operator, environment.flatten_tolerance_get());
                 Value::Shape(v)
             }
+            Expr::Mirror { base_shape, axis } => {
+                let s = base_shape.interpret(environment)?.get_shape()?;
+                let (from, to): (Point, Point) = match axis {
+                    MirrorAxis::Horizontal => ((0, 0).into(), (0, 1).into()),
+                    MirrorAxis::Vertical => ((0, 0).into(), (1, 0).into()),
+                    MirrorAxis::Line(from, to) => (
+                        from.interpret(environment)?.get_point()?,
+                        to.interpret(environment)?.get_point()?,
+                    ),
+                };
+                Value::Shape(mirror(s, &from, &to)?)
+            }
+            Expr::Apply {
+                transform,
+                base_shape,
+            } => {
+                let t = transform.interpret(environment)?.get_transform()?;
+                let s = base_shape.interpret(environment)?.get_shape()?;
+                Value::Shape(apply(s, &t))
+            }
+            Expr::Move {
+                base_shape,
+                point,
+                absolute,
+            } => {
+                let s = base_shape.interpret(environment)?.get_shape()?;
+                let p = point.interpret(environment)?.get_point()?;
+                if *absolute {
+                    Value::Shape(place_shape_at(s, p))
+                } else {
+                    Value::Shape(translate(s, &p))
+                }
+            }
             Expr::ArrayIndex { identifier, index } => {
-                let v1 = identifier.interpret(environment)?.get_array()?;
+                let v1 = identifier.interpret(environment)?;
                 let v2 = index.interpret(environment)?.get_int()?;
 
+                //Strings are indexed by character, giving a string of length one
+                let v1 = match v1 {
+                    Value::String(string) => string
+                        .chars()
+                        .map(|c| Value::String(c.to_string()))
+                        .collect(),
+                    v1 => v1.get_array()?,
+                };
+
                 let idx: usize = v2.try_into().map_err(|_| errors::ArrayOutOfBoundsWithNumbers(v1.len(), v2))?;
                 v1.get(idx).ok_or_else(|| errors::ArrayOutOfBoundsWithNumbers(v1.len(), v2))?.clone()
             },
@@ -484,3 +611,31 @@ impl InterpretE for Expr {
         Ok(val)
     }
 }
+
+/// The point an `around` clause refers to, relative to the shape being manipulated
+fn anchor_point(
+    anchor: &Option<Anchor>,
+    shape: &FigureArray,
+    environment: &mut super::environment::IEnvironment,
+) -> Result<Option<Point>, Box<dyn std::error::Error>> {
+    let point = match anchor {
+        None => return Ok(None),
+        Some(Anchor::Center) => shape.get_center(),
+        Some(Anchor::TopLeft) => shape.get_top_left(),
+        Some(Anchor::TopRight) => (shape.max_x(), shape.max_y()).into(),
+        Some(Anchor::BottomLeft) => (shape.min_x(), shape.min_y()).into(),
+        Some(Anchor::BottomRight) => (shape.max_x(), shape.min_y()).into(),
+        Some(Anchor::Point(point)) => point.interpret(environment)?.get_point()?,
+    };
+    Ok(Some(point))
+}
+
+/// Members measured from the area of a shape, the rest are handled where they are read
+fn shape_member(shape: &FigureArray, member: &str, tolerance: f64) -> Value {
+    match member {
+        "area" => Value::Float(geometry::area(shape, tolerance)),
+        "perimeter" => Value::Float(geometry::perimeter(shape, tolerance)),
+        "centroid" => Value::Point(geometry::centroid(shape, tolerance)),
+        _ => unreachable!(),
+    }
+}
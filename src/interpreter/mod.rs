@@ -5,6 +5,7 @@ use data_types::figurearray::FigureArray;
 use environment::IEnvironment;
 use value::Value;
 
+pub mod builtins;
 pub mod data_types;
 pub mod utils;
 pub mod expression;
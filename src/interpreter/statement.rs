@@ -1,13 +1,26 @@
-use std::path::Path;
-use crate::program::{program::Program, statement::Stmt};
-use super::{errors, utils::manipulation::place_shape_at, value::Value, InterpretE, InterpretS};
+use crate::program::{span::Spanned, statement::Stmt};
+use super::{
+    environment::LoopControl, errors, utils::manipulation::place_shape_at, value::Value,
+    InterpretE, InterpretS,
+};
+
+impl InterpretS for Spanned<Stmt> {
+    fn interpret(
+        &self,
+        environment: &mut super::environment::IEnvironment,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.node
+            .interpret(environment)
+            .map_err(|err| self.span.locate(err))
+    }
+}
 
 impl InterpretS for Stmt {
     fn interpret(
         &self,
         environment: &mut super::environment::IEnvironment,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        if environment.rvalue_get().is_some() {
+        if environment.rvalue_get().is_some() || environment.lcontrol_get().is_some() {
             return Ok(());
         }
         match self {
@@ -58,7 +71,7 @@ impl InterpretS for Stmt {
             }
 
             Stmt::Import { name, path } => {
-                let subprogram = Program::from_file(Path::new(path))?;
+                let subprogram = environment.imports_get().load(path)?;
                 environment.stable_push(name.clone(), subprogram);
             }
 
@@ -93,11 +106,36 @@ impl InterpretS for Stmt {
                     }
 
                     environment.pop_scope();
-                    if environment.rvalue_get().is_some() {
+                    let control = environment.lcontrol_take();
+                    if environment.rvalue_get().is_some() || control == Some(LoopControl::Break) {
+                        break;
+                    }
+                }
+            }
+            Stmt::While { condition, body } => {
+                let mut iterations = 0;
+                while condition.interpret(environment)?.get_bool()? {
+                    iterations += 1;
+                    if iterations > environment.iteration_limit_get() {
+                        return Err(
+                            errors::IterationLimitReached(environment.iteration_limit_get()).into(),
+                        );
+                    }
+
+                    environment.push_scope();
+                    for stmt in body.iter() {
+                        stmt.interpret(environment)?;
+                    }
+
+                    environment.pop_scope();
+                    let control = environment.lcontrol_take();
+                    if environment.rvalue_get().is_some() || control == Some(LoopControl::Break) {
                         break;
                     }
                 }
             }
+            Stmt::Break => environment.lcontrol_set(LoopControl::Break),
+            Stmt::Continue => environment.lcontrol_set(LoopControl::Continue),
             Stmt::Fork {
                 branches,
                 otherwise,
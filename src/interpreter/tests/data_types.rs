@@ -1,4 +1,4 @@
-use crate::interpreter::data_types::{line::Line, point::Point};
+use crate::interpreter::data_types::{figure::{Figure, FigureKind}, figurearray::FigureArray, line::Line, point::Point};
 use super::*;
 
 
@@ -34,8 +34,102 @@ fn insert_first_and_last_point() {
     assert_eq!(i1.get_last_point().unwrap().clone(), (50,50).into());
 }
 
+#[test]
+fn curve_extreme_points() {
+    //A quadratic arch peaks halfway, at half the height of its control point
+    let arch = Line::Curved(vec![(0,0).into(), (10,20).into(), (20,0).into()]);
+    let extremes = arch.get_extreme_points();
+    assert_eq!(extremes.len(), 3);
+    assert!(extremes[2].approx_eq(&(10.,10.).into(), 1e-9));
+
+    //An S-shaped cubic turns twice in x, its x range is wider than its end points
+    let s_curve = Line::Curved(vec![(0,0).into(), (10,0).into(), (-10,10).into(), (0,10).into()]);
+    let xs: Vec<f64> = s_curve.get_extreme_points().iter().map(Point::get_x_f64).collect();
+    let (min, max) = xs.iter().fold((0f64, 0f64), |(min, max), x| (min.min(*x), max.max(*x)));
+    assert!((max - 2.886751345948129).abs() < 1e-9 && (min + 2.886751345948129).abs() < 1e-9);
+
+    let straight = Line::Straight(vec![(0,0).into(), (3,4).into()]);
+    assert_eq!(straight.get_extreme_points(), straight.get_points().clone());
+}
+
+#[test]
+fn spline_through_many_points() {
+    let points: Vec<Point> = vec![(0,0).into(), (1,2).into(), (3,1).into(), (4,4).into(), (6,0).into()];
+    let segments = Line::Curved(points.clone()).get_segments();
+    assert_eq!(segments.len(), 4);
+    assert!(segments[0].get_points()[1].approx_eq(&(1. / 6., 1. / 3.).into(), 1e-9));
+    for (idx, pair) in segments.windows(2).enumerate() {
+        //Every segment runs between neighbouring points and leaves them in the direction it arrived
+        let (before, after) = (pair[0].get_points(), pair[1].get_points());
+        assert_eq!((&before[3], &after[0]), (&points[idx + 1], &points[idx + 1]));
+        assert!((&before[3] - &before[2]).approx_eq(&(&after[1] - &after[0]), 1e-9));
+    }
+    //The curve goes over (4, 4) as it is still rising there
+    let top = Line::Curved(points.clone()).get_extreme_points().iter().map(Point::get_y_f64).fold(0., f64::max);
+    assert!(top > 4. && top < 4.1);
+
+    //A closed curve is also smooth where it starts
+    let closed = Line::Curved(vec![(0,0).into(), (2,2).into(), (4,0).into(), (2,-2).into(), (0,0).into()]).get_segments();
+    assert!(closed[0].get_points()[1].approx_eq(&(0., 2. / 3.).into(), 1e-9));
+    assert!(closed[3].get_points()[2].approx_eq(&(0., -2. / 3.).into(), 1e-9));
+
+    //Transforming the points transforms the Béziers the same way
+    use crate::interpreter::data_types::transform::Transform;
+    let transform = Transform::rotate(30.) * Transform::scale(2., 1.);
+    let moved = Line::Curved(points.iter().map(|p| transform.apply(p)).collect()).get_segments();
+    for (segment, moved) in segments.iter().zip(&moved) {
+        for (p, q) in segment.get_points().iter().zip(moved.get_points()) {
+            assert!(transform.apply(p).approx_eq(q, 1e-9));
+        }
+    }
+}
+
 
 /***** Figure *****/
+#[test]
+fn text_is_not_an_attribute() {
+    //An attribute with any name is kept apart from the text of the figure
+    let mut text = Figure::text("hi".into(), 10);
+    text.set_attribute(("content".into(), Value::Integer(3)));
+    assert_eq!(text.get_text(), Some("hi"));
+    assert_eq!(text.get_attributes().len(), 1);
+    assert_eq!(Figure::from(text.get_lines().clone()).get_text(), None);
+}
+
+#[test]
+fn tight_bounds_and_control_hull() {
+    let arch: Figure = vec![
+        Line::Curved(vec![(0,0).into(), (10,20).into(), (20,0).into()]),
+        Line::Straight(vec![(20,0).into(), (0,0).into()]),
+    ].into();
+    assert_eq!((arch.get_width(), arch.get_height()), (20.into(), 10.0.into()));
+    assert_eq!(arch.get_control_hull(), (0.into(), 0.into(), 20.into(), 20.into()));
+
+    let shape: FigureArray = vec![arch].into();
+    assert_eq!(shape.get_top_left(), Point::from((Value::Integer(0), Value::Float(10.))));
+    assert_eq!(shape.control_hull().3, 20.into());
+}
+
+#[test]
+fn primitive_figures() {
+    let mut ellipse = Figure::ellipse(3., 2.);
+    ellipse.set_attribute(("primitive".into(), Value::String("rect".into())));
+    assert_eq!(ellipse.get_kind(), &FigureKind::Ellipse);
+    assert_eq!((ellipse.get_width(), ellipse.get_height()), (6.0.into(), 4.0.into()));
+    assert!(ellipse.is_closed().unwrap());
+
+    //The radius is at most half the height, so the short sides are only corners
+    let rect = Figure::rect(4., 2., 5.);
+    assert_eq!(rect.get_lines().len(), 6);
+    assert_eq!(rect.get_control_hull(), (0.0.into(), 0.0.into(), 4.0.into(), 2.0.into()));
+    assert!(rect.is_closed().unwrap());
+
+    let square = Figure::regular_polygon(4, 2.);
+    assert_eq!(square.get_kind(), &FigureKind::Path);
+    assert_eq!(*square.get_first_point().unwrap(), (0., 2.).into());
+    assert_eq!(*square.get_lines()[1].get_last_point().unwrap(), (0., -2.).into());
+}
+
 #[test]
 fn test_height_width() {
     let i1 = basic_square().get_shape().unwrap().get_figures().first().unwrap().clone();
@@ -47,6 +141,18 @@ fn test_height_width() {
     assert_eq!(i2.get_width(), 2.into());
 }
 
+#[test]
+fn width_is_along_x_and_height_along_y() {
+    let rect: Figure = vec![
+        Line::Straight(vec![(0,0).into(), (4,0).into()]),
+        Line::Straight(vec![(4,0).into(), (4,2).into()]),
+        Line::Straight(vec![(4,2).into(), (0,2).into()]),
+        Line::Straight(vec![(0,2).into(), (0,0).into()]),
+    ].into();
+    assert_eq!(rect.get_width(), 4.into());
+    assert_eq!(rect.get_height(), 2.into());
+}
+
 #[test]
 fn max_min() {
     let i1 = basic_square().get_shape().unwrap().get_figures().first().unwrap().clone();
@@ -187,4 +293,20 @@ fn test_value_partial_cmp() {
         assert_eq!(&i3 - &i4, Value::Float(3.0));
         assert_eq!(&i3 - &i1, Value::Float(-0.5));
         assert_eq!(&i1 - &i3, Value::Float(0.5));
-    }
\ No newline at end of file
+    }
+
+/***** Transform *****/
+#[test]
+fn transform_composition_applies_right_first() {
+    use crate::interpreter::data_types::transform::Transform;
+    let p: Point = (1, 0).into();
+    let moved_then_scaled = Transform::scale(2., 3.) * Transform::translate(1., 1.);
+    let scaled_then_moved = Transform::translate(1., 1.) * Transform::scale(2., 3.);
+    assert_eq!(moved_then_scaled.apply(&p), (4., 3.).into());
+    assert_eq!(scaled_then_moved.apply(&p), (3., 1.).into());
+
+    //Same direction as the rotate manipulation, and exact for right angles
+    assert_eq!(Transform::rotate(90.).apply(&p), (0., -1.).into());
+    assert_eq!(Transform::skew(45., 0.).apply(&(0, 2).into()), (2., 2.).into());
+    assert_eq!(Transform::identity() * Transform::rotate(30.), Transform::rotate(30.));
+}
@@ -7,10 +7,10 @@ use crate::{
         environment::IEnvironment,
         errors,
         value::Value,
-        InterpretE, InterpretS,
+        InterpretE, InterpretP, InterpretS,
     },
     program::{
-        expression::Expr,
+        expression::{Anchor, Expr},
         operators::{
             binaryoperator::BinaryOperator, pathoperator::PathOperator, polyoperator::PolyOperator,
             unaryoperator::UnaryOperator,
@@ -19,6 +19,7 @@ use crate::{
         r#type::Type,
         statement::Stmt,
     },
+    typechecker::TypeCheckP,
 };
 
 #[test]
@@ -679,11 +680,11 @@ fn member_color() {
         name: "color".into(),
         declared_type: Type::Color,
         value: Expr::Color(
-            Box::new(Expr::Integer(4)),
-            Box::new(Expr::Integer(5)),
-            Box::new(Expr::Integer(6)),
-            Box::new(Expr::Integer(7)),
-        ),
+            Box::new(Expr::Integer(4).into()),
+            Box::new(Expr::Integer(5).into()),
+            Box::new(Expr::Integer(6).into()),
+            Box::new(Expr::Integer(7).into()),
+        ).into(),
     }
     .interpret(&mut env)
     .unwrap();
@@ -726,7 +727,7 @@ fn member_point() {
     let _ = Stmt::VarDecl {
         name: "point".into(),
         declared_type: Type::Point,
-        value: Expr::Point(Box::new(Expr::Integer(4)), Box::new(Expr::Integer(5))),
+        value: Expr::Point(Box::new(Expr::Integer(4).into()), Box::new(Expr::Integer(5).into())).into(),
     }
     .interpret(&mut env)
     .unwrap();
@@ -818,7 +819,7 @@ fn array_size() {
     let _ = Stmt::VarDecl {
         name: "array".into(),
         declared_type: Type::IntArray,
-        value: Expr::Array(vec![Expr::Integer(4), Expr::Integer(5)]),
+        value: Expr::Array(vec![Expr::Integer(4).into(), Expr::Integer(5).into()]).into(),
     }
     .interpret(&mut env)
     .unwrap();
@@ -836,7 +837,7 @@ fn array_size() {
 fn array_interpret() {
     let mut env = IEnvironment::new();
 
-    let i2 = Expr::Array(vec![Expr::Integer(4)])
+    let i2 = Expr::Array(vec![Expr::Integer(4).into()])
         .interpret(&mut env)
         .unwrap();
 
@@ -861,6 +862,7 @@ fn scale() {
         }
         .into(),
         factor: Expr::Integer(4).into(),
+        anchor: None,
     }
     .interpret(&mut env)
     .unwrap();
@@ -941,6 +943,7 @@ fn rotate() {
         }
         .into(),
         factor: Expr::Integer(90).into(),
+        anchor: None,
     }
     .interpret(&mut env)
     .unwrap();
@@ -953,6 +956,37 @@ fn rotate() {
     );
 }
 
+#[test]
+fn rotate_around_named_pivot() {
+    let mut env = IEnvironment::new();
+    let i1 = Expr::Rotate {
+        base_shape: Expr::SCall {
+            name: None,
+            args: HashMap::new(),
+            path_poly: Some(
+                Expr::PathOperation {
+                    lhs: Expr::Point(Expr::Integer(1).into(), Expr::Integer(2).into()).into(),
+                    rhs: Expr::Point(Expr::Integer(3).into(), Expr::Integer(4).into()).into(),
+                    operator: PathOperator::Line,
+                }
+                .into(),
+            ),
+        }
+        .into(),
+        factor: Expr::Integer(90).into(),
+        anchor: Some(Anchor::TopRight),
+    }
+    .interpret(&mut env)
+    .unwrap();
+
+    assert_eq!(
+        i1,
+        Value::Shape(
+            vec![vec![Line::Straight(vec![(1., 6.).into(), (3., 4.).into()])].into()].into(),
+        )
+    );
+}
+
 #[test]
 fn pathoperation_point_point() {
     let mut env = IEnvironment::new();
@@ -1559,8 +1593,7 @@ pub fn scall_pathpoly() {
                 Expr::Integer(255).into(),
                 Expr::Integer(255).into(),
                 Expr::Integer(255).into(),
-            )
-            .into(),
+            ).into(),
         )]
         .into_iter()
         .collect(),
@@ -1716,6 +1749,36 @@ pub fn scall_identifier() {
     )
 }
 
+#[test]
+pub fn scall_imported_text() {
+    //An imported shape called text is used instead of the built-in text constructor
+    let mut env = IEnvironment::new();
+    let pgr = Program::new(
+        &"size: int = 1;
+    begin
+    x: shape = (0,0)--(size,0)(||);
+    draw x;
+    "
+        .into(),
+    )
+    .unwrap();
+    env.stable_push("text".into(), pgr);
+
+    let i1 = Expr::SCall {
+        name: Some("text".into()),
+        args: vec![("size".to_owned(), Expr::Integer(5).into())]
+            .into_iter()
+            .collect(),
+        path_poly: None,
+    }
+    .interpret(&mut env)
+    .unwrap();
+
+    let shape = i1.get_shape().unwrap();
+    assert_eq!(shape.get_figures()[0].get_text(), None);
+    assert_eq!(shape.max_x(), 5.into());
+}
+
 #[test]
 pub fn fcall_remove() {
     let mut env = IEnvironment::new();
@@ -1732,8 +1795,8 @@ pub fn fcall_remove() {
     let i1 = Expr::FCall {
         name: "remove".into(),
         args: vec![
-            Expr::Array(vec![Expr::Integer(1), Expr::Integer(2), Expr::Integer(3)]),
-            Expr::Integer(1),
+            Expr::Array(vec![Expr::Integer(1).into(), Expr::Integer(2).into(), Expr::Integer(3).into()]).into(),
+            Expr::Integer(1).into(),
         ],
     }
     .interpret(&mut env)
@@ -1764,8 +1827,8 @@ pub fn fcall_push() {
     let i1 = Expr::FCall {
         name: "push".into(),
         args: vec![
-            Expr::Array(vec![Expr::Integer(1), Expr::Integer(2), Expr::Integer(3)]),
-            Expr::Integer(4),
+            Expr::Array(vec![Expr::Integer(1).into(), Expr::Integer(2).into(), Expr::Integer(3).into()]).into(),
+            Expr::Integer(4).into(),
         ],
     }
     .interpret(&mut env)
@@ -1781,3 +1844,422 @@ pub fn fcall_push() {
         ])
     )
 }
+
+#[test]
+fn string_concatenation() {
+    let mut env = IEnvironment::new();
+    let i1 = Expr::BinaryOperation {
+        lhs: Expr::String("hello".into()).into(),
+        rhs: Expr::String(" world".into()).into(),
+        operator: BinaryOperator::Add,
+    }
+    .interpret(&mut env)
+    .unwrap();
+    assert_eq!(i1, Value::String("hello world".into()))
+}
+
+#[test]
+fn string_size_and_index() {
+    let mut env = IEnvironment::new();
+    env.vtable_push("s".into(), Value::String("høj".into()));
+
+    let i1 = Expr::Member {
+        identifier: "s".into(),
+        member_access: "size".into(),
+    }
+    .interpret(&mut env)
+    .unwrap();
+    let i2 = Expr::ArrayIndex {
+        identifier: Expr::Variable("s".into()).into(),
+        index: Expr::Integer(1).into(),
+    }
+    .interpret(&mut env)
+    .unwrap();
+    let i3 = Expr::ArrayIndex {
+        identifier: Expr::Variable("s".into()).into(),
+        index: Expr::Integer(3).into(),
+    }
+    .interpret(&mut env);
+
+    assert_eq!(i1, Value::Integer(3));
+    assert_eq!(i2, Value::String("ø".into()));
+    assert!(i3
+        .unwrap_err()
+        .downcast_ref::<errors::ArrayOutOfBoundsWithNumbers>()
+        .is_some());
+}
+
+#[test]
+fn scall_text_bounds() {
+    let mut env = IEnvironment::new();
+    let i1 = Expr::SCall {
+        name: Some("text".into()),
+        args: [
+            ("content".into(), Expr::String("hello".into()).into()),
+            ("size".into(), Expr::Integer(10).into()),
+        ]
+        .into_iter()
+        .collect(),
+        path_poly: None,
+    }
+    .interpret(&mut env)
+    .unwrap()
+    .get_shape()
+    .unwrap();
+
+    assert_eq!(i1.get_figures()[0].get_text(), Some("hello"));
+    assert!(i1.width().approx_eq(&Value::Float(30.0), 1e-9));
+    assert!(i1.height().approx_eq(&Value::Float(10.0), 1e-9));
+}
+
+#[test]
+fn scall_text_size_not_positive() {
+    let mut env = IEnvironment::new();
+    let i1 = Expr::SCall {
+        name: Some("text".into()),
+        args: [
+            ("content".into(), Expr::String("hello".into()).into()),
+            ("size".into(), Expr::Integer(0).into()),
+        ]
+        .into_iter()
+        .collect(),
+        path_poly: None,
+    }
+    .interpret(&mut env);
+
+    assert!(i1
+        .unwrap_err()
+        .downcast_ref::<errors::TextSizeNotPositive>()
+        .is_some());
+}
+
+#[test]
+fn math_builtins() {
+    let code = "begin
+a: float = sqrt(16);
+b: int = pow(2, 10);
+c: int = abs(-3);
+d: float = sin(90);
+e: float = atan2(1, 1);
+f: int = clamp(15, 0, 10);
+g: float = lerp(0, 10, 0.25);
+h: int = round(2.5);
+return 0;";
+    let mut program = Program::new(&code.to_string()).unwrap();
+    program.interpret().unwrap();
+
+    let mut value = |name: &str| program.ienvironment.vtable_find(name.into()).unwrap().clone();
+    assert!(value("a").approx_eq(&Value::Float(4.0), 1e-9));
+    assert_eq!(value("b"), Value::Integer(1024));
+    assert_eq!(value("c"), Value::Integer(3));
+    assert!(value("d").approx_eq(&Value::Float(1.0), 1e-9));
+    assert!(value("e").approx_eq(&Value::Float(45.0), 1e-9));
+    assert_eq!(value("f"), Value::Integer(10));
+    assert!(value("g").approx_eq(&Value::Float(2.5), 1e-9));
+    assert_eq!(value("h"), Value::Integer(3));
+}
+
+#[test]
+fn math_constant_pi() {
+    let mut env = IEnvironment::new();
+    let i1 = Expr::Variable("PI".into()).interpret(&mut env).unwrap();
+    assert_eq!(i1, Value::Float(std::f64::consts::PI));
+}
+
+#[test]
+fn math_builtin_domain_error() {
+    let mut env = IEnvironment::new();
+    let i1 = Expr::FCall {
+        name: "sqrt".into(),
+        args: vec![Expr::Integer(-4).into()],
+    }
+    .interpret(&mut env);
+
+    assert!(i1.unwrap_err().downcast_ref::<errors::MathError>().is_some());
+}
+
+#[test]
+fn math_trig_at_right_angles() {
+    let mut env = IEnvironment::new();
+    let mut call = |name: &str, degrees: i64| {
+        Expr::FCall {
+            name: name.into(),
+            args: vec![Expr::Integer(degrees).into()],
+        }
+        .interpret(&mut env)
+    };
+    assert_eq!(call("sin", -180).unwrap().to_string(), "0.0");
+    assert_eq!(call("cos", 90).unwrap(), Value::Float(0.));
+    assert_eq!(call("tan", 45).unwrap(), Value::Float(1.));
+    assert!(call("tan", 90).unwrap_err().downcast_ref::<errors::MathError>().is_some());
+    assert!(call("tan", -270).unwrap_err().downcast_ref::<errors::MathError>().is_some());
+}
+
+#[test]
+fn declared_function_shadows_math_builtin() {
+    let code = "begin
+abs(x: int): int -> {
+    return 7;
+}
+a: int = abs(-3);
+return 0;";
+    let mut program = Program::new(&code.to_string()).unwrap();
+    program.type_check().unwrap();
+    program.interpret().unwrap();
+
+    assert_eq!(
+        program.ienvironment.vtable_find("a".into()).unwrap().clone(),
+        Value::Integer(7)
+    );
+}
+
+#[test]
+fn array_builtins() {
+    let code = "begin
+a: int[] = [3, 1, 2];
+b: int[] = sort(concat(a, [0]));
+c: int[] = slice(reverse(a), 1, 3);
+d: int[] = insert(a, 3, 9);
+e: bool = contains(a, 2);
+f: int = index_of(a, 5);
+g: string[] = fill(2, \"x\");
+return 0;";
+    let mut program = Program::new(&code.to_string()).unwrap();
+    program.type_check().unwrap();
+    program.interpret().unwrap();
+
+    let mut value = |name: &str| program.ienvironment.vtable_find(name.into()).unwrap().clone();
+    let ints = |ints: &[i64]| Value::Array(ints.iter().map(|i| Value::Integer(*i)).collect());
+    assert_eq!(value("b"), ints(&[0, 1, 2, 3]));
+    assert_eq!(value("c"), ints(&[1, 3]));
+    assert_eq!(value("d"), ints(&[3, 1, 2, 9]));
+    assert_eq!(value("e"), Value::Boolean(true));
+    assert_eq!(value("f"), Value::Integer(-1));
+    assert_eq!(
+        value("g"),
+        Value::Array(vec![Value::String("x".into()), Value::String("x".into())])
+    );
+}
+
+#[test]
+fn fcall_remove_out_of_bounds() {
+    let mut env = IEnvironment::new();
+    let i1 = Expr::FCall {
+        name: "remove".into(),
+        args: vec![
+            Expr::Array(vec![Expr::Integer(1).into()]).into(),
+            Expr::Integer(1).into(),
+        ],
+    }
+    .interpret(&mut env);
+
+    assert!(i1
+        .unwrap_err()
+        .downcast_ref::<errors::ArrayOutOfBoundsWithNumbers>()
+        .is_some());
+}
+
+#[test]
+fn fcall_fill_over_length_limit() {
+    let mut env = IEnvironment::new();
+    let i1 = Expr::FCall {
+        name: "fill".into(),
+        args: vec![Expr::Integer(1_000_000_000_000_000).into(), Expr::Integer(0).into()],
+    }
+    .interpret(&mut env);
+
+    assert!(i1
+        .unwrap_err()
+        .downcast_ref::<errors::ArrayLengthLimitReached>()
+        .is_some());
+}
+
+#[test]
+fn fcall_slice_reversed_bounds() {
+    let mut env = IEnvironment::new();
+    let i1 = Expr::FCall {
+        name: "slice".into(),
+        args: vec![
+            Expr::Array(vec![Expr::Integer(1).into(), Expr::Integer(2).into()]).into(),
+            Expr::Integer(2).into(),
+            Expr::Integer(1).into(),
+        ],
+    }
+    .interpret(&mut env);
+
+    assert!(i1
+        .unwrap_err()
+        .downcast_ref::<errors::ArrayOutOfBoundsWithNumbers>()
+        .is_some());
+}
+
+#[test]
+fn numeric_conversions() {
+    let code = "n: int = 7;
+begin
+a: int = int(-2.7);
+b: float = float(n) / 2;
+c: int = n / 2;
+d: int = round(2.5);
+e: bool = n == 7.0;
+return 0;";
+    let mut program = Program::new(&code.to_string()).unwrap();
+    program.type_check().unwrap();
+    program.interpret().unwrap();
+
+    let mut value = |name: &str| program.ienvironment.vtable_find(name.into()).unwrap().clone();
+    assert_eq!(value("a"), Value::Integer(-2));
+    assert_eq!(value("b"), Value::Float(3.5));
+    assert_eq!(value("c"), Value::Integer(3));
+    assert_eq!(value("d"), Value::Integer(3));
+    assert_eq!(value("e"), Value::Boolean(true));
+}
+
+#[test]
+fn non_finite_operands_are_errors() {
+    let mut env = IEnvironment::new();
+    let operation = |lhs: f64, operator| Expr::BinaryOperation {
+        lhs: Expr::Float(lhs).into(),
+        rhs: Expr::Integer(1).into(),
+        operator,
+    };
+    let i1 = operation(f64::NAN, BinaryOperator::Divide).interpret(&mut env);
+    let i2 = operation(f64::INFINITY, BinaryOperator::LessThan).interpret(&mut env);
+    let i3 = operation(f64::MAX, BinaryOperator::Add).interpret(&mut env);
+    let i4 = operation(f64::MAX, BinaryOperator::GreaterThan).interpret(&mut env);
+
+    assert!(i1.unwrap_err().downcast_ref::<errors::MathError>().is_some());
+    assert!(i2.unwrap_err().downcast_ref::<errors::MathError>().is_some());
+    assert_eq!(i3.unwrap(), Value::Float(f64::MAX));
+    assert_eq!(i4.unwrap(), Value::Boolean(true));
+}
+
+#[test]
+fn integer_modulus_by_zero() {
+    let mut env = IEnvironment::new();
+    let i1 = Expr::BinaryOperation {
+        lhs: Expr::Integer(5).into(),
+        rhs: Expr::Integer(0).into(),
+        operator: BinaryOperator::Modulus,
+    }
+    .interpret(&mut env);
+
+    assert!(i1.unwrap_err().downcast_ref::<errors::DivideByZero>().is_some());
+}
+
+#[test]
+fn transform_builtins_and_apply() {
+    let code = "begin
+t: transform = translation((10, 0)) * rotation(90);
+s: shape = apply t to (0, 0)--(2, 0)(||);
+w: float = s.width;
+h: float = s.height;
+x: float = s.x;
+return 0;";
+    let mut program = Program::new(&code.to_string()).unwrap();
+    program.type_check().unwrap();
+    program.interpret().unwrap();
+
+    let mut value = |name: &str| program.ienvironment.vtable_find(name.into()).unwrap().clone();
+    assert_eq!(value("w"), Value::Float(0.));
+    assert_eq!(value("h"), Value::Float(2.));
+    assert_eq!(value("x"), Value::Float(10.));
+
+    let mut env = IEnvironment::new();
+    let skew = Expr::FCall {
+        name: "skew".into(),
+        args: vec![Expr::Integer(90).into(), Expr::Integer(0).into()],
+    }
+    .interpret(&mut env);
+    assert!(skew.unwrap_err().downcast_ref::<errors::MathError>().is_some());
+}
+
+#[test]
+fn geometry_builtins_and_members() {
+    let code = "begin
+s: shape = (0, 0)--(4, 0)--(4, 2)--(0, 2)--*(||);
+t: shape = (3, 1)--(6, 1)--(6, 5)--*(||);
+far: shape = (10, 10)--(12, 10)--(12, 12)--*(||);
+a: float = s.area;
+p: float = s.perimeter;
+c: point = s.centroid;
+inside: bool = contains(s, (1, 1));
+outside: bool = contains(s, (5, 1));
+listed: bool = contains([1, 2], 2);
+near: bool = overlaps(s, t);
+apart: bool = overlaps(s, far);
+d: float = distance((0, 0), (3, 4));
+return 0;";
+    let mut program = Program::new(&code.to_string()).unwrap();
+    program.type_check().unwrap();
+    program.interpret().unwrap();
+
+    let mut value = |name: &str| program.ienvironment.vtable_find(name.into()).unwrap().clone();
+    assert!(value("a").approx_eq(&Value::Float(8.), 1e-9));
+    assert!(value("p").approx_eq(&Value::Float(12.), 1e-9));
+    assert!(value("c").get_point().unwrap().approx_eq(&(2., 1.).into(), 1e-9));
+    assert_eq!(value("inside"), Value::Boolean(true));
+    assert_eq!(value("outside"), Value::Boolean(false));
+    assert_eq!(value("listed"), Value::Boolean(true));
+    assert_eq!(value("near"), Value::Boolean(true));
+    assert_eq!(value("apart"), Value::Boolean(false));
+    assert!(value("d").approx_eq(&Value::Float(5.), 1e-9));
+}
+
+#[test]
+fn primitive_shape_calls() {
+    let code = "begin
+c: shape = circle(|radius = 2|);
+r: shape = rect(|width = 4.0, height = 2.0, corner_radius = 0.5|);
+p: shape = regular_polygon(|sides = 6, radius = 1.0|);
+w: float = c.width;
+h: float = r.height;
+return 0;";
+    let mut program = Program::new(&code.to_string()).unwrap();
+    program.type_check().unwrap();
+    program.interpret().unwrap();
+
+    let mut value = |name: &str| program.ienvironment.vtable_find(name.into()).unwrap().clone();
+    assert!(value("w").approx_eq(&Value::Float(4.), 1e-9));
+    assert!(value("h").approx_eq(&Value::Float(2.), 1e-9));
+    assert_eq!(value("p").get_shape().unwrap().get_figures()[0].get_lines().len(), 6);
+
+    let mut env = IEnvironment::new();
+    let call = |name: &str, args: Vec<(&str, Expr)>| Expr::SCall {
+        name: Some(name.into()),
+        args: args.into_iter().map(|(key, arg)| (key.into(), arg.into())).collect(),
+        path_poly: None,
+    };
+    let i1 = call("ellipse", vec![("rx", Expr::Float(1.)), ("ry", Expr::Float(-1.))]).interpret(&mut env);
+    let i2 = call("regular_polygon", vec![("sides", Expr::Integer(2)), ("radius", Expr::Float(1.))]).interpret(&mut env);
+    assert!(i1.unwrap_err().downcast_ref::<errors::PrimitiveSizeNotPositive>().is_some());
+    assert!(i2.unwrap_err().downcast_ref::<errors::PrimitiveSidesInvalid>().is_some());
+}
+
+#[test]
+fn arc_path_operations() {
+    let code = "begin
+dome: polygon = (0, 0) arc(2, false, true) (4, 0)--*;
+bowl: path = (0, 0)--(1, 0) arc((2.0, 1.0), false, false) (5, 0) ~~ (6, 1) ~~ (7, 0);
+w: float = dome.width;
+h: float = dome.height;
+return 0;";
+    let mut program = Program::new(&code.to_string()).unwrap();
+    program.type_check().unwrap();
+    program.interpret().unwrap();
+
+    let mut value = |name: &str| program.ienvironment.vtable_find(name.into()).unwrap().clone();
+    assert!(value("w").approx_eq(&Value::Float(4.), 1e-9));
+    assert!(value("h").approx_eq(&Value::Float(2.), 1e-9));
+
+    let Value::Figure(bowl) = value("bowl") else {
+        panic!("bowl is not a path")
+    };
+    let lines = bowl.get_lines();
+    assert!(matches!(
+        lines.as_slice(),
+        [Line::Straight(_), Line::Arc(_), Line::Curved(_)]
+    ));
+    //The arc dips below the path by its y radius
+    assert!(bowl.get_min_y().approx_eq(&Value::Float(-1.), 1e-9));
+}
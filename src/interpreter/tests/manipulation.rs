@@ -1,6 +1,7 @@
-use crate::interpreter::{data_types::{direction::Direction, line::Line}, value::Value};
+use crate::interpreter::{data_types::{direction::Direction, figure::Figure, figurearray::FigureArray, line::Line, point::Point}, value::Value};
+use crate::program::operators::shapeoperator::ShapeOperator;
 
-use super::super::utils::manipulation::*;
+use super::super::utils::{clipping::combine, geometry, layout, manipulation::*};
 use super::*;
 
 /****************************** Place *******************************/
@@ -194,11 +195,20 @@ fn test_place_with_offset() {
     );
 }
 
+/****************************** Move *******************************/
+#[test]
+fn translate_moves_every_point() {
+    let moved = translate(triangle(), &(-10, 5).into());
+    let expected: Vec<Point> = vec![(0, 15).into(), (4, 15).into(), (0, 17).into()];
+    assert_eq!(first_points(&moved), expected);
+    assert_eq!(moved.get_top_left(), (0, 17).into());
+}
+
 /*********************************************** Scale *****************************************/
 #[test]
 fn scale_operation() {
     let i1 = basic_square().get_shape().unwrap();
-    let i2 = scale(i1, 2.into()).unwrap(); 
+    let i2 = scale(i1, 2.into(), None).unwrap(); 
 
     assert_eq!(i2.get_figures()[0].get_lines().to_vec(), 
         vec![
@@ -256,6 +266,22 @@ fn test_scale_point() {
 }
 
 
+#[test]
+fn scale_by_point_around_anchor() {
+    //Top left of the triangle is (10, 12), its center (12, 11)
+    let stretched = scale(triangle(), Value::Point((2, 3).into()), None).unwrap();
+    let expected: Vec<Point> = vec![(10, 6).into(), (18, 6).into(), (10, 12).into()];
+    assert_eq!(first_points(&stretched), expected);
+
+    let centered = scale(triangle(), 2.into(), Some(triangle().get_center())).unwrap();
+    assert_eq!(centered.get_center(), triangle().get_center());
+    assert_eq!((centered.width(), centered.height()), (8.0.into(), 4.0.into()));
+
+    let around_origin = scale(triangle(), Value::Point((1, -1).into()), Some((0, 0).into())).unwrap();
+    let expected: Vec<Point> = vec![(10, -10).into(), (14, -10).into(), (10, -12).into()];
+    assert_eq!(first_points(&around_origin), expected);
+}
+
 /*********************************************** Rotate ****************************************/
 #[test]
 fn rotate_point_test() {
@@ -289,7 +315,7 @@ fn rotate_point_test() {
 #[test]
 fn rotate_test() {
     let square = basic_square().get_shape().unwrap();
-    let i1 = rotate(square, 45.into());
+    let i1 = rotate(square, 45.into(), None);
     let epsilon = 1e-6;
 
     let rotated_lines = vec![
@@ -306,7 +332,7 @@ fn rotate_test() {
     ); 
 
     let triangle = basic_triangle().get_shape().unwrap();
-    let i1 = rotate(triangle, (-90).into());
+    let i1 = rotate(triangle, (-90).into(), None);
     let epsilon = 1e-6;
 
     let rotated_lines = vec![
@@ -321,3 +347,292 @@ fn rotate_test() {
         )
     )    
 }
+
+#[test]
+fn rotate_around_point() {
+    let rotated = rotate(triangle(), 90.into(), Some((0, 0).into()));
+    let expected: Vec<Point> = vec![(10, -10).into(), (10, -14).into(), (12, -10).into()];
+    first_points(&rotated).iter().zip(expected.iter()).for_each(|(p1, p2)| assert!(p1.approx_eq(p2, 1e-9)));
+}
+
+/****************************** Mirror *******************************/
+fn triangle() -> FigureArray {
+    let corners: Vec<Point> = vec![(10, 10).into(), (14, 10).into(), (10, 12).into()];
+    let lines = (0..3)
+        .map(|idx| Line::Straight(vec![corners[idx].clone(), corners[(idx + 1) % 3].clone()]))
+        .collect::<Vec<Line>>();
+    vec![Figure::from(lines)].into()
+}
+
+fn first_points(shape: &FigureArray) -> Vec<Point> {
+    shape.get_figures()[0]
+        .get_lines()
+        .iter()
+        .map(|line| line.get_first_point().unwrap().clone())
+        .collect()
+}
+
+#[test]
+fn mirror_keeps_top_left() {
+    let horizontal = mirror(triangle(), &(0, 0).into(), &(0, 1).into()).unwrap();
+    let expected: Vec<Point> = vec![(14, 10).into(), (10, 10).into(), (14, 12).into()];
+    first_points(&horizontal).iter().zip(expected.iter()).for_each(|(p1, p2)| assert!(p1.approx_eq(p2, 1e-9)));
+
+    let vertical = mirror(triangle(), &(5, 5).into(), &(6, 5).into()).unwrap();
+    let expected: Vec<Point> = vec![(10, 12).into(), (14, 12).into(), (10, 10).into()];
+    first_points(&vertical).iter().zip(expected.iter()).for_each(|(p1, p2)| assert!(p1.approx_eq(p2, 1e-9)));
+
+    //Across the diagonal x and y swap, before the shape is moved back to its top left corner
+    let diagonal = mirror(triangle(), &(0, 0).into(), &(3, 3).into()).unwrap();
+    let expected: Vec<Point> = vec![(10, 8).into(), (10, 12).into(), (12, 8).into()];
+    first_points(&diagonal).iter().zip(expected.iter()).for_each(|(p1, p2)| assert!(p1.approx_eq(p2, 1e-9)));
+    assert!(diagonal.get_top_left().approx_eq(&triangle().get_top_left(), 1e-9));
+}
+
+#[test]
+fn mirror_needs_a_line() {
+    assert!(mirror(triangle(), &(1, 1).into(), &(1, 1).into()).is_err());
+}
+
+/****************************** Combine *******************************/
+fn square_at(x: f64, y: f64, size: f64) -> FigureArray {
+    let corners: Vec<Point> = vec![
+        (x, y).into(),
+        (x + size, y).into(),
+        (x + size, y + size).into(),
+        (x, y + size).into(),
+    ];
+    let lines = (0..4)
+        .map(|idx| Line::Straight(vec![corners[idx].clone(), corners[(idx + 1) % 4].clone()]))
+        .collect::<Vec<Line>>();
+    vec![Figure::from(lines)].into()
+}
+
+//Signed area of all contours, holes run the other way and count negatively
+fn area(shape: &FigureArray) -> f64 {
+    shape
+        .get_figures()
+        .iter()
+        .flat_map(|fig| fig.get_lines())
+        .map(|line| {
+            let (p, q) = (line.get_first_point().unwrap(), line.get_last_point().unwrap());
+            (p.get_x_f64() * q.get_y_f64() - q.get_x_f64() * p.get_y_f64()) / 2.
+        })
+        .sum()
+}
+
+#[test]
+fn combine_overlapping_squares() {
+    let (a, b) = (square_at(0., 0., 2.), square_at(1., 1., 2.));
+
+    let union = combine(&a, &b, ShapeOperator::Union, 0.1);
+    assert!((area(&union) - 7.).abs() < 1e-9);
+    assert_eq!((union.width(), union.height()), (3.0.into(), 3.0.into()));
+
+    let intersection = combine(&a, &b, ShapeOperator::Intersect, 0.1);
+    assert!((area(&intersection) - 1.).abs() < 1e-9);
+    assert_eq!(intersection.get_top_left(), (1., 2.).into());
+
+    let difference = combine(&a, &b, ShapeOperator::Subtract, 0.1);
+    assert!((area(&difference) - 3.).abs() < 1e-9);
+
+    let xor = combine(&a, &b, ShapeOperator::Xor, 0.1);
+    assert!((area(&xor) - 6.).abs() < 1e-9);
+}
+
+#[test]
+fn combine_hole_and_shared_edges() {
+    let outer = square_at(0., 0., 4.);
+
+    //The hole is a second contour running the other way
+    let with_hole = combine(&outer, &square_at(1., 1., 2.), ShapeOperator::Subtract, 0.1);
+    assert_eq!(with_hole.get_figures()[0].get_contours().len(), 2);
+    assert!(with_hole.get_figures()[0].is_closed().unwrap());
+    assert!((area(&with_hole) - 12.).abs() < 1e-9);
+
+    //Squares sharing an edge merge into one rectangle without the shared edge
+    let merged = combine(&outer, &square_at(4., 0., 4.), ShapeOperator::Union, 0.1);
+    assert_eq!(merged.get_figures()[0].get_lines().len(), 4);
+    assert!((area(&merged) - 32.).abs() < 1e-9);
+
+    let apart = combine(&outer, &square_at(5., 5., 1.), ShapeOperator::Intersect, 0.1);
+    assert!(apart.get_figures().is_empty());
+    let same = combine(&outer, &outer, ShapeOperator::Xor, 0.1);
+    assert!(same.get_figures().is_empty());
+}
+
+#[test]
+fn combine_flattens_curves_within_tolerance() {
+    //A parabola over a width of 20 with its control point at height 20 encloses 400 / 3
+    let arch: FigureArray = vec![Figure::from(vec![
+        Line::Curved(vec![(0., 0.).into(), (10., 20.).into(), (20., 0.).into()]),
+        Line::Straight(vec![(20., 0.).into(), (0., 0.).into()]),
+    ])]
+    .into();
+    let exact = 400. / 3.;
+
+    let coarse = combine(&arch, &arch, ShapeOperator::Union, 1.);
+    let fine = combine(&arch, &arch, ShapeOperator::Union, 0.01);
+    //The segments lie inside the curve, so the area can only shrink, by at most tolerance * length
+    assert!(area(&coarse) < exact && exact - area(&coarse) < 1. * 30.);
+    assert!(area(&fine) < exact && exact - area(&fine) < 0.01 * 30.);
+    assert!(fine.get_figures()[0].get_lines().len() > coarse.get_figures()[0].get_lines().len());
+}
+
+/****************************** Layout *******************************/
+fn top_lefts(shape: &FigureArray) -> Vec<Point> {
+    shape
+        .get_figures()
+        .iter()
+        .map(|fig| FigureArray::from(vec![fig.clone()]).get_top_left())
+        .collect()
+}
+
+#[test]
+fn layout_row_and_column_align_with_first_shape() {
+    let shapes = || vec![square_at(5., 5., 2.), square_at(0., 0., 4.), square_at(0., 0., 1.)];
+
+    let row = layout::row(shapes(), 1., "bottom").unwrap();
+    let expected: Vec<Point> = vec![(5., 7.).into(), (8., 9.).into(), (13., 6.).into()];
+    assert_eq!(top_lefts(&row), expected);
+
+    let column = layout::column(shapes(), 0.5, "center").unwrap();
+    let expected: Vec<Point> = vec![(5., 7.).into(), (4., 4.5).into(), (5.5, 0.).into()];
+    assert_eq!(top_lefts(&column), expected);
+
+    assert!(layout::row(shapes(), 1., "left").is_err());
+    assert!(layout::row(Vec::new(), 1., "top").unwrap().get_figures().is_empty());
+}
+
+#[test]
+fn layout_leaves_out_empty_shapes() {
+    let shapes = || vec![FigureArray::new(), square_at(5., 5., 2.), FigureArray::new(), square_at(0., 0., 1.)];
+
+    //The first shape with figures is where the row starts
+    let row = layout::row(shapes(), 1., "top").unwrap();
+    let expected: Vec<Point> = vec![(5., 7.).into(), (8., 7.).into()];
+    assert_eq!(top_lefts(&row), expected);
+
+    let spread = layout::distribute(shapes(), 10.).unwrap();
+    let expected: Vec<Point> = vec![(5., 7.).into(), (14., 7.).into()];
+    assert_eq!(top_lefts(&spread), expected);
+}
+
+#[test]
+fn layout_grid_and_distribute() {
+    let shapes = || vec![square_at(0., 0., 2.), square_at(3., 3., 4.), square_at(0., 0., 2.)];
+
+    //Cells are 4 by 4 and the smaller squares are centered in them
+    let grid = layout::grid(shapes(), 2, 1.).unwrap();
+    let expected: Vec<Point> = vec![(1., 1.).into(), (5., 2.).into(), (1., -4.).into()];
+    assert_eq!(top_lefts(&grid), expected);
+    assert!(layout::grid(shapes(), 0, 1.).is_err());
+
+    //The tops line up with the first shape
+    let spread = layout::distribute(shapes(), 20.).unwrap();
+    let expected: Vec<Point> = vec![(0., 2.).into(), (8., 2.).into(), (18., 2.).into()];
+    assert_eq!(top_lefts(&spread), expected);
+}
+
+/****************************** Geometry *******************************/
+#[test]
+fn geometry_of_shape_with_hole() {
+    let with_hole = combine(&square_at(0., 0., 4.), &square_at(2., 1., 1.), ShapeOperator::Subtract, 0.1);
+
+    assert!((geometry::area(&with_hole, 0.1) - 15.).abs() < 1e-9);
+    assert!((geometry::perimeter(&with_hole, 0.1) - 20.).abs() < 1e-9);
+    //The hole pulls the centroid away from it
+    let centroid = geometry::centroid(&with_hole, 0.1);
+    assert!(centroid.approx_eq(&(2. - 0.5 / 15., 2. + 0.5 / 15.).into(), 1e-9));
+    assert!(!geometry::contains(&with_hole, &(2.5, 1.5).into(), 0.1));
+    assert!(geometry::contains(&with_hole, &(0.5, 0.5).into(), 0.1));
+}
+
+#[test]
+fn geometry_flattens_curves_and_keeps_paths_open() {
+    let arch: FigureArray = vec![Figure::from(vec![
+        Line::Curved(vec![(0., 0.).into(), (10., 20.).into(), (20., 0.).into()]),
+        Line::Straight(vec![(20., 0.).into(), (0., 0.).into()]),
+    ])]
+    .into();
+    assert!((geometry::area(&arch, 0.01) - 400. / 3.).abs() < 0.01 * 30.);
+
+    //An open path is filled as if it was closed, but its length is only what is drawn
+    let open: FigureArray = vec![Figure::from(vec![
+        Line::Straight(vec![(0., 0.).into(), (3., 4.).into()]),
+        Line::Straight(vec![(3., 4.).into(), (3., 0.).into()]),
+    ])]
+    .into();
+    assert!((geometry::area(&open, 0.1) - 6.).abs() < 1e-9);
+    assert!((geometry::perimeter(&open, 0.1) - 9.).abs() < 1e-9);
+    let line: FigureArray = vec![Figure::from(vec![Line::Straight(vec![(0., 0.).into(), (3., 4.).into()])])].into();
+    assert_eq!(geometry::area(&line, 0.1), 0.);
+    assert_eq!(geometry::centroid(&line, 0.1), line.get_center());
+}
+
+#[test]
+fn geometry_of_crossing_and_empty_shapes() {
+    //Both halves of a bowtie are filled, the same way contains sees it
+    let bowtie: FigureArray = vec![Figure::from(vec![
+        Line::Straight(vec![(0., 0.).into(), (10., 0.).into()]),
+        Line::Straight(vec![(10., 0.).into(), (0., 10.).into()]),
+        Line::Straight(vec![(0., 10.).into(), (10., 10.).into()]),
+        Line::Straight(vec![(10., 10.).into(), (0., 0.).into()]),
+    ])]
+    .into();
+    assert!((geometry::area(&bowtie, 0.1) - 50.).abs() < 1e-9);
+    assert!(geometry::contains(&bowtie, &(5., 9.).into(), 0.1));
+    assert!(geometry::centroid(&bowtie, 0.1).approx_eq(&(5., 5.).into(), 1e-9));
+
+    let empty = FigureArray::new();
+    assert!(geometry::area(&empty, 0.1).is_sign_positive());
+    assert!(geometry::perimeter(&empty, 0.1).is_sign_positive());
+}
+
+/****************************** Arcs *******************************/
+fn half_circle() -> FigureArray {
+    //Clockwise from (0, 0) to (4, 0), over the top of the circle around (2, 0)
+    vec![Figure::from(vec![Line::arc((0, 0).into(), (4, 0).into(), (2., 2.), false, true)])].into()
+}
+
+fn bounds(shape: &FigureArray) -> Vec<f64> {
+    [shape.min_x(), shape.min_y(), shape.max_x(), shape.max_y()]
+        .iter()
+        .map(|v| (v.get_float().unwrap() * 1e9).round() / 1e9)
+        .collect()
+}
+
+#[test]
+fn arc_bounds_follow_the_ellipse() {
+    assert_eq!(bounds(&half_circle()), vec![0., 0., 4., 2.]);
+    //The control hull holds the whole ellipse
+    let (min_x, min_y, max_x, max_y) = half_circle().control_hull();
+    assert_eq!((min_x, min_y, max_x, max_y), (0.0.into(), (-2.0).into(), 4.0.into(), 2.0.into()));
+
+    let closed = vec![Figure::from(vec![
+        Line::arc((0, 0).into(), (4, 0).into(), (2., 2.), false, true),
+        Line::Straight(vec![(4, 0).into(), (0, 0).into()]),
+    ])]
+    .into();
+    assert!((geometry::area(&closed, 0.001) - 2. * std::f64::consts::PI).abs() < 0.01);
+}
+
+#[test]
+fn arc_rotate_and_scale() {
+    //A quarter turn clockwise around the center of the circle leaves the right half
+    let rotated = rotate(half_circle(), 90.into(), Some((2, 0).into()));
+    assert_eq!(bounds(&rotated), vec![2., -2., 4., 2.]);
+
+    let squashed = scale(half_circle(), Value::Point((1., 0.5).into()), Some((0, 0).into())).unwrap();
+    assert_eq!(bounds(&squashed), vec![0., 0., 4., 1.]);
+    let arc = squashed.get_figures()[0].get_lines()[0].get_arc().unwrap();
+    let (rx, ry, rotation) = arc.axes();
+    assert!((rx - 2.).abs() < 1e-9 && (ry - 1.).abs() < 1e-9 && rotation.abs() < 1e-9);
+    assert!(!arc.is_large() && !arc.is_positive());
+
+    //Turning the squashed arc keeps its radii and turns its axes
+    let turned = rotate(squashed, 90.into(), Some((0, 0).into()));
+    let (rx, ry, rotation) = turned.get_figures()[0].get_lines()[0].get_arc().unwrap().axes();
+    assert!((rx - 2.).abs() < 1e-9 && (ry - 1.).abs() < 1e-9);
+    assert!((rotation.rem_euclid(180.) - 90.).abs() < 1e-9);
+}
@@ -4,11 +4,12 @@ use crate::{
         environment::IEnvironment,
         errors,
         value::Value,
-        InterpretE, InterpretS,
+        InterpretE, InterpretP, InterpretS,
     },
     program::{
         expression::Expr,
         operators::{binaryoperator::BinaryOperator, pathoperator::PathOperator},
+        program::Program,
         r#type::Type,
         statement::Stmt,
     },
@@ -20,7 +21,7 @@ fn var_decl() {
     let _ = Stmt::VarDecl {
         name: "x".into(),
         declared_type: Type::Int,
-        value: Expr::Integer(4),
+        value: Expr::Integer(4).into(),
     }
     .interpret(&mut env);
     assert_eq!(
@@ -39,13 +40,13 @@ fn func_decl_no_return() {
         statements: vec![Stmt::VarDecl {
             name: "y".into(),
             declared_type: Type::Int,
-            value: Expr::Integer(4),
-        }],
+            value: Expr::Integer(4).into(),
+        }.into()],
     }
     .interpret(&mut env);
     let i1 = Expr::FCall {
         name: "f".into(),
-        args: vec![Expr::Integer(4)],
+        args: vec![Expr::Integer(4).into()],
     }
     .interpret(&mut env);
     assert!(i1
@@ -62,13 +63,13 @@ fn func_decl_return() {
         name: "f".into(),
         return_type: Type::Bool,
         parameters: vec![("x".into(), Type::Int)],
-        statements: vec![Stmt::Return(Expr::Boolean(true))],
+        statements: vec![Stmt::Return(Expr::Boolean(true).into()).into()],
     }
     .interpret(&mut env);
     assert_eq!(
         Expr::FCall {
             name: "f".into(),
-            args: vec![Expr::Integer(4)]
+            args: vec![Expr::Integer(4).into()]
         }
         .interpret(&mut env)
         .unwrap(),
@@ -88,11 +89,11 @@ fn fork() {
                 lhs: Expr::Integer(1).into(),
                 rhs: Expr::Integer(3).into(),
                 operator: BinaryOperator::LessThan,
-            },
+            }.into(),
             vec![Stmt::Assign {
                 name: "x".into(),
-                value: Expr::Integer(8),
-            }],
+                value: Expr::Integer(8).into(),
+            }.into()],
         )],
         otherwise: Option::None,
     }
@@ -116,16 +117,16 @@ fn fork_otherwise() {
                 lhs: Expr::Integer(1).into(),
                 rhs: Expr::Integer(3).into(),
                 operator: BinaryOperator::GreaterThan,
-            },
+            }.into(),
             vec![Stmt::Assign {
                 name: "x".into(),
-                value: Expr::Integer(8),
-            }],
+                value: Expr::Integer(8).into(),
+            }.into()],
         )],
         otherwise: Some(vec![Stmt::Assign {
             name: "x".into(),
-            value: Expr::Integer(9),
-        }]),
+            value: Expr::Integer(9).into(),
+        }.into()]),
     }
     .interpret(&mut env);
 
@@ -149,8 +150,7 @@ fn draw_without_place() {
                     Expr::Integer(255).into(),
                     Expr::Integer(255).into(),
                     Expr::Integer(255).into(),
-                )
-                .into(),
+                ).into(),
             )]
             .into_iter()
             .collect(),
@@ -162,7 +162,7 @@ fn draw_without_place() {
                 }
                 .into(),
             ),
-        },
+        }.into(),
         point: None,
     }
     .interpret(&mut env)
@@ -210,8 +210,7 @@ fn draw_with_place() {
                     Expr::Integer(255).into(),
                     Expr::Integer(255).into(),
                     Expr::Integer(255).into(),
-                )
-                .into(),
+                ).into(),
             )]
             .into_iter()
             .collect(),
@@ -223,7 +222,7 @@ fn draw_with_place() {
                 }
                 .into(),
             ),
-        },
+        }.into(),
         point: Some(Expr::Point(Expr::Integer(4).into(), Expr::Integer(5).into()).into()),
     }
     .interpret(&mut env)
@@ -277,8 +276,8 @@ fn for_loop() {
     env.vtable_push("count".into(), Value::Integer(0));
     Stmt::For {
         counter: "i".into(),
-        from: Expr::Integer(0),
-        to: Expr::Integer(5),
+        from: Expr::Integer(0).into(),
+        to: Expr::Integer(5).into(),
         body: vec![Stmt::Assign {
             name: "count".into(),
             value: Expr::BinaryOperation {
@@ -287,7 +286,7 @@ fn for_loop() {
                 operator: BinaryOperator::Add,
             }
             .into(),
-        }],
+        }.into()],
     }
     .interpret(&mut env)
     .unwrap();
@@ -304,8 +303,8 @@ fn for_loop_return() {
     env.vtable_push("count".into(), Value::Integer(0));
     Stmt::For {
         counter: "i".into(),
-        from: Expr::Integer(0),
-        to: Expr::Integer(5),
+        from: Expr::Integer(0).into(),
+        to: Expr::Integer(5).into(),
         body: vec![
             Stmt::Assign {
                 name: "count".into(),
@@ -315,8 +314,8 @@ fn for_loop_return() {
                     operator: BinaryOperator::Add,
                 }
                 .into(),
-            },
-            Stmt::Return(Expr::Integer(0)),
+            }.into(),
+            Stmt::Return(Expr::Integer(0).into()).into(),
         ],
     }
     .interpret(&mut env)
@@ -364,13 +363,13 @@ fn decl_default() {
     let i1 = Stmt::Decl {
         name: "x".into(),
         declared_type: Type::Int,
-        value: Some(Expr::Integer(3)),
+        value: Some(Expr::Integer(3).into()),
     }
     .interpret(&mut env);
     Stmt::Decl {
         name: "y".into(),
         declared_type: Type::Int,
-        value: Some(Expr::Integer(3)),
+        value: Some(Expr::Integer(3).into()),
     }
     .interpret(&mut env).unwrap();
 
@@ -385,10 +384,91 @@ fn array_assign() {
 
     env.vtable_push("x".into(), Value::Array(vec![Value::Integer(1), Value::Integer(2), Value::Integer(3)]));
 
-    let i1 = Stmt::ArrayAssign{ name: "x".into(), value: Expr::Integer(5), index: Expr::Integer(1)}
+    let i1 = Stmt::ArrayAssign{ name: "x".into(), value: Expr::Integer(5).into(), index: Expr::Integer(1).into()}
     .interpret(&mut env);
 
     assert!(i1.is_ok());
 
     assert_eq!(env.vtable_find("x".into()).unwrap().clone(),Value::Array(vec![Value::Integer(1), Value::Integer(5), Value::Integer(3)]))
 }
+
+#[test]
+fn while_loop() {
+    let mut env = IEnvironment::new();
+
+    env.vtable_push("x".into(), Value::Integer(0));
+
+    Stmt::While {
+        condition: Expr::BinaryOperation {
+            lhs: Expr::Variable("x".into()).into(),
+            rhs: Expr::Integer(5).into(),
+            operator: BinaryOperator::LessThan,
+        }
+        .into(),
+        body: vec![Stmt::Assign {
+            name: "x".into(),
+            value: Expr::BinaryOperation {
+                lhs: Expr::Variable("x".into()).into(),
+                rhs: Expr::Integer(1).into(),
+                operator: BinaryOperator::Add,
+            }
+            .into(),
+        }
+        .into()],
+    }
+    .interpret(&mut env)
+    .unwrap();
+
+    assert_eq!(
+        env.vtable_find("x".into()).unwrap().clone(),
+        Value::Integer(5)
+    )
+}
+
+#[test]
+fn while_iteration_limit() {
+    let mut env = IEnvironment::new();
+    env.iteration_limit_set(10);
+
+    let err = Stmt::While {
+        condition: Expr::Boolean(true).into(),
+        body: vec![Stmt::VarDecl {
+            name: "x".into(),
+            declared_type: Type::Int,
+            value: Expr::Integer(1).into(),
+        }
+        .into()],
+    }
+    .interpret(&mut env)
+    .unwrap_err();
+
+    assert!(err.downcast_ref::<errors::IterationLimitReached>().is_some())
+}
+
+#[test]
+fn break_and_continue() {
+    let code = "begin
+    i: int = 0;
+    sum: int = 0;
+    while (true) {
+        i = i + 1;
+        fork {
+            (i == 2) -> { continue; }
+            (i == 5) -> { break; }
+        }
+        sum = sum + i;
+    }
+    for j in 0 to 10 {
+        fork { (j == 3) -> { break; } }
+        sum = sum + 100;
+    }
+    return sum;";
+    let mut program = Program::new(&code.to_string()).unwrap();
+
+    program.interpret().unwrap();
+
+    assert_eq!(
+        program.ienvironment.rvalue_get(),
+        Some(&Value::Integer(1 + 3 + 4 + 300))
+    )
+}
@@ -0,0 +1,363 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::interpreter::data_types::{
+    figure::Figure,
+    figurearray::FigureArray,
+    line::{bezier_point, Line},
+    point::Point,
+};
+use crate::program::operators::shapeoperator::ShapeOperator;
+
+pub(super) type Coord = (f64, f64);
+
+/// A closed polygon, the last corner connects back to the first
+pub(super) type Ring = Vec<Coord>;
+
+/*************************  Combine ****************************/
+/// Applies a boolean operation to the areas covered by two shapes.
+/// The area of a shape is what its figures fill with the nonzero rule, open figures are closed
+/// by a straight line the way a fill closes them. Curves are replaced by straight segments
+/// that stay within `tolerance` of the curve.
+///
+/// The result is one figure with the attributes of the first figure of `base`, outlines run
+/// counterclockwise and holes clockwise. An empty area gives a shape without figures.
+pub fn combine(
+    base: &FigureArray,
+    second: &FigureArray,
+    operator: ShapeOperator,
+    tolerance: f64,
+) -> FigureArray {
+    let base_rings = shape_rings(base, tolerance);
+    let second_rings = shape_rings(second, tolerance);
+
+    let mut graph = Graph::new(base_rings.iter().chain(&second_rings).flatten().flatten());
+    base_rings
+        .iter()
+        .chain(&second_rings)
+        .flatten()
+        .for_each(|ring| graph.add_ring(ring));
+    graph.split();
+
+    let boundary =
+        graph.boundary(|p| operator.apply(covers(&base_rings, p), covers(&second_rings, p)));
+
+    let mut figure = Figure::new();
+    for ring in graph.rings(&boundary) {
+        let corners: Vec<Point> = ring.into_iter().map(Point::from).collect();
+        for (idx, corner) in corners.iter().enumerate() {
+            let next = corners[(idx + 1) % corners.len()].clone();
+            figure.push_line_after(Line::Straight(vec![corner.clone(), next]));
+        }
+    }
+    if figure.get_lines().is_empty() {
+        return FigureArray::new();
+    }
+
+    //The result is a plain outline, a text box does not keep its text and a primitive is no longer one
+    if let Some(first) = base.get_figures().first() {
+        first
+            .get_attributes()
+            .iter()
+            .for_each(|(name, value)| figure.set_attribute((name.clone(), value.clone())));
+    }
+    vec![figure].into()
+}
+
+/// The outline of what the rings fill with the nonzero rule, split where the rings cross or overlap.
+/// Outlines run counterclockwise and holes clockwise, the same way `combine` gives them.
+pub(super) fn fill_rings(rings: &[Ring]) -> Vec<Ring> {
+    let mut graph = Graph::new(rings.iter().flatten());
+    rings.iter().for_each(|ring| graph.add_ring(ring));
+    graph.split();
+
+    let figure = [rings.to_vec()];
+    let boundary = graph.boundary(|p| covers(&figure, p));
+    graph.rings(&boundary)
+}
+
+/// The rings of every figure of a shape, one list per figure
+pub(super) fn shape_rings(shape: &FigureArray, tolerance: f64) -> Vec<Vec<Ring>> {
+    shape
+        .get_figures()
+        .iter()
+        .map(|figure| {
+            figure
+                .get_contours()
+                .into_iter()
+                .map(|contour| flatten(contour, tolerance))
+                .filter(|ring| ring.len() > 2)
+                .collect()
+        })
+        .collect()
+}
+
+/// Lines with two points are straight, curves are cut into their bezier pieces with `Line::get_segments`
+/// and arcs follow their ellipse, the same way the generators draw them.
+pub(super) fn flatten(contour: &[Line], tolerance: f64) -> Ring {
+    let mut ring: Ring = Vec::new();
+    for line in contour.iter().flat_map(Line::get_segments) {
+        if let Some(arc) = line.get_arc() {
+            let segments = arc.segment_count(tolerance);
+            ring.extend((0..=segments).map(|i| arc.point(i as f64 / segments as f64)));
+            continue;
+        }
+        let controls: Vec<Coord> = line.get_points().iter().map(coord).collect();
+        match controls.as_slice() {
+            [] => (),
+            [point] => ring.push(*point),
+            [start, end] => ring.extend([start, end]),
+            _ => {
+                let segments = segment_count(&controls, tolerance);
+                ring.push(controls[0]);
+                ring.extend(
+                    (1..=segments).map(|i| bezier_point(&controls, i as f64 / segments as f64)),
+                );
+            }
+        }
+    }
+    //Each line repeats the end of the previous one and the last line returns to the start
+    ring.dedup();
+    if ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    ring
+}
+
+/// How many straight segments keep a bezier curve within `tolerance`.
+/// Splitting a curve of degree d into n equal parts moves it at most
+/// d * (d - 1) / 8 * (largest second difference of the control points) / n²
+fn segment_count(controls: &[Coord], tolerance: f64) -> usize {
+    let degree = (controls.len() - 1) as f64;
+    let bend = controls
+        .windows(3)
+        .map(|w| length((w[0].0 - 2. * w[1].0 + w[2].0, w[0].1 - 2. * w[1].1 + w[2].1)))
+        .fold(0., f64::max);
+    let segments = (degree * (degree - 1.) / 8. * bend / tolerance)
+        .sqrt()
+        .ceil();
+    if segments.is_nan() {
+        1
+    } else {
+        segments.clamp(1., 1024.) as usize
+    }
+}
+
+/// Whether a point is inside any figure of a shape, each figure is filled with the nonzero rule
+pub(super) fn covers(figures: &[Vec<Ring>], p: Coord) -> bool {
+    figures.iter().any(|rings| winding(rings, p) != 0)
+}
+
+fn winding(rings: &[Ring], p: Coord) -> i32 {
+    let mut winding = 0;
+    for ring in rings {
+        for (idx, a) in ring.iter().enumerate() {
+            let b = ring[(idx + 1) % ring.len()];
+            let side = cross(sub(b, *a), sub(p, *a));
+            if a.1 <= p.1 && b.1 > p.1 && side > 0. {
+                winding += 1;
+            } else if a.1 > p.1 && b.1 <= p.1 && side < 0. {
+                winding -= 1;
+            }
+        }
+    }
+    winding
+}
+
+/// The edges of both shapes, split so that edges only meet at their ends.
+/// Points closer than `epsilon` are the same vertex.
+struct Graph {
+    vertices: Vec<Coord>,
+    edges: Vec<(usize, usize)>,
+    epsilon: f64,
+}
+
+impl Graph {
+    fn new<'a>(points: impl Iterator<Item = &'a Coord>) -> Self {
+        let size = points.fold(1_f64, |size, p| size.max(p.0.abs()).max(p.1.abs()));
+        Graph {
+            vertices: Vec::new(),
+            edges: Vec::new(),
+            epsilon: size * 1e-9,
+        }
+    }
+
+    fn vertex(&mut self, p: Coord) -> usize {
+        match self
+            .vertices
+            .iter()
+            .position(|vertex| length(sub(*vertex, p)) <= self.epsilon)
+        {
+            Some(id) => id,
+            None => {
+                self.vertices.push(p);
+                self.vertices.len() - 1
+            }
+        }
+    }
+
+    fn add_ring(&mut self, ring: &Ring) {
+        for (idx, p) in ring.iter().enumerate() {
+            let from = self.vertex(*p);
+            let to = self.vertex(ring[(idx + 1) % ring.len()]);
+            if from != to {
+                self.edges.push((from, to));
+            }
+        }
+    }
+
+    /// Adds a vertex where edges cross, then splits every edge at the vertices lying on it,
+    /// which also splits edges that overlap each other.
+    fn split(&mut self) {
+        let edges = self.edges.clone();
+        for (idx, (a, b)) in edges.iter().enumerate() {
+            for (c, d) in &edges[idx + 1..] {
+                let (a, b, c, d) = (
+                    self.vertices[*a],
+                    self.vertices[*b],
+                    self.vertices[*c],
+                    self.vertices[*d],
+                );
+                if let Some(p) = crossing(a, b, c, d) {
+                    self.vertex(p);
+                }
+            }
+        }
+
+        self.edges = Vec::new();
+        for (a, b) in edges {
+            let (start, end) = (self.vertices[a], self.vertices[b]);
+            let mut on_edge: Vec<(f64, usize)> = self
+                .vertices
+                .iter()
+                .enumerate()
+                .filter(|(id, _)| *id != a && *id != b)
+                .filter_map(|(id, p)| position_on(start, end, *p, self.epsilon).map(|t| (t, id)))
+                .collect();
+            on_edge.sort_by(|lhs, rhs| lhs.0.total_cmp(&rhs.0));
+
+            let mut from = a;
+            for to in on_edge.into_iter().map(|(_, id)| id).chain([b]) {
+                self.edges.push((from, to));
+                from = to;
+            }
+        }
+    }
+
+    /// The edges with `inside` on exactly one side, turned so that the inside is on their left.
+    /// Edges shared by both shapes are only looked at once.
+    fn boundary(&self, inside: impl Fn(Coord) -> bool) -> Vec<(usize, usize)> {
+        let mut seen = HashSet::new();
+        self.edges
+            .iter()
+            .filter(|(a, b)| seen.insert((*a.min(b), *a.max(b))))
+            .filter_map(|(a, b)| {
+                let (start, end) = (self.vertices[*a], self.vertices[*b]);
+                let edge_length = length(sub(end, start));
+                //Close enough that no other edge passes between the edge and the samples
+                let offset = (self.epsilon * 100.).min(edge_length / 4.) / edge_length;
+                let middle = ((start.0 + end.0) / 2., (start.1 + end.1) / 2.);
+                let normal = (-(end.1 - start.1) * offset, (end.0 - start.0) * offset);
+                let left = inside((middle.0 + normal.0, middle.1 + normal.1));
+                let right = inside((middle.0 - normal.0, middle.1 - normal.1));
+                match (left, right) {
+                    (true, false) => Some((*a, *b)),
+                    (false, true) => Some((*b, *a)),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Follows the boundary edges into closed rings, leaving out corners on a straight line
+    fn rings(&self, boundary: &[(usize, usize)]) -> Vec<Ring> {
+        let mut outgoing: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (idx, (from, _)) in boundary.iter().enumerate() {
+            outgoing.entry(*from).or_default().push(idx);
+        }
+
+        let mut used = vec![false; boundary.len()];
+        let mut rings = Vec::new();
+        for first in 0..boundary.len() {
+            if used[first] {
+                continue;
+            }
+            used[first] = true;
+            let (start, mut current) = boundary[first];
+            let mut ring = vec![self.vertices[start]];
+            while current != start {
+                ring.push(self.vertices[current]);
+                let next = outgoing
+                    .get(&current)
+                    .and_then(|edges| edges.iter().find(|idx| !used[**idx]));
+                match next {
+                    Some(idx) => {
+                        used[*idx] = true;
+                        current = boundary[*idx].1;
+                    }
+                    None => break,
+                }
+            }
+
+            let ring = self.simplify(ring);
+            if ring.len() > 2 {
+                rings.push(ring);
+            }
+        }
+        rings
+    }
+
+    fn simplify(&self, mut ring: Ring) -> Ring {
+        let is_straight = |ring: &Ring, idx: usize| {
+            let previous = ring[(idx + ring.len() - 1) % ring.len()];
+            let next = ring[(idx + 1) % ring.len()];
+            let (to_corner, from_corner) = (sub(ring[idx], previous), sub(next, ring[idx]));
+            cross(to_corner, from_corner).abs() <= self.epsilon * length(sub(next, previous))
+                && dot(to_corner, from_corner) > 0.
+        };
+        while let Some(idx) = (0..ring.len()).find(|idx| ring.len() > 2 && is_straight(&ring, *idx))
+        {
+            ring.remove(idx);
+        }
+        ring
+    }
+}
+
+/// Where two segments cross, `None` when they are parallel or do not meet
+fn crossing(a: Coord, b: Coord, c: Coord, d: Coord) -> Option<Coord> {
+    let (r, s) = (sub(b, a), sub(d, c));
+    let denominator = cross(r, s);
+    if denominator.abs() <= f64::EPSILON * length(r) * length(s) {
+        return None;
+    }
+    let t = cross(sub(c, a), s) / denominator;
+    let u = cross(sub(c, a), r) / denominator;
+    ((0. ..=1.).contains(&t) && (0. ..=1.).contains(&u)).then_some((a.0 + t * r.0, a.1 + t * r.1))
+}
+
+/// How far along the segment from `start` to `end` the point is, if it lies strictly between them
+fn position_on(start: Coord, end: Coord, p: Coord, epsilon: f64) -> Option<f64> {
+    let direction = sub(end, start);
+    let t = dot(sub(p, start), direction) / dot(direction, direction);
+    let closest = (start.0 + t * direction.0, start.1 + t * direction.1);
+    (t > 0. && t < 1. && length(sub(p, closest)) <= epsilon).then_some(t)
+}
+
+pub(super) fn coord(point: &Point) -> Coord {
+    (point.get_x_f64(), point.get_y_f64())
+}
+
+pub(super) fn sub(p: Coord, q: Coord) -> Coord {
+    (p.0 - q.0, p.1 - q.1)
+}
+
+pub(super) fn cross(p: Coord, q: Coord) -> f64 {
+    p.0 * q.1 - p.1 * q.0
+}
+
+fn dot(p: Coord, q: Coord) -> f64 {
+    p.0 * q.0 + p.1 * q.1
+}
+
+pub(super) fn length(p: Coord) -> f64 {
+    p.0.hypot(p.1)
+}
@@ -0,0 +1,101 @@
+use super::clipping::{
+    self, combine, covers, cross, fill_rings, flatten, length, shape_rings, sub, Coord,
+};
+use crate::interpreter::data_types::{figurearray::FigureArray, point::Point};
+use crate::program::operators::shapeoperator::ShapeOperator;
+
+/*************************  Measures ****************************/
+/// The area a shape fills, curves are flattened to within `tolerance`.
+/// Figures are measured separately, so figures that overlap count twice, `union` them first to avoid it.
+/// Holes are subtracted from the figure they are in and open paths are filled as if they were closed, like `combine` does.
+pub fn area(shape: &FigureArray, tolerance: f64) -> f64 {
+    let area: f64 = figure_moments(shape, tolerance)
+        .iter()
+        .map(|(area, _)| area)
+        .sum();
+    //An empty sum is -0
+    area + 0.
+}
+
+/// The length of every outline of the shape, unlike `area` open paths are only measured where they are drawn.
+pub fn perimeter(shape: &FigureArray, tolerance: f64) -> f64 {
+    shape
+        .get_figures()
+        .iter()
+        .flat_map(|figure| figure.get_contours())
+        .map(|contour| {
+            let ring = flatten(contour, tolerance);
+            let closed = match (
+                contour[0].get_first_point(),
+                contour[contour.len() - 1].get_last_point(),
+            ) {
+                (Ok(first), Ok(last)) => first.approx_eq(last, 1e-9),
+                _ => false,
+            };
+            let open_length: f64 = ring.windows(2).map(|w| length(sub(w[1], w[0]))).sum();
+            match (closed, ring.first(), ring.last()) {
+                (true, Some(first), Some(last)) => open_length + length(sub(*first, *last)),
+                _ => open_length,
+            }
+        })
+        .sum::<f64>()
+        + 0.
+}
+
+/// The center of mass of the area of the shape, weighted the same way as `area`.
+/// A shape without area, such as a single line, gives the center of its bounds instead.
+pub fn centroid(shape: &FigureArray, tolerance: f64) -> Point {
+    let (area, moment) = figure_moments(shape, tolerance).iter().fold(
+        (0., (0., 0.)),
+        |(total, moment), (area, figure_moment)| {
+            (
+                total + area,
+                (moment.0 + figure_moment.0, moment.1 + figure_moment.1),
+            )
+        },
+    );
+    if area < 1e-12 {
+        shape.get_center()
+    } else {
+        (moment.0 / area, moment.1 / area).into()
+    }
+}
+
+/// Area and first moment of every figure. The figure is first traced the way `contains` fills it,
+/// so a figure crossing itself is not cancelled out and holes run the other way and are subtracted.
+fn figure_moments(shape: &FigureArray, tolerance: f64) -> Vec<(f64, Coord)> {
+    shape_rings(shape, tolerance)
+        .iter()
+        .map(|rings| {
+            let mut area = 0.;
+            let mut moment = (0., 0.);
+            for ring in fill_rings(rings) {
+                for (idx, a) in ring.iter().enumerate() {
+                    let b = ring[(idx + 1) % ring.len()];
+                    let step = cross(*a, b);
+                    area += step / 2.;
+                    moment.0 += (a.0 + b.0) * step / 6.;
+                    moment.1 += (a.1 + b.1) * step / 6.;
+                }
+            }
+            (area, moment)
+        })
+        .collect()
+}
+
+/*************************  Queries ****************************/
+/// Whether the point is inside the area the shape fills, see `clipping::combine` for how figures are filled
+pub fn contains(shape: &FigureArray, point: &Point, tolerance: f64) -> bool {
+    covers(&shape_rings(shape, tolerance), clipping::coord(point))
+}
+
+/// Whether the areas of two shapes share more than an edge or a corner
+pub fn overlaps(a: &FigureArray, b: &FigureArray, tolerance: f64) -> bool {
+    !combine(a, b, ShapeOperator::Intersect, tolerance)
+        .get_figures()
+        .is_empty()
+}
+
+pub fn distance(p1: &Point, p2: &Point) -> f64 {
+    length(sub(clipping::coord(p1), clipping::coord(p2)))
+}
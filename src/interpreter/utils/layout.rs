@@ -0,0 +1,151 @@
+use std::error::Error;
+
+use super::manipulation::place_shape_at;
+use crate::interpreter::{data_types::figurearray::FigureArray, errors};
+
+/// How shapes line up across a row or a column, `Start` is the top of a row or the left of a column
+enum Align {
+    Start,
+    Center,
+    End,
+}
+
+impl Align {
+    fn new(align: &str, names: [&str; 3]) -> Result<Self, Box<dyn Error>> {
+        match names.iter().position(|name| *name == align) {
+            Some(0) => Ok(Self::Start),
+            Some(1) => Ok(Self::Center),
+            Some(_) => Ok(Self::End),
+            None => Err(errors::LayoutAlignInvalid(align.to_owned(), names.join(", ")).into()),
+        }
+    }
+
+    /// Offset of a shape of size `size` inside the space `space`
+    fn offset(&self, space: f64, size: f64) -> f64 {
+        match self {
+            Self::Start => 0.,
+            Self::Center => (space - size) / 2.,
+            Self::End => space - size,
+        }
+    }
+}
+
+/// Left, top, width and height of a shape
+fn bounds(s: &FigureArray) -> Result<(f64, f64, f64, f64), Box<dyn Error>> {
+    Ok((
+        s.min_x().get_float()?,
+        s.max_y().get_float()?,
+        s.width().get_float()?,
+        s.height().get_float()?,
+    ))
+}
+
+/// The shapes that have figures, an empty shape has no bounds to place or to line the others up with
+fn drawn(shapes: Vec<FigureArray>) -> Vec<FigureArray> {
+    shapes
+        .into_iter()
+        .filter(|s| !s.get_figures().is_empty())
+        .collect()
+}
+
+/// Places the shapes left to right with `gap` between them, starting where the first shape is.
+/// `align` is `top`, `center` or `bottom` and lines the shapes up with the first one.
+pub fn row(shapes: Vec<FigureArray>, gap: f64, align: &str) -> Result<FigureArray, Box<dyn Error>> {
+    let align = Align::new(align, ["top", "center", "bottom"])?;
+    let shapes = drawn(shapes);
+    let mut result = FigureArray::new();
+    let Some(first) = shapes.first() else {
+        return Ok(result);
+    };
+    let (mut x, top, _, height) = bounds(first)?;
+    for s in shapes {
+        let (_, _, width, size) = bounds(&s)?;
+        let y = top - align.offset(height, size);
+        result.extend(place_shape_at(s, (x, y).into()));
+        x += width + gap;
+    }
+    Ok(result)
+}
+
+/// Places the shapes top to bottom with `gap` between them, starting where the first shape is.
+/// `align` is `left`, `center` or `right` and lines the shapes up with the first one.
+pub fn column(
+    shapes: Vec<FigureArray>,
+    gap: f64,
+    align: &str,
+) -> Result<FigureArray, Box<dyn Error>> {
+    let align = Align::new(align, ["left", "center", "right"])?;
+    let shapes = drawn(shapes);
+    let mut result = FigureArray::new();
+    let Some(first) = shapes.first() else {
+        return Ok(result);
+    };
+    let (left, mut y, width, _) = bounds(first)?;
+    for s in shapes {
+        let (_, _, size, height) = bounds(&s)?;
+        let x = left + align.offset(width, size);
+        result.extend(place_shape_at(s, (x, y).into()));
+        y -= height + gap;
+    }
+    Ok(result)
+}
+
+/// Places the shapes in rows of `columns` cells, filling each row left to right.
+/// Every cell is as big as the largest shape and each shape is centered in its cell.
+pub fn grid(
+    shapes: Vec<FigureArray>,
+    columns: i64,
+    gap: f64,
+) -> Result<FigureArray, Box<dyn Error>> {
+    if columns < 1 {
+        return Err(errors::LayoutColumnsInvalid(columns).into());
+    }
+    let shapes = drawn(shapes);
+    let mut result = FigureArray::new();
+    let Some(first) = shapes.first() else {
+        return Ok(result);
+    };
+    let (left, top, _, _) = bounds(first)?;
+    let sizes = shapes
+        .iter()
+        .map(|s| Ok((s.width().get_float()?, s.height().get_float()?)))
+        .collect::<Result<Vec<(f64, f64)>, Box<dyn Error>>>()?;
+    let cell_width = sizes.iter().map(|size| size.0).fold(0., f64::max);
+    let cell_height = sizes.iter().map(|size| size.1).fold(0., f64::max);
+
+    for (idx, (s, (width, height))) in shapes.into_iter().zip(sizes).enumerate() {
+        let (cell_row, cell_column) =
+            ((idx as i64 / columns) as f64, (idx as i64 % columns) as f64);
+        let x = left + cell_column * (cell_width + gap) + (cell_width - width) / 2.;
+        let y = top - cell_row * (cell_height + gap) - (cell_height - height) / 2.;
+        result.extend(place_shape_at(s, (x, y).into()));
+    }
+    Ok(result)
+}
+
+/// Spreads the shapes over `width` from the left of the first shape, with equal gaps between them.
+/// The tops are lined up with the first shape like a `top` aligned `row`,
+/// the shapes overlap when they are wider than `width` together.
+pub fn distribute(shapes: Vec<FigureArray>, width: f64) -> Result<FigureArray, Box<dyn Error>> {
+    let shapes = drawn(shapes);
+    let mut result = FigureArray::new();
+    let Some(first) = shapes.first() else {
+        return Ok(result);
+    };
+    let (mut x, top, _, _) = bounds(first)?;
+    let total = shapes
+        .iter()
+        .map(|s| s.width().get_float())
+        .sum::<Result<f64, _>>()?;
+    let gap = if shapes.len() > 1 {
+        (width - total) / (shapes.len() - 1) as f64
+    } else {
+        0.
+    };
+    for s in shapes {
+        let size = s.width().get_float()?;
+        result.extend(place_shape_at(s, (x, top).into()));
+        x += size + gap;
+    }
+    Ok(result)
+}
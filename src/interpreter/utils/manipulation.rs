@@ -1,5 +1,7 @@
 use crate::interpreter::data_types::point::Point;
+use crate::interpreter::data_types::transform::Transform;
 use crate::interpreter::data_types::{direction::Direction, figurearray::FigureArray};
+use crate::interpreter::errors;
 use crate::interpreter::value::Value;
 use core::f64;
 use std::error::Error;
@@ -19,52 +21,101 @@ pub fn place(
     s2
 }
 
-pub fn place_shape_at(mut s: FigureArray, p: Point) -> FigureArray {
+pub fn place_shape_at(s: FigureArray, p: Point) -> FigureArray {
     let top_left = s.get_top_left();
+    map_points(s, |point| place_point_at(&top_left, point, &p))
+}
+
+pub fn place_point_at(point_top_left: &Point, point: &Point, offset: &Point) -> Point {
+    &(point - point_top_left) + offset
+}
+
+/// Replaces every point of every line in the shape, the walk shared by all manipulations
+pub fn map_points(mut s: FigureArray, mut f: impl FnMut(&Point) -> Point) -> FigureArray {
     s.get_mut_figures().iter_mut().for_each(|fig| {
         fig.get_mut_lines().iter_mut().for_each(|line| {
             line.get_mut_points()
                 .iter_mut()
-                .for_each(|point| *point = place_point_at(&top_left, point, &p));
+                .for_each(|point| *point = f(point));
         })
     });
     s
 }
 
-pub fn place_point_at(point_top_left: &Point, point: &Point, offset: &Point) -> Point {
-    &(point - point_top_left) + offset
+/*************************  Move ****************************/
+pub fn translate(s: FigureArray, offset: &Point) -> FigureArray {
+    map_points(s, |point| point + offset)
+}
+
+/*************************  Apply ****************************/
+/// Applies an affine transform to every point, curves stay exact since their control points move with them
+pub fn apply(s: FigureArray, transform: &Transform) -> FigureArray {
+    map_points(s, |point| transform.apply(point))
 }
 
 /*************************  Scale ****************************/
-pub fn scale(mut shape: FigureArray, factor: Value) -> Result<FigureArray, Box<dyn Error>> {
+/// Scales a shape around `anchor`, which defaults to its top left corner.
+/// A point factor scales x and y independently.
+pub fn scale(
+    shape: FigureArray,
+    factor: Value,
+    anchor: Option<Point>,
+) -> Result<FigureArray, Box<dyn Error>> {
+    let anchor = anchor.unwrap_or_else(|| shape.get_top_left());
+    Ok(map_points(shape, |point| scale_point(point, &anchor, &factor)))
+}
+
+pub fn scale_point(p: &Point, anchor: &Point, factor: &Value) -> Point {
+    let dist = p - anchor;
+    let scaled: Point = match factor {
+        Value::Point(factor) => (
+            dist.get_x() * factor.get_x(),
+            dist.get_y() * factor.get_y(),
+        )
+            .into(),
+        _ => dist * factor,
+    };
+    &scaled + anchor
+}
+
+/*************************  Mirror ****************************/
+/// Mirrors a shape in the line through `from` and `to`. Like `scale` the top left corner stays in place,
+/// so only the direction of the line matters.
+pub fn mirror(
+    shape: FigureArray,
+    from: &Point,
+    to: &Point,
+) -> Result<FigureArray, Box<dyn Error>> {
     let top_left = shape.get_top_left();
-    shape.get_mut_figures().iter_mut().for_each(|fig| {
-        fig.get_mut_lines().iter_mut().for_each(|line| {
-            line.get_mut_points().iter_mut().for_each(|point| {
-                *point = scale_point(point, &top_left, &factor);
-            });
-        });
-    });
-    Ok(shape)
+    let direction = (
+        to.get_x_f64() - from.get_x_f64(),
+        to.get_y_f64() - from.get_y_f64(),
+    );
+    if direction == (0., 0.) {
+        return Err(errors::MirrorLineNotALine.into());
+    }
+    let shape = map_points(shape, |point| mirror_point(point, from, direction));
+    Ok(place_shape_at(shape, top_left))
 }
 
-pub fn scale_point(p: &Point, top_left: &Point, factor: &Value) -> Point {
-    &((p - top_left) * factor) + top_left
+pub fn mirror_point(p: &Point, from: &Point, direction: (f64, f64)) -> Point {
+    let (x, y) = (p.get_x_f64() - from.get_x_f64(), p.get_y_f64() - from.get_y_f64());
+    //Position along the line of the point closest to p
+    let t = (x * direction.0 + y * direction.1) / (direction.0.powi(2) + direction.1.powi(2));
+    (
+        from.get_x_f64() + 2. * t * direction.0 - x,
+        from.get_y_f64() + 2. * t * direction.1 - y,
+    )
+        .into()
 }
 
 /*************************  Rotate ****************************/
-pub fn rotate(mut s: FigureArray, rotate_by: Value) -> FigureArray {
-    let rotate_around = s.get_center();
+/// Rotates a shape around `anchor`, which defaults to its center.
+pub fn rotate(s: FigureArray, rotate_by: Value, anchor: Option<Point>) -> FigureArray {
+    let rotate_around = anchor.unwrap_or_else(|| s.get_center());
     //Convert degrees into radians
     let theta = &rotate_by * &(f64::consts::PI / 180.).into();
-    s.get_mut_figures().iter_mut().for_each(|fig| {
-        fig.get_mut_lines().iter_mut().for_each(|line| {
-            line.get_mut_points()
-                .iter_mut()
-                .for_each(|point| *point = rotate_point(point, &rotate_around, &theta));
-        })
-    });
-    s
+    map_points(s, |point| rotate_point(point, &rotate_around, &theta))
 }
 
 pub fn rotate_point(p: &Point, rotate_around: &Point, theta: &Value) -> Point {
@@ -1 +1,4 @@
-pub mod manipulation;
\ No newline at end of file
+pub mod clipping;
+pub mod manipulation;
+pub mod layout;
+pub mod geometry;
@@ -1,7 +1,12 @@
-use super::data_types::{figure::Figure, figurearray::FigureArray, point::Point};
+use super::{
+    data_types::{figure::Figure, figurearray::FigureArray, point::Point, transform::Transform},
+    errors,
+};
+use crate::program::operators::binaryoperator::BinaryOperator;
 use std::{
     cmp::Ordering,
     error::Error,
+    fmt,
     ops::{Add, Div, Mul, Neg, Not, Sub},
 };
 
@@ -11,11 +16,20 @@ pub enum Value {
     Variable(String),
     Boolean(bool),
     Float(f64),
+    String(String),
     Point(Point),
     Color(Box<Value>, Box<Value>, Box<Value>, Box<Value>),
     Shape(FigureArray),
     Figure(Figure),
     Array(Vec<Value>),
+    Transform(Transform),
+}
+
+/// Two numbers converted to a common type by `Value::promote`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Numbers {
+    Ints(i64, i64),
+    Floats(f64, f64),
 }
 
 impl Value {
@@ -34,10 +48,17 @@ impl Value {
         }
     }
 
+    pub fn get_string(&self) -> Result<&str, Box<dyn Error>> {
+        match self {
+            Value::String(s) => Ok(s),
+            _ => Err(crate::program::errors::ExprParseAsStringError.into()),
+        }
+    }
+
     pub fn get_array(&self) -> Result<Vec<Value>, Box<dyn Error>> {
         match self {
             Value::Array(i) => Ok(i.clone()),
-            _ => Err(crate::program::errors::ExprParseAsFloatError.into()),
+            _ => Err(crate::program::errors::ExprParseAsArrayError.into()),
         }
     }
 
@@ -62,6 +83,78 @@ impl Value {
         }
     }
 
+    pub fn get_transform(&self) -> Result<Transform, Box<dyn Error>> {
+        match self {
+            Value::Transform(t) => Ok(*t),
+            _ => Err(crate::program::errors::ExprParseAsTransformError.into()),
+        }
+    }
+
+    /// Converts two numbers to a common type, following the rule of `Type::promote`:
+    /// two ints stay ints, otherwise both become floats.
+    pub fn promote(&self, other: &Value) -> Result<Numbers, Box<dyn Error>> {
+        match (self, other) {
+            (Value::Integer(v1), Value::Integer(v2)) => Ok(Numbers::Ints(*v1, *v2)),
+            _ => Ok(Numbers::Floats(self.get_float()?, other.get_float()?)),
+        }
+    }
+
+    /// Applies an arithmetic operator to two promoted numbers.
+    /// Dividing by zero, int overflow and floats that are or become NaN or infinite are errors.
+    pub fn arithmetic(
+        &self,
+        operator: BinaryOperator,
+        other: &Value,
+    ) -> Result<Value, Box<dyn Error>> {
+        let divides = matches!(operator, BinaryOperator::Divide | BinaryOperator::Modulus);
+        match self.promote(other)? {
+            Numbers::Ints(_, 0) if divides => Err(errors::DivideByZero.into()),
+            Numbers::Floats(_, v2) if divides && v2 == 0.0 => Err(errors::DivideByZero.into()),
+            Numbers::Ints(v1, v2) => {
+                let int = match operator {
+                    BinaryOperator::Add => v1.checked_add(v2),
+                    BinaryOperator::Subtract => v1.checked_sub(v2),
+                    BinaryOperator::Multiply => v1.checked_mul(v2),
+                    BinaryOperator::Divide => v1.checked_div(v2),
+                    BinaryOperator::Modulus => v1.checked_rem(v2),
+                    _ => unreachable!(),
+                };
+                int.map(Value::Integer).ok_or_else(|| {
+                    errors::MathError(format!("{} {} {} overflowed", v1, operator, v2)).into()
+                })
+            }
+            Numbers::Floats(v1, v2) => {
+                let float = match operator {
+                    BinaryOperator::Add => v1 + v2,
+                    BinaryOperator::Subtract => v1 - v2,
+                    BinaryOperator::Multiply => v1 * v2,
+                    BinaryOperator::Divide => v1 / v2,
+                    BinaryOperator::Modulus => v1 % v2,
+                    _ => unreachable!(),
+                };
+                if v1.is_finite() && v2.is_finite() && float.is_finite() {
+                    Ok(Value::Float(float))
+                } else {
+                    let operation = format!("{:?} {} {:?} gave {:?}", v1, operator, v2, float);
+                    Err(errors::MathError(operation).into())
+                }
+            }
+        }
+    }
+
+    /// Orders two promoted numbers, NaN and infinity can't be compared.
+    pub fn compare(&self, other: &Value) -> Result<Ordering, Box<dyn Error>> {
+        match self.promote(other)? {
+            Numbers::Ints(v1, v2) => Ok(v1.cmp(&v2)),
+            Numbers::Floats(v1, v2) => v1
+                .partial_cmp(&v2)
+                .filter(|_| v1.is_finite() && v2.is_finite())
+                .ok_or_else(|| {
+                    errors::MathError(format!("can't compare {:?} and {:?}", v1, v2)).into()
+                }),
+        }
+    }
+
     pub fn approx_eq(&self, other: &Value, epsilon: f64) -> bool {
         let (i1, i2) = match (self, other) {
             (Value::Integer(v1), Value::Integer(v2)) => (*v1 as f64, *v2 as f64),
@@ -74,6 +167,44 @@ impl Value {
     }
 }
 
+/// Human readable form of a value, shapes are summarised by their bounds.
+/// Numbers are written the way they are in DAP source code, so a whole float keeps its decimal point.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Integer(i) => write!(f, "{}", i),
+            Value::Variable(name) => write!(f, "{}", name),
+            Value::Boolean(b) => write!(f, "{}", b),
+            Value::Float(x) if x.is_finite() && x.fract() == 0. => write!(f, "{:.1}", x),
+            Value::Float(x) => write!(f, "{}", x),
+            Value::String(s) => write!(f, "{:?}", s),
+            Value::Point(point) => write!(f, "({}, {})", point.get_x(), point.get_y()),
+            Value::Color(r, g, b, a) => write!(f, "({}, {}, {}, {})", r, g, b, a),
+            Value::Shape(figures) => {
+                let top_left = figures.get_top_left();
+                write!(
+                    f,
+                    "shape of {} figure(s), width: {}, height: {}, top left: ({}, {})",
+                    figures.get_figures().len(),
+                    figures.width(),
+                    figures.height(),
+                    top_left.get_x(),
+                    top_left.get_y()
+                )
+            }
+            Value::Figure(figure) => {
+                let kind = if figure.is_closed().unwrap_or(false) { "polygon" } else { "path" };
+                write!(f, "{} of {} line(s)", kind, figure.get_lines().len())
+            }
+            Value::Array(values) => {
+                let values: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+                write!(f, "[{}]", values.join(", "))
+            }
+            Value::Transform(transform) => write!(f, "{}", transform),
+        }
+    }
+}
+
 impl From<i64> for Value {
     fn from(value: i64) -> Self {
         Self::Integer(value)
@@ -93,6 +224,7 @@ impl Add for Value {
             (Value::Float(v1), Value::Float(v2)) => Value::Float(v1 + v2),
             (Value::Float(v1), Value::Integer(v2)) => Value::Float(v1 + v2 as f64),
             (Value::Integer(v1), Value::Float(v2)) => Value::Float(v1 as f64 + v2),
+            (Value::String(v1), Value::String(v2)) => Value::String(v1 + &v2),
             _ => unreachable!(),
         }
     }
@@ -119,6 +251,7 @@ impl Add for &Value {
             (Value::Float(v1), Value::Float(v2)) => Value::Float(v1 + v2),
             (Value::Float(v1), Value::Integer(v2)) => Value::Float(v1 + *v2 as f64),
             (Value::Integer(v1), Value::Float(v2)) => Value::Float(*v1 as f64 + v2),
+            (Value::String(v1), Value::String(v2)) => Value::String(format!("{}{}", v1, v2)),
             _ => unreachable!(),
         }
     }
@@ -195,18 +328,24 @@ impl Not for Value {
     }
 }
 
+/// Total order on numbers, used to find extremes such as the bounds of a figure.
+/// NaN sorts above infinity instead of panicking, DAP comparisons use `Value::compare`.
 impl Ord for Value {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         match (self, other) {
             (Value::Integer(v1), Value::Integer(v2)) => v1.cmp(v2),
-            (Value::Float(v1), Value::Float(v2)) => v1.partial_cmp(v2).unwrap(),
-            (Value::Float(v1), Value::Integer(v2)) => v1.partial_cmp(&(*v2 as f64)).unwrap(),
-            (Value::Integer(v1), Value::Float(v2)) => (&(*v1 as f64)).partial_cmp(v2).unwrap(),
+            (Value::Float(v1), Value::Float(v2)) => float_cmp(*v1, *v2),
+            (Value::Float(v1), Value::Integer(v2)) => float_cmp(*v1, *v2 as f64),
+            (Value::Integer(v1), Value::Float(v2)) => float_cmp(*v1 as f64, *v2),
             _ => unreachable!(),
         }
     }
 }
 
+fn float_cmp(v1: f64, v2: f64) -> Ordering {
+    v1.partial_cmp(&v2).unwrap_or_else(|| v1.total_cmp(&v2))
+}
+
 impl PartialOrd for Value {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -73,280 +73,376 @@ pub const TERMINALS: &[Symbol] = &[
         name: "BOOLEAN"
     },
     Symbol {
-        id: 0x003C,
+        id: 0x0045,
         name: "begin"
     },
     Symbol {
-        id: 0x003E,
+        id: 0x0047,
         name: "import"
     },
     Symbol {
-        id: 0x003F,
+        id: 0x0048,
         name: ";"
     },
     Symbol {
-        id: 0x0040,
+        id: 0x0049,
         name: ":"
     },
     Symbol {
-        id: 0x0041,
+        id: 0x004A,
         name: "="
     },
     Symbol {
-        id: 0x0043,
+        id: 0x004C,
         name: "fork"
     },
     Symbol {
-        id: 0x0044,
+        id: 0x004D,
         name: "{"
     },
     Symbol {
-        id: 0x0045,
+        id: 0x004E,
         name: "}"
     },
     Symbol {
-        id: 0x0047,
+        id: 0x0050,
         name: "("
     },
     Symbol {
-        id: 0x0048,
+        id: 0x0051,
         name: ")"
     },
     Symbol {
-        id: 0x0049,
+        id: 0x0052,
         name: "->"
     },
     Symbol {
-        id: 0x004A,
+        id: 0x0053,
         name: "otherwise"
     },
     Symbol {
-        id: 0x004B,
+        id: 0x0054,
         name: "for"
     },
     Symbol {
-        id: 0x004C,
+        id: 0x0055,
         name: "in"
     },
     Symbol {
-        id: 0x004D,
+        id: 0x0056,
         name: "to"
     },
     Symbol {
-        id: 0x004E,
+        id: 0x0057,
+        name: "while"
+    },
+    Symbol {
+        id: 0x0058,
+        name: "break"
+    },
+    Symbol {
+        id: 0x0059,
+        name: "continue"
+    },
+    Symbol {
+        id: 0x005A,
         name: "draw"
     },
     Symbol {
-        id: 0x004F,
+        id: 0x005B,
         name: "at"
     },
     Symbol {
-        id: 0x0050,
+        id: 0x005C,
         name: "return"
     },
     Symbol {
-        id: 0x0051,
+        id: 0x005D,
         name: ","
     },
     Symbol {
-        id: 0x0053,
+        id: 0x005F,
         name: "["
     },
     Symbol {
-        id: 0x0054,
+        id: 0x0060,
         name: "]"
     },
     Symbol {
-        id: 0x0056,
+        id: 0x0062,
         name: "--"
     },
     Symbol {
-        id: 0x0057,
+        id: 0x0063,
         name: "~~"
     },
     Symbol {
-        id: 0x0059,
+        id: 0x0065,
+        name: "arc"
+    },
+    Symbol {
+        id: 0x0066,
         name: "--*"
     },
     Symbol {
-        id: 0x005A,
+        id: 0x0067,
         name: "~~*"
     },
     Symbol {
-        id: 0x005B,
+        id: 0x0068,
         name: "scale"
     },
     Symbol {
-        id: 0x005C,
+        id: 0x0069,
         name: "by"
     },
     Symbol {
-        id: 0x005D,
+        id: 0x006A,
+        name: "around"
+    },
+    Symbol {
+        id: 0x006B,
+        name: "center"
+    },
+    Symbol {
+        id: 0x006C,
+        name: "topleft"
+    },
+    Symbol {
+        id: 0x006D,
+        name: "topright"
+    },
+    Symbol {
+        id: 0x006E,
+        name: "bottomleft"
+    },
+    Symbol {
+        id: 0x006F,
+        name: "bottomright"
+    },
+    Symbol {
+        id: 0x0070,
         name: "rotate"
     },
     Symbol {
-        id: 0x005E,
+        id: 0x0071,
         name: "place"
     },
     Symbol {
-        id: 0x005F,
+        id: 0x0072,
         name: "offset"
     },
     Symbol {
-        id: 0x0060,
+        id: 0x0073,
         name: "left"
     },
     Symbol {
-        id: 0x0061,
+        id: 0x0074,
         name: "right"
     },
     Symbol {
-        id: 0x0062,
+        id: 0x0075,
         name: "top"
     },
     Symbol {
-        id: 0x0063,
+        id: 0x0076,
         name: "bottom"
     },
     Symbol {
-        id: 0x0064,
+        id: 0x0077,
         name: "ontop"
     },
     Symbol {
-        id: 0x0065,
-        name: "center"
+        id: 0x0078,
+        name: "union"
     },
     Symbol {
-        id: 0x0066,
+        id: 0x0079,
+        name: "intersect"
+    },
+    Symbol {
+        id: 0x007A,
+        name: "xor"
+    },
+    Symbol {
+        id: 0x007B,
+        name: "with"
+    },
+    Symbol {
+        id: 0x007C,
+        name: "subtract"
+    },
+    Symbol {
+        id: 0x007D,
+        name: "from"
+    },
+    Symbol {
+        id: 0x007E,
+        name: "mirror"
+    },
+    Symbol {
+        id: 0x007F,
+        name: "horizontally"
+    },
+    Symbol {
+        id: 0x0080,
+        name: "vertically"
+    },
+    Symbol {
+        id: 0x0081,
+        name: "across"
+    },
+    Symbol {
+        id: 0x0082,
+        name: "move"
+    },
+    Symbol {
+        id: 0x0083,
+        name: "apply"
+    },
+    Symbol {
+        id: 0x0084,
         name: "(|"
     },
     Symbol {
-        id: 0x0067,
+        id: 0x0085,
         name: "|)"
     },
     Symbol {
-        id: 0x0069,
+        id: 0x0087,
         name: "||"
     },
     Symbol {
-        id: 0x006A,
+        id: 0x0088,
         name: "&&"
     },
     Symbol {
-        id: 0x006B,
+        id: 0x0089,
         name: "=="
     },
     Symbol {
-        id: 0x006C,
+        id: 0x008A,
         name: "<"
     },
     Symbol {
-        id: 0x006D,
+        id: 0x008B,
         name: ">"
     },
     Symbol {
-        id: 0x006E,
+        id: 0x008C,
         name: ">="
     },
     Symbol {
-        id: 0x006F,
+        id: 0x008D,
         name: "<="
     },
     Symbol {
-        id: 0x0070,
+        id: 0x008E,
         name: "!="
     },
     Symbol {
-        id: 0x0071,
+        id: 0x008F,
         name: "+"
     },
     Symbol {
-        id: 0x0072,
+        id: 0x0090,
         name: "-"
     },
     Symbol {
-        id: 0x0073,
+        id: 0x0091,
         name: "%"
     },
     Symbol {
-        id: 0x0074,
+        id: 0x0092,
         name: "*"
     },
     Symbol {
-        id: 0x0075,
+        id: 0x0093,
         name: "/"
     },
     Symbol {
-        id: 0x0076,
+        id: 0x0094,
         name: "!"
     },
     Symbol {
-        id: 0x0078,
-        name: "."
-    },
-    Symbol {
-        id: 0x0079,
+        id: 0x0095,
         name: "int"
     },
     Symbol {
-        id: 0x007A,
+        id: 0x0096,
         name: "float"
     },
     Symbol {
-        id: 0x007B,
+        id: 0x0098,
+        name: "."
+    },
+    Symbol {
+        id: 0x0099,
         name: "point"
     },
     Symbol {
-        id: 0x007C,
+        id: 0x009A,
         name: "color"
     },
     Symbol {
-        id: 0x007D,
+        id: 0x009B,
         name: "shape"
     },
     Symbol {
-        id: 0x007E,
+        id: 0x009C,
         name: "path"
     },
     Symbol {
-        id: 0x007F,
+        id: 0x009D,
         name: "polygon"
     },
     Symbol {
-        id: 0x0080,
+        id: 0x009E,
         name: "bool"
     },
     Symbol {
-        id: 0x0081,
+        id: 0x009F,
         name: "int[]"
     },
     Symbol {
-        id: 0x0082,
+        id: 0x00A0,
         name: "float[]"
     },
     Symbol {
-        id: 0x0083,
+        id: 0x00A1,
         name: "point[]"
     },
     Symbol {
-        id: 0x0084,
+        id: 0x00A2,
         name: "color[]"
     },
     Symbol {
-        id: 0x0085,
+        id: 0x00A3,
         name: "shape[]"
     },
     Symbol {
-        id: 0x0086,
+        id: 0x00A4,
         name: "path[]"
     },
     Symbol {
-        id: 0x0087,
+        id: 0x00A5,
         name: "polygon[]"
     },
     Symbol {
-        id: 0x0088,
+        id: 0x00A6,
         name: "bool[]"
+    },
+    Symbol {
+        id: 0x00A7,
+        name: "string"
+    },
+    Symbol {
+        id: 0x00A8,
+        name: "string[]"
+    },
+    Symbol {
+        id: 0x00A9,
+        name: "transform"
     }
 ];
 
@@ -382,78 +478,96 @@ pub const ID_VARIABLE_FORK_CASE: u32 = 0x0015;
 pub const ID_VARIABLE_OTHERWISE: u32 = 0x0016;
 /// The unique identifier for variable `For`
 pub const ID_VARIABLE_FOR: u32 = 0x0017;
+/// The unique identifier for variable `While`
+pub const ID_VARIABLE_WHILE: u32 = 0x0018;
+/// The unique identifier for variable `Break`
+pub const ID_VARIABLE_BREAK: u32 = 0x0019;
+/// The unique identifier for variable `Continue`
+pub const ID_VARIABLE_CONTINUE: u32 = 0x001A;
 /// The unique identifier for variable `Draw`
-pub const ID_VARIABLE_DRAW: u32 = 0x0018;
+pub const ID_VARIABLE_DRAW: u32 = 0x001B;
 /// The unique identifier for variable `Return`
-pub const ID_VARIABLE_RETURN: u32 = 0x0019;
+pub const ID_VARIABLE_RETURN: u32 = 0x001C;
 /// The unique identifier for variable `FuncDecl`
-pub const ID_VARIABLE_FUNC_DECL: u32 = 0x001A;
+pub const ID_VARIABLE_FUNC_DECL: u32 = 0x001D;
 /// The unique identifier for variable `Params`
-pub const ID_VARIABLE_PARAMS: u32 = 0x001B;
+pub const ID_VARIABLE_PARAMS: u32 = 0x001E;
 /// The unique identifier for variable `Param`
-pub const ID_VARIABLE_PARAM: u32 = 0x001C;
+pub const ID_VARIABLE_PARAM: u32 = 0x001F;
 /// The unique identifier for variable `Assign`
-pub const ID_VARIABLE_ASSIGN: u32 = 0x001D;
+pub const ID_VARIABLE_ASSIGN: u32 = 0x0020;
 /// The unique identifier for variable `VarDecl`
-pub const ID_VARIABLE_VAR_DECL: u32 = 0x001E;
+pub const ID_VARIABLE_VAR_DECL: u32 = 0x0021;
 /// The unique identifier for variable `ArrayAssign`
-pub const ID_VARIABLE_ARRAY_ASSIGN: u32 = 0x001F;
+pub const ID_VARIABLE_ARRAY_ASSIGN: u32 = 0x0022;
 /// The unique identifier for variable `ExprT`
-pub const ID_VARIABLE_EXPR_T: u32 = 0x0020;
+pub const ID_VARIABLE_EXPR_T: u32 = 0x0023;
 /// The unique identifier for variable `Array`
-pub const ID_VARIABLE_ARRAY: u32 = 0x0021;
+pub const ID_VARIABLE_ARRAY: u32 = 0x0024;
 /// The unique identifier for variable `Point`
-pub const ID_VARIABLE_POINT: u32 = 0x0022;
+pub const ID_VARIABLE_POINT: u32 = 0x0025;
 /// The unique identifier for variable `Color`
-pub const ID_VARIABLE_COLOR: u32 = 0x0023;
+pub const ID_VARIABLE_COLOR: u32 = 0x0026;
 /// The unique identifier for variable `Path`
-pub const ID_VARIABLE_PATH: u32 = 0x0024;
+pub const ID_VARIABLE_PATH: u32 = 0x0027;
+/// The unique identifier for variable `Arc`
+pub const ID_VARIABLE_ARC: u32 = 0x0028;
 /// The unique identifier for variable `Polygon`
-pub const ID_VARIABLE_POLYGON: u32 = 0x0025;
+pub const ID_VARIABLE_POLYGON: u32 = 0x0029;
 /// The unique identifier for variable `ExprS`
-pub const ID_VARIABLE_EXPR_S: u32 = 0x0026;
+pub const ID_VARIABLE_EXPR_S: u32 = 0x002A;
 /// The unique identifier for variable `Manipulation`
-pub const ID_VARIABLE_MANIPULATION: u32 = 0x0027;
+pub const ID_VARIABLE_MANIPULATION: u32 = 0x002B;
 /// The unique identifier for variable `Scale`
-pub const ID_VARIABLE_SCALE: u32 = 0x0028;
+pub const ID_VARIABLE_SCALE: u32 = 0x002C;
+/// The unique identifier for variable `Anchor`
+pub const ID_VARIABLE_ANCHOR: u32 = 0x002D;
 /// The unique identifier for variable `Rotate`
-pub const ID_VARIABLE_ROTATE: u32 = 0x0029;
+pub const ID_VARIABLE_ROTATE: u32 = 0x002E;
 /// The unique identifier for variable `Place`
-pub const ID_VARIABLE_PLACE: u32 = 0x002A;
+pub const ID_VARIABLE_PLACE: u32 = 0x002F;
 /// The unique identifier for variable `Pos`
-pub const ID_VARIABLE_POS: u32 = 0x002B;
+pub const ID_VARIABLE_POS: u32 = 0x0030;
+/// The unique identifier for variable `Combine`
+pub const ID_VARIABLE_COMBINE: u32 = 0x0031;
+/// The unique identifier for variable `Mirror`
+pub const ID_VARIABLE_MIRROR: u32 = 0x0032;
+/// The unique identifier for variable `Move`
+pub const ID_VARIABLE_MOVE: u32 = 0x0033;
+/// The unique identifier for variable `Apply`
+pub const ID_VARIABLE_APPLY: u32 = 0x0034;
 /// The unique identifier for variable `SCall`
-pub const ID_VARIABLE_SCALL: u32 = 0x002C;
+pub const ID_VARIABLE_SCALL: u32 = 0x0035;
 /// The unique identifier for variable `AttrS`
-pub const ID_VARIABLE_ATTR_S: u32 = 0x002D;
+pub const ID_VARIABLE_ATTR_S: u32 = 0x0036;
 /// The unique identifier for variable `Attr`
-pub const ID_VARIABLE_ATTR: u32 = 0x002E;
+pub const ID_VARIABLE_ATTR: u32 = 0x0037;
 /// The unique identifier for variable `ExprV`
-pub const ID_VARIABLE_EXPR_V: u32 = 0x002F;
+pub const ID_VARIABLE_EXPR_V: u32 = 0x0038;
 /// The unique identifier for variable `ExprV1`
-pub const ID_VARIABLE_EXPR_V1: u32 = 0x0030;
+pub const ID_VARIABLE_EXPR_V1: u32 = 0x0039;
 /// The unique identifier for variable `ExprV2`
-pub const ID_VARIABLE_EXPR_V2: u32 = 0x0031;
+pub const ID_VARIABLE_EXPR_V2: u32 = 0x003A;
 /// The unique identifier for variable `ExprV3`
-pub const ID_VARIABLE_EXPR_V3: u32 = 0x0032;
+pub const ID_VARIABLE_EXPR_V3: u32 = 0x003B;
 /// The unique identifier for variable `ExprV4`
-pub const ID_VARIABLE_EXPR_V4: u32 = 0x0033;
+pub const ID_VARIABLE_EXPR_V4: u32 = 0x003C;
 /// The unique identifier for variable `ExprV5`
-pub const ID_VARIABLE_EXPR_V5: u32 = 0x0034;
+pub const ID_VARIABLE_EXPR_V5: u32 = 0x003D;
 /// The unique identifier for variable `ExprV6`
-pub const ID_VARIABLE_EXPR_V6: u32 = 0x0035;
+pub const ID_VARIABLE_EXPR_V6: u32 = 0x003E;
 /// The unique identifier for variable `ExprV7`
-pub const ID_VARIABLE_EXPR_V7: u32 = 0x0036;
+pub const ID_VARIABLE_EXPR_V7: u32 = 0x003F;
 /// The unique identifier for variable `FCall`
-pub const ID_VARIABLE_FCALL: u32 = 0x0037;
+pub const ID_VARIABLE_FCALL: u32 = 0x0040;
 /// The unique identifier for variable `Args`
-pub const ID_VARIABLE_ARGS: u32 = 0x0038;
+pub const ID_VARIABLE_ARGS: u32 = 0x0041;
 /// The unique identifier for variable `ArrayIdx`
-pub const ID_VARIABLE_ARRAY_IDX: u32 = 0x0039;
+pub const ID_VARIABLE_ARRAY_IDX: u32 = 0x0042;
 /// The unique identifier for variable `Member`
-pub const ID_VARIABLE_MEMBER: u32 = 0x003A;
+pub const ID_VARIABLE_MEMBER: u32 = 0x0043;
 /// The unique identifier for variable `Types`
-pub const ID_VARIABLE_TYPES: u32 = 0x003B;
+pub const ID_VARIABLE_TYPES: u32 = 0x0044;
 
 
 /// The collection of variables matched by this parser
@@ -502,182 +616,218 @@ pub const VARIABLES: &[Symbol] = &[
     },
     Symbol {
         id: 0x0018,
-        name: "Draw"
+        name: "While"
     },
     Symbol {
         id: 0x0019,
-        name: "Return"
+        name: "Break"
     },
     Symbol {
         id: 0x001A,
-        name: "FuncDecl"
+        name: "Continue"
     },
     Symbol {
         id: 0x001B,
-        name: "Params"
+        name: "Draw"
     },
     Symbol {
         id: 0x001C,
-        name: "Param"
+        name: "Return"
     },
     Symbol {
         id: 0x001D,
-        name: "Assign"
+        name: "FuncDecl"
     },
     Symbol {
         id: 0x001E,
-        name: "VarDecl"
+        name: "Params"
     },
     Symbol {
         id: 0x001F,
-        name: "ArrayAssign"
+        name: "Param"
     },
     Symbol {
         id: 0x0020,
-        name: "ExprT"
+        name: "Assign"
     },
     Symbol {
         id: 0x0021,
-        name: "Array"
+        name: "VarDecl"
     },
     Symbol {
         id: 0x0022,
-        name: "Point"
+        name: "ArrayAssign"
     },
     Symbol {
         id: 0x0023,
-        name: "Color"
+        name: "ExprT"
     },
     Symbol {
         id: 0x0024,
-        name: "Path"
+        name: "Array"
     },
     Symbol {
         id: 0x0025,
-        name: "Polygon"
+        name: "Point"
     },
     Symbol {
         id: 0x0026,
-        name: "ExprS"
+        name: "Color"
     },
     Symbol {
         id: 0x0027,
-        name: "Manipulation"
+        name: "Path"
     },
     Symbol {
         id: 0x0028,
-        name: "Scale"
+        name: "Arc"
     },
     Symbol {
         id: 0x0029,
-        name: "Rotate"
+        name: "Polygon"
     },
     Symbol {
         id: 0x002A,
-        name: "Place"
+        name: "ExprS"
     },
     Symbol {
         id: 0x002B,
-        name: "Pos"
+        name: "Manipulation"
     },
     Symbol {
         id: 0x002C,
-        name: "SCall"
+        name: "Scale"
     },
     Symbol {
         id: 0x002D,
-        name: "AttrS"
+        name: "Anchor"
     },
     Symbol {
         id: 0x002E,
-        name: "Attr"
+        name: "Rotate"
     },
     Symbol {
         id: 0x002F,
-        name: "ExprV"
+        name: "Place"
     },
     Symbol {
         id: 0x0030,
-        name: "ExprV1"
+        name: "Pos"
     },
     Symbol {
         id: 0x0031,
-        name: "ExprV2"
+        name: "Combine"
     },
     Symbol {
         id: 0x0032,
-        name: "ExprV3"
+        name: "Mirror"
     },
     Symbol {
         id: 0x0033,
-        name: "ExprV4"
+        name: "Move"
     },
     Symbol {
         id: 0x0034,
-        name: "ExprV5"
+        name: "Apply"
     },
     Symbol {
         id: 0x0035,
-        name: "ExprV6"
+        name: "SCall"
     },
     Symbol {
         id: 0x0036,
-        name: "ExprV7"
+        name: "AttrS"
     },
     Symbol {
         id: 0x0037,
-        name: "FCall"
+        name: "Attr"
     },
     Symbol {
         id: 0x0038,
-        name: "Args"
+        name: "ExprV"
     },
     Symbol {
         id: 0x0039,
-        name: "ArrayIdx"
+        name: "ExprV1"
     },
     Symbol {
         id: 0x003A,
-        name: "Member"
+        name: "ExprV2"
     },
     Symbol {
         id: 0x003B,
-        name: "Types"
+        name: "ExprV3"
+    },
+    Symbol {
+        id: 0x003C,
+        name: "ExprV4"
     },
     Symbol {
         id: 0x003D,
-        name: "__V61"
+        name: "ExprV5"
+    },
+    Symbol {
+        id: 0x003E,
+        name: "ExprV6"
+    },
+    Symbol {
+        id: 0x003F,
+        name: "ExprV7"
+    },
+    Symbol {
+        id: 0x0040,
+        name: "FCall"
+    },
+    Symbol {
+        id: 0x0041,
+        name: "Args"
     },
     Symbol {
         id: 0x0042,
-        name: "__V66"
+        name: "ArrayIdx"
+    },
+    Symbol {
+        id: 0x0043,
+        name: "Member"
+    },
+    Symbol {
+        id: 0x0044,
+        name: "Types"
     },
     Symbol {
         id: 0x0046,
         name: "__V70"
     },
     Symbol {
-        id: 0x0052,
-        name: "__V82"
+        id: 0x004B,
+        name: "__V75"
     },
     Symbol {
-        id: 0x0055,
-        name: "__V85"
+        id: 0x004F,
+        name: "__V79"
     },
     Symbol {
-        id: 0x0058,
-        name: "__V88"
+        id: 0x005E,
+        name: "__V94"
     },
     Symbol {
-        id: 0x0068,
-        name: "__V104"
+        id: 0x0061,
+        name: "__V97"
     },
     Symbol {
-        id: 0x0077,
-        name: "__V119"
+        id: 0x0064,
+        name: "__V100"
     },
     Symbol {
-        id: 0x0089,
+        id: 0x0086,
+        name: "__V134"
+    },
+    Symbol {
+        id: 0x0097,
+        name: "__V151"
+    },
+    Symbol {
+        id: 0x00AA,
         name: "__VAxiom"
     }
 ];
@@ -756,6 +906,9 @@ pub trait Visitor {
     fn on_variable_fork_case(&self, node: &AstNode) {}
     fn on_variable_otherwise(&self, node: &AstNode) {}
     fn on_variable_for(&self, node: &AstNode) {}
+    fn on_variable_while(&self, node: &AstNode) {}
+    fn on_variable_break(&self, node: &AstNode) {}
+    fn on_variable_continue(&self, node: &AstNode) {}
     fn on_variable_draw(&self, node: &AstNode) {}
     fn on_variable_return(&self, node: &AstNode) {}
     fn on_variable_func_decl(&self, node: &AstNode) {}
@@ -769,13 +922,19 @@ pub trait Visitor {
     fn on_variable_point(&self, node: &AstNode) {}
     fn on_variable_color(&self, node: &AstNode) {}
     fn on_variable_path(&self, node: &AstNode) {}
+    fn on_variable_arc(&self, node: &AstNode) {}
     fn on_variable_polygon(&self, node: &AstNode) {}
     fn on_variable_expr_s(&self, node: &AstNode) {}
     fn on_variable_manipulation(&self, node: &AstNode) {}
     fn on_variable_scale(&self, node: &AstNode) {}
+    fn on_variable_anchor(&self, node: &AstNode) {}
     fn on_variable_rotate(&self, node: &AstNode) {}
     fn on_variable_place(&self, node: &AstNode) {}
     fn on_variable_pos(&self, node: &AstNode) {}
+    fn on_variable_combine(&self, node: &AstNode) {}
+    fn on_variable_mirror(&self, node: &AstNode) {}
+    fn on_variable_move(&self, node: &AstNode) {}
+    fn on_variable_apply(&self, node: &AstNode) {}
     fn on_variable_scall(&self, node: &AstNode) {}
     fn on_variable_attr_s(&self, node: &AstNode) {}
     fn on_variable_attr(&self, node: &AstNode) {}
@@ -824,42 +983,51 @@ pub fn visit_ast_node(node: AstNode, visitor: &dyn Visitor) {
         0x0015 => visitor.on_variable_fork_case(&node),
         0x0016 => visitor.on_variable_otherwise(&node),
         0x0017 => visitor.on_variable_for(&node),
-        0x0018 => visitor.on_variable_draw(&node),
-        0x0019 => visitor.on_variable_return(&node),
-        0x001A => visitor.on_variable_func_decl(&node),
-        0x001B => visitor.on_variable_params(&node),
-        0x001C => visitor.on_variable_param(&node),
-        0x001D => visitor.on_variable_assign(&node),
-        0x001E => visitor.on_variable_var_decl(&node),
-        0x001F => visitor.on_variable_array_assign(&node),
-        0x0020 => visitor.on_variable_expr_t(&node),
-        0x0021 => visitor.on_variable_array(&node),
-        0x0022 => visitor.on_variable_point(&node),
-        0x0023 => visitor.on_variable_color(&node),
-        0x0024 => visitor.on_variable_path(&node),
-        0x0025 => visitor.on_variable_polygon(&node),
-        0x0026 => visitor.on_variable_expr_s(&node),
-        0x0027 => visitor.on_variable_manipulation(&node),
-        0x0028 => visitor.on_variable_scale(&node),
-        0x0029 => visitor.on_variable_rotate(&node),
-        0x002A => visitor.on_variable_place(&node),
-        0x002B => visitor.on_variable_pos(&node),
-        0x002C => visitor.on_variable_scall(&node),
-        0x002D => visitor.on_variable_attr_s(&node),
-        0x002E => visitor.on_variable_attr(&node),
-        0x002F => visitor.on_variable_expr_v(&node),
-        0x0030 => visitor.on_variable_expr_v1(&node),
-        0x0031 => visitor.on_variable_expr_v2(&node),
-        0x0032 => visitor.on_variable_expr_v3(&node),
-        0x0033 => visitor.on_variable_expr_v4(&node),
-        0x0034 => visitor.on_variable_expr_v5(&node),
-        0x0035 => visitor.on_variable_expr_v6(&node),
-        0x0036 => visitor.on_variable_expr_v7(&node),
-        0x0037 => visitor.on_variable_fcall(&node),
-        0x0038 => visitor.on_variable_args(&node),
-        0x0039 => visitor.on_variable_array_idx(&node),
-        0x003A => visitor.on_variable_member(&node),
-        0x003B => visitor.on_variable_types(&node),
+        0x0018 => visitor.on_variable_while(&node),
+        0x0019 => visitor.on_variable_break(&node),
+        0x001A => visitor.on_variable_continue(&node),
+        0x001B => visitor.on_variable_draw(&node),
+        0x001C => visitor.on_variable_return(&node),
+        0x001D => visitor.on_variable_func_decl(&node),
+        0x001E => visitor.on_variable_params(&node),
+        0x001F => visitor.on_variable_param(&node),
+        0x0020 => visitor.on_variable_assign(&node),
+        0x0021 => visitor.on_variable_var_decl(&node),
+        0x0022 => visitor.on_variable_array_assign(&node),
+        0x0023 => visitor.on_variable_expr_t(&node),
+        0x0024 => visitor.on_variable_array(&node),
+        0x0025 => visitor.on_variable_point(&node),
+        0x0026 => visitor.on_variable_color(&node),
+        0x0027 => visitor.on_variable_path(&node),
+        0x0028 => visitor.on_variable_arc(&node),
+        0x0029 => visitor.on_variable_polygon(&node),
+        0x002A => visitor.on_variable_expr_s(&node),
+        0x002B => visitor.on_variable_manipulation(&node),
+        0x002C => visitor.on_variable_scale(&node),
+        0x002D => visitor.on_variable_anchor(&node),
+        0x002E => visitor.on_variable_rotate(&node),
+        0x002F => visitor.on_variable_place(&node),
+        0x0030 => visitor.on_variable_pos(&node),
+        0x0031 => visitor.on_variable_combine(&node),
+        0x0032 => visitor.on_variable_mirror(&node),
+        0x0033 => visitor.on_variable_move(&node),
+        0x0034 => visitor.on_variable_apply(&node),
+        0x0035 => visitor.on_variable_scall(&node),
+        0x0036 => visitor.on_variable_attr_s(&node),
+        0x0037 => visitor.on_variable_attr(&node),
+        0x0038 => visitor.on_variable_expr_v(&node),
+        0x0039 => visitor.on_variable_expr_v1(&node),
+        0x003A => visitor.on_variable_expr_v2(&node),
+        0x003B => visitor.on_variable_expr_v3(&node),
+        0x003C => visitor.on_variable_expr_v4(&node),
+        0x003D => visitor.on_variable_expr_v5(&node),
+        0x003E => visitor.on_variable_expr_v6(&node),
+        0x003F => visitor.on_variable_expr_v7(&node),
+        0x0040 => visitor.on_variable_fcall(&node),
+        0x0041 => visitor.on_variable_args(&node),
+        0x0042 => visitor.on_variable_array_idx(&node),
+        0x0043 => visitor.on_variable_member(&node),
+        0x0044 => visitor.on_variable_types(&node),
         _ => ()
     };
 }
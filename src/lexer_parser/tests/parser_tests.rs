@@ -549,3 +549,23 @@ fn indexing(){
 
     test_equality(start, program);
 }
+
+#[test]
+fn path_arc(){
+    let program =
+    "begin
+    _ = (0, 0) arc(2, true, false) (4, 0)--(5, 5);
+    ";
+    let path_depth = 3;
+    let mut start = vec![("Program", 0), ("DeclS", 1), ("StmtS", 1), ("Assign", 2), ("_", 3)];
+    let path = vec![("--", path_depth),
+    ("Arc", path_depth+1), ("Point", path_depth+2), ("0", path_depth+3), ("0", path_depth+3),
+    ("2", path_depth+2), ("true", path_depth+2), ("false", path_depth+2),
+    ("Point", path_depth+2), ("4", path_depth+3), ("0", path_depth+3),
+    ("Point", path_depth+1), ("5", path_depth+2), ("5", path_depth+2)
+    ];
+
+    start.extend(path.into_iter());
+
+    test_equality(start, program);
+}
@@ -1,49 +1,116 @@
-use std::{env, error::Error, path::Path};
+use std::{env, error::Error, fs, path::PathBuf, process, time::SystemTime};
 
 use codebase::{
-    generators::generator::get_generator, interpreter::InterpretP, program::program::Program,
+    generators::generator::get_generator,
+    interpreter::InterpretP,
+    program::program::Program,
+    terminal::{
+        cli::{Cli, Command, USAGE},
+        repl::Repl,
+        status,
+        watch::{Watcher, POLL_INTERVAL},
+    },
     typechecker::TypeCheckP,
 };
 
-fn main() -> Result<(), Box<dyn Error>> {
-    //Lexer/Parser
+fn main() {
+    //Errors are printed with Display, so multi-line diagnostics keep their layout
+    if let Err(err) = run() {
+        eprintln!("Error: {}", err);
+        process::exit(1);
+    }
+}
 
-    let mut args = env::args();
-    let executable_name = args.next().unwrap(); // Will always exists, returns name of exetuable the program was executed using
-    let file_to_parse = args.next().ok_or_else(|| {
-        format!(
-            "{} <input> <generator> [-argument value] [-argument2 value2]...",
-            executable_name
-        )
-    })?;
-    let file_stem = file_to_parse.rsplitn(2, '.').last().unwrap();
+fn run() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse(env::args().skip(1))?;
+    //The parameter list is the only output of list-params
+    status::set_quiet(cli.quiet || cli.command == Command::ListParams);
 
-    let output_generators: Vec<String> = args
-        .next()
-        .ok_or_else(|| {
-            format!(
-                "{} <input> <generator> [-argument value] [-argument2 value2]...",
-                executable_name
-            )
-        })?
-        .split(",")
-        .map(str::to_string)
-        .collect();
+    match cli.command {
+        Command::Help => {
+            println!("{}", USAGE);
+            return Ok(());
+        }
+        Command::Repl => return Repl::new().run(),
+        _ => (),
+    }
 
-    let mut program = Program::from_file(Path::new(file_to_parse.as_str()))
-        .map_err(|err| format!("[Lexer/Parser] {}", err))?;
-    program
-        .type_check()
-        .map_err(|err| format!("[Typechecker] {}", err))?;
+    let mut watched_files = Vec::new();
+    if !cli.watch {
+        return build(&cli, &mut watched_files);
+    }
+
+    //Every cycle starts from scratch, errors are reported and the next change is awaited
+    loop {
+        //Which files to watch is only known after the build, a save made during it is caught by its time
+        let started = SystemTime::now();
+        if let Err(err) = build(&cli, &mut watched_files) {
+            eprintln!("Error: {}", err);
+        }
+        let watcher = Watcher::new(watched_files.clone());
+        if !watcher.modified_since(started) {
+            status::print(format!(
+                "[Watch] Waiting for changes to {} file(s)",
+                watched_files.len()
+            ));
+            watcher.wait(POLL_INTERVAL);
+        }
+        status::print("[Watch] Change detected, rebuilding");
+    }
+}
+
+/// Runs the command on the input file, each command goes one step further than the previous:
+/// parse and typecheck, interpret, generate the outputs.
+/// `watched_files` is set to the input file and every file it imports, also when this fails.
+fn build(cli: &Cli, watched_files: &mut Vec<PathBuf>) -> Result<(), Box<dyn Error>> {
+    *watched_files = vec![cli.input.clone()];
 
-    program.parse_terminal_args(args)?;
+    //Lexer/Parser
+    let mut program =
+        Program::from_file(&cli.input).map_err(|err| format!("[Lexer/Parser] {}", err))?;
+    program.add_import_dirs(&cli.import_dirs);
+    if let Some(limit) = cli.iteration_limit {
+        program.ienvironment.iteration_limit_set(limit);
+    }
+    if let Some(tolerance) = cli.flatten_tolerance {
+        program.ienvironment.flatten_tolerance_set(tolerance);
+    }
+
+    //Typechecker
+    //The resolver shares its module cache with the copies used while typechecking
+    let imports = program.tenvironment.imports_get().clone();
+    let checked = program.type_check();
+    *watched_files = imports.files();
+    checked.map_err(|err| format!("[Typechecker] {}", err))?;
+
+    if cli.command == Command::ListParams {
+        for param in program.params() {
+            match (param.required, param.default) {
+                (true, _) => println!("-{}: {} (required)", param.name, param.declared_type),
+                (false, Some(default)) => {
+                    println!("-{}: {} = {}", param.name, param.declared_type, default)
+                }
+                (false, None) => println!(
+                    "-{}: {} (default depends on other parameters)",
+                    param.name, param.declared_type
+                ),
+            }
+        }
+        return Ok(());
+    }
+
+    program.parse_terminal_args(cli.params.iter().cloned())?;
     program.tenvironment.clear();
-    println!("[Typechecker] OK");
+    status::print("[Typechecker] OK");
+    if cli.command == Command::Check {
+        return Ok(());
+    }
 
+    //Interpreter
     program
         .interpret()
         .map_err(|err| format!("[Interpreter] {}", err))?;
-    println!("[Interpreter] OK");
+    status::print("[Interpreter] OK");
 
     if let Some(returnvalue) = program.ienvironment.rvalue_get() {
         let exit_code = returnvalue.get_int()?;
@@ -51,25 +118,38 @@ fn main() -> Result<(), Box<dyn Error>> {
             return Err(format!("[Interpreter] exited with error code: {}", exit_code).into());
         }
     }
+    if cli.command == Command::Run {
+        return Ok(());
+    }
 
     //Generate Files from draw array
-    output_generators
-        .into_iter()
+    let output_stem = cli.output_stem();
+    if let Some(dir) = output_stem.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    cli.formats
+        .iter()
         .filter_map(|gen_name| {
-            let generator = get_generator(&gen_name);
+            let generator = get_generator(gen_name);
             if generator.is_none() {
-                println!("[Generator] Unsupported format: {}", gen_name);
+                eprintln!("[Generator] Unsupported format: {}", gen_name);
             }
             Some(gen_name).zip(generator)
         })
         .for_each(|(gen_name, mut generator)| {
-            if let Err(err) =
-                generator.generate(program.ienvironment.darray_get().clone(), file_stem.into())
-            {
-                println!(
+            match generator.generate(
+                program.ienvironment.darray_get().clone(),
+                output_stem.display().to_string(),
+            ) {
+                Ok(()) => status::print(format!(
+                    "[Generator] Wrote {}.{}",
+                    output_stem.display(),
+                    gen_name
+                )),
+                Err(err) => eprintln!(
                     "[Generator] Failed to generate format: {}, err: {}",
                     gen_name, err
-                );
+                ),
             }
         });
 
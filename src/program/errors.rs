@@ -1,5 +1,7 @@
 use core::fmt;
-use std::error::Error;
+use std::{error::Error, path::PathBuf};
+
+use super::span::Span;
 
 #[derive(Debug, Clone)]
 pub struct ASTNodeValueInvalid(pub String);
@@ -57,6 +59,15 @@ impl fmt::Display for ExprParseAsFloatError {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct ExprParseAsStringError;
+impl Error for ExprParseAsStringError {}
+impl fmt::Display for ExprParseAsStringError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Expression could not be parsed as string")
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ExprParseAsArrayError;
 impl Error for ExprParseAsArrayError {}
@@ -94,6 +105,15 @@ impl fmt::Display for ExprParseAsPointError {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct ExprParseAsTransformError;
+impl Error for ExprParseAsTransformError {}
+impl fmt::Display for ExprParseAsTransformError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Expression could not be parsed as a transform")
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct HimeParseMalfunction;
 impl Error for HimeParseMalfunction {}
@@ -104,3 +124,62 @@ impl fmt::Display for HimeParseMalfunction {
 }
 
 
+
+
+#[derive(Debug, Clone)]
+pub struct SyntaxError(pub String, pub usize, pub usize, pub String, pub String, pub String);
+impl Error for SyntaxError {}
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}:{}:{}: {}\n    {}\n    {}", self.0, self.1, self.2, self.3, self.4, self.5)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SyntaxErrors(pub Vec<SyntaxError>);
+impl Error for SyntaxErrors {}
+impl fmt::Display for SyntaxErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Could not parse the program source code, found {} syntax error(s):", self.0.len())?;
+        for err in &self.0 {
+            write!(f, "\n{}", err)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct LocatedError(pub Span, pub Box<dyn Error>);
+impl Error for LocatedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.1.as_ref())
+    }
+}
+impl fmt::Display for LocatedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}: {}", self.0, self.1)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportNotFound(pub String, pub Vec<PathBuf>);
+impl Error for ImportNotFound {}
+impl fmt::Display for ImportNotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Could not find imported file \"{}\", looked in:", self.0)?;
+        for path in &self.1 {
+            write!(f, "\n    {}", path.display())?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportCycle(pub Vec<PathBuf>);
+impl Error for ImportCycle {}
+impl fmt::Display for ImportCycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let chain: Vec<String> = self.0.iter().map(|path| path.display().to_string()).collect();
+        write!(f, "Import cycle detected: {}", chain.join(" -> "))
+    }
+}
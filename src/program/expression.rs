@@ -1,4 +1,4 @@
-use std::{collections::HashMap, error::Error};
+use std::{collections::HashMap, error::Error, rc::Rc};
 
 use hime_redist::{ast::AstNode, symbols::SemanticElementTrait};
 
@@ -6,8 +6,9 @@ use super::{
     errors,
     operators::{
         binaryoperator::BinaryOperator, pathoperator::PathOperator, polyoperator::PolyOperator,
-        unaryoperator::UnaryOperator,
+        shapeoperator::ShapeOperator, unaryoperator::UnaryOperator,
     },
+    span::{Span, Spanned},
 };
 
 #[derive(Debug, PartialEq, Clone)]
@@ -16,62 +17,125 @@ pub enum Expr {
     Variable(String),
     Boolean(bool),
     Float(f64),
-    Point(Box<Expr>, Box<Expr>),
-    Color(Box<Expr>, Box<Expr>, Box<Expr>, Box<Expr>),
+    String(String),
+    Point(Box<Spanned<Expr>>, Box<Spanned<Expr>>),
+    Color(Box<Spanned<Expr>>, Box<Spanned<Expr>>, Box<Spanned<Expr>>, Box<Spanned<Expr>>),
     PathOperation {
-        lhs: Box<Expr>,
-        rhs: Box<Expr>,
+        lhs: Box<Spanned<Expr>>,
+        rhs: Box<Spanned<Expr>>,
         operator: PathOperator,
     },
     ArrayIndex {
-        identifier: Box<Expr>,
-        index: Box<Expr>,
+        identifier: Box<Spanned<Expr>>,
+        index: Box<Spanned<Expr>>,
     },
     PolygonOperation {
-        path: Box<Expr>,
+        path: Box<Spanned<Expr>>,
         operator: PolyOperator,
     },
-    Array(Vec<Expr>),
+    Array(Vec<Spanned<Expr>>),
     BinaryOperation {
-        lhs: Box<Expr>,
-        rhs: Box<Expr>,
+        lhs: Box<Spanned<Expr>>,
+        rhs: Box<Spanned<Expr>>,
         operator: BinaryOperator,
     },
     UnaryOperation {
         operator: UnaryOperator,
-        expr: Box<Expr>,
+        expr: Box<Spanned<Expr>>,
     },
     FCall {
         name: String,
-        args: Vec<Expr>,
+        args: Vec<Spanned<Expr>>,
     },
     SCall {
         name: Option<String>,
-        args: HashMap<String, Expr>,
-        path_poly: Option<Box<Expr>>,
+        args: HashMap<String, Spanned<Expr>>,
+        path_poly: Option<Box<Spanned<Expr>>>,
     },
     Member {
         identifier: String,
         member_access: String,
     },
     Place {
-        base_shape: Box<Expr>,
-        second_shape: Box<Expr>,
+        base_shape: Box<Spanned<Expr>>,
+        second_shape: Box<Spanned<Expr>>,
         place_at: String,
-        point: Option<Box<Expr>>,
+        point: Option<Box<Spanned<Expr>>>,
     },
     Scale {
-        base_shape: Box<Expr>,
-        factor: Box<Expr>,
+        base_shape: Box<Spanned<Expr>>,
+        factor: Box<Spanned<Expr>>,
+        anchor: Option<Anchor>,
     },
     Rotate {
-        base_shape: Box<Expr>,
-        factor: Box<Expr>,
+        base_shape: Box<Spanned<Expr>>,
+        factor: Box<Spanned<Expr>>,
+        anchor: Option<Anchor>,
     },
+    /// `subtract a from b` has `b` as base shape
+    Combine {
+        base_shape: Box<Spanned<Expr>>,
+        second_shape: Box<Spanned<Expr>>,
+        operator: ShapeOperator,
+    },
+    Mirror {
+        base_shape: Box<Spanned<Expr>>,
+        axis: MirrorAxis,
+    },
+    /// `move s by p` translates s by p, `move s to p` puts the top left corner of s at p
+    Move {
+        base_shape: Box<Spanned<Expr>>,
+        point: Box<Spanned<Expr>>,
+        absolute: bool,
+    },
+    /// `apply t to s` transforms every point of s by the transform t
+    Apply {
+        transform: Box<Spanned<Expr>>,
+        base_shape: Box<Spanned<Expr>>,
+    },
+}
+
+/// The point given with `around` that a manipulation keeps in place
+#[derive(Debug, PartialEq, Clone)]
+pub enum Anchor {
+    /// `around center`, the center of the shape
+    Center,
+    /// `around topleft` and the other corners of the shape's bounding box
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    /// `around p`, any point
+    Point(Box<Spanned<Expr>>),
+}
+
+impl Anchor {
+    fn new(anchor: AstNode, file: &Rc<str>) -> Result<Self, Box<dyn Error>> {
+        Ok(match anchor.child(0).get_symbol().name {
+            "center" => Anchor::Center,
+            "topleft" => Anchor::TopLeft,
+            "topright" => Anchor::TopRight,
+            "bottomleft" => Anchor::BottomLeft,
+            "bottomright" => Anchor::BottomRight,
+            _ => Anchor::Point(Box::new(Expr::new(anchor.child(0), file)?)),
+        })
+    }
+}
+
+/// The line `mirror` flips a shape in
+#[derive(Debug, PartialEq, Clone)]
+pub enum MirrorAxis {
+    /// `horizontally`, left and right swap
+    Horizontal,
+    /// `vertically`, top and bottom swap
+    Vertical,
+    /// `across a to b`, the line through the points a and b
+    Line(Box<Spanned<Expr>>, Box<Spanned<Expr>>),
 }
 
 impl Expr {
-    pub fn new(expr: AstNode) -> Result<Self, Box<dyn Error>> {
+    pub fn new(expr: AstNode, file: &Rc<str>) -> Result<Spanned<Self>, Box<dyn Error>> {
+        let span = Span::new(&expr, file);
         let expr = match expr.get_symbol().name {
             "INTEGER" => Expr::Integer(
                 expr.get_value()
@@ -82,13 +146,13 @@ impl Expr {
             "+" | "-" | "*" | "/" | "%" | "<" | ">" | "<=" | ">=" | "!=" | "==" | "&&" | "||"
             | "!" => {
                 if expr.children_count() == 2 {
-                    let lhs = Box::new(Expr::new(expr.child(0))?);
-                    let rhs = Box::new(Expr::new(expr.child(1))?);
+                    let lhs = Box::new(Expr::new(expr.child(0), file)?);
+                    let rhs = Box::new(Expr::new(expr.child(1), file)?);
                     let operator = BinaryOperator::new(expr.get_symbol())?;
 
                     Expr::BinaryOperation { lhs, rhs, operator }
                 } else if expr.children_count() == 1 {
-                    let uexpr = Box::new(Expr::new(expr.child(0))?);
+                    let uexpr = Box::new(Expr::new(expr.child(0), file)?);
                     let operator = UnaryOperator::new(expr.get_symbol())?;
 
                     Expr::UnaryOperation {
@@ -105,16 +169,33 @@ impl Expr {
                 }
             }
             "--" | "~~" => {
-                let lhs = Box::new(Expr::new(expr.child(0))?);
-                let rhs = Box::new(Expr::new(expr.child(1))?);
+                let lhs = Box::new(Expr::new(expr.child(0), file)?);
+                let rhs = Box::new(Expr::new(expr.child(1), file)?);
                 let operator = PathOperator::new(expr.get_symbol())?;
 
                 //println!("lhs: {:?}\n operator: {:?}\n rhs {:?}\n\n", lhs, operator, rhs);
 
                 Expr::PathOperation { lhs, rhs, operator }
             }
+            "Arc" => {
+                //The arc parameters are between the points it joins
+                if expr.children_count() != 5 {
+                    return Err(
+                        errors::ASTNodeChildrenCountInvalid(5, expr.children_count()).into(),
+                    );
+                }
+                let lhs = Box::new(Expr::new(expr.child(0), file)?);
+                let rhs = Box::new(Expr::new(expr.child(4), file)?);
+                let operator = PathOperator::Arc {
+                    radius: Box::new(Expr::new(expr.child(1), file)?),
+                    large: Box::new(Expr::new(expr.child(2), file)?),
+                    sweep: Box::new(Expr::new(expr.child(3), file)?),
+                };
+
+                Expr::PathOperation { lhs, rhs, operator }
+            }
             "--*" | "~~*" => {
-                let path = Box::new(Expr::new(expr.child(0))?);
+                let path = Box::new(Expr::new(expr.child(0), file)?);
                 let operator = PolyOperator::new(expr.get_symbol())?;
 
                 Expr::PolygonOperation { path, operator }
@@ -134,14 +215,21 @@ impl Expr {
                     .ok_or_else(|| errors::ASTNodeValueInvalid(expr.get_symbol().name.to_owned()))?
                     .parse()?,
             ),
+            "STRING" => {
+                let literal = expr
+                    .get_value()
+                    .ok_or_else(|| errors::ASTNodeValueInvalid(expr.get_symbol().name.to_owned()))?;
+                //The token includes the surrounding quotes
+                Expr::String(literal[1..literal.len() - 1].to_owned())
+            }
             "Point" => Expr::Point(
-                Box::new(Expr::new(expr.child(0))?),
-                Box::new(Expr::new(expr.child(1))?),
+                Box::new(Expr::new(expr.child(0), file)?),
+                Box::new(Expr::new(expr.child(1), file)?),
             ),
             "Array" => Expr::Array(
                 expr.children()
                     .iter()
-                    .map(|arg| Expr::new(arg))
+                    .map(|arg| Expr::new(arg, file))
                     .collect::<Result<Vec<_>, _>>()?,
             ),
             "ArrayIdx" => {
@@ -151,8 +239,8 @@ impl Expr {
                     );
                 }
                 Expr::ArrayIndex {
-                    identifier: Box::new(Expr::new(expr.child(0))?),
-                    index: Box::new(Expr::new(expr.child(1))?),
+                    identifier: Box::new(Expr::new(expr.child(0), file)?),
+                    index: Box::new(Expr::new(expr.child(1), file)?),
                 }
             }
             "Color" => {
@@ -162,10 +250,10 @@ impl Expr {
                     );
                 }
                 Expr::Color(
-                    Box::new(Expr::new(expr.child(0))?),
-                    Box::new(Expr::new(expr.child(1))?),
-                    Box::new(Expr::new(expr.child(2))?),
-                    Box::new(Expr::new(expr.child(3))?),
+                    Box::new(Expr::new(expr.child(0), file)?),
+                    Box::new(Expr::new(expr.child(1), file)?),
+                    Box::new(Expr::new(expr.child(2), file)?),
+                    Box::new(Expr::new(expr.child(3), file)?),
                 )
             }
             "FCall" => {
@@ -185,7 +273,7 @@ impl Expr {
                             .child(1)
                             .children()
                             .iter()
-                            .map(|arg| Expr::new(arg))
+                            .map(|arg| Expr::new(arg, file))
                             .collect::<Result<Vec<_>, _>>()?,
                     }
                 // case: if function does not have params
@@ -244,8 +332,8 @@ impl Expr {
                                         )
                                     })?
                                     .into();
-                                let value = Expr::new(arg.child(1))?;
-                                Ok::<(String, Expr), Box<dyn Error>>((key, value))
+                                let value = Expr::new(arg.child(1), file)?;
+                                Ok::<(String, Spanned<Expr>), Box<dyn Error>>((key, value))
                             })
                             .collect::<Result<HashMap<_, _>, _>>()?,
                         path_poly: None,
@@ -267,11 +355,11 @@ impl Expr {
                                         )
                                     })?
                                     .into();
-                                let value = Expr::new(arg.child(1))?;
-                                Ok::<(String, Expr), Box<dyn Error>>((key, value))
+                                let value = Expr::new(arg.child(1), file)?;
+                                Ok::<(String, Spanned<Expr>), Box<dyn Error>>((key, value))
                             })
                             .collect::<Result<HashMap<_, _>, _>>()?,
-                        path_poly: Some(Box::new(Expr::new(expr.child(0))?)),
+                        path_poly: Some(Box::new(Expr::new(expr.child(0), file)?)),
                     }
                 }
             }
@@ -308,12 +396,12 @@ impl Expr {
                 let to_match = expr.child(0).to_string();
                 match to_match.as_str() {
                     "Place" => {
-                        let first_shape = Box::new(Expr::new(expr.child(0).child(0))?);
+                        let first_shape = Box::new(Expr::new(expr.child(0).child(0), file)?);
 
                         let placement = expr.child(0).child(1).get_symbol().to_string();
                         if expr.child(0).children_count() == 4 {
-                            let second_shape = Box::new(Expr::new(expr.child(0).child(3))?);
-                            let point = Box::new(Expr::new(expr.child(0).child(2))?);
+                            let second_shape = Box::new(Expr::new(expr.child(0).child(3), file)?);
+                            let point = Box::new(Expr::new(expr.child(0).child(2), file)?);
                             Expr::Place {
                                 base_shape: first_shape,
                                 second_shape: second_shape,
@@ -321,7 +409,7 @@ impl Expr {
                                 point: Some(point),
                             }
                         } else {
-                            let second_shape = Box::new(Expr::new(expr.child(0).child(2))?);
+                            let second_shape = Box::new(Expr::new(expr.child(0).child(2), file)?);
                             Expr::Place {
                                 base_shape: first_shape,
                                 second_shape: second_shape,
@@ -331,19 +419,79 @@ impl Expr {
                         }
                     }
                     "Rotate" => {
-                        let shape = Box::new(Expr::new(expr.child(0).child(0))?);
-                        let factor = Box::new(Expr::new(expr.child(0).child(1))?);
+                        let shape = Box::new(Expr::new(expr.child(0).child(0), file)?);
+                        let factor = Box::new(Expr::new(expr.child(0).child(1), file)?);
+                        let anchor = if expr.child(0).children_count() == 3 {
+                            Some(Anchor::new(expr.child(0).child(2), file)?)
+                        } else {
+                            None
+                        };
                         Expr::Rotate {
                             base_shape: shape,
                             factor: factor,
+                            anchor,
                         }
                     }
                     "Scale" => {
-                        let shape = Box::new(Expr::new(expr.child(0).child(0))?);
-                        let factor = Box::new(Expr::new(expr.child(0).child(1))?);
+                        let shape = Box::new(Expr::new(expr.child(0).child(0), file)?);
+                        let factor = Box::new(Expr::new(expr.child(0).child(1), file)?);
+                        let anchor = if expr.child(0).children_count() == 3 {
+                            Some(Anchor::new(expr.child(0).child(2), file)?)
+                        } else {
+                            None
+                        };
                         Expr::Scale {
                             base_shape: shape,
                             factor: factor,
+                            anchor,
+                        }
+                    }
+                    "Combine" => {
+                        let combine = expr.child(0);
+                        let operator = ShapeOperator::new(combine.child(0).get_symbol())?;
+                        let first_shape = Box::new(Expr::new(combine.child(1), file)?);
+                        let second_shape = Box::new(Expr::new(combine.child(2), file)?);
+                        let (base_shape, second_shape) = match operator {
+                            ShapeOperator::Subtract => (second_shape, first_shape),
+                            _ => (first_shape, second_shape),
+                        };
+                        Expr::Combine {
+                            base_shape,
+                            second_shape,
+                            operator,
+                        }
+                    }
+                    "Mirror" => {
+                        let mirror = expr.child(0);
+                        let shape = Box::new(Expr::new(mirror.child(0), file)?);
+                        let axis = if mirror.children_count() == 3 {
+                            MirrorAxis::Line(
+                                Box::new(Expr::new(mirror.child(1), file)?),
+                                Box::new(Expr::new(mirror.child(2), file)?),
+                            )
+                        } else if mirror.child(1).get_symbol().name == "horizontally" {
+                            MirrorAxis::Horizontal
+                        } else {
+                            MirrorAxis::Vertical
+                        };
+                        Expr::Mirror {
+                            base_shape: shape,
+                            axis,
+                        }
+                    }
+                    "Move" => {
+                        let movement = expr.child(0);
+                        Expr::Move {
+                            base_shape: Box::new(Expr::new(movement.child(0), file)?),
+                            point: Box::new(Expr::new(movement.child(2), file)?),
+                            absolute: movement.child(1).get_symbol().name == "to",
+                        }
+                    }
+                    "Apply" => {
+                        let apply = expr.child(0);
+                        Expr::Apply {
+                            transform: Box::new(Expr::new(apply.child(0), file)?),
+                            base_shape: Box::new(Expr::new(apply.child(1), file)?),
                         }
                     }
                     _ => unreachable!(),
@@ -352,6 +500,6 @@ impl Expr {
             _ => unreachable!(),
         };
 
-        Ok(expr)
+        Ok(Spanned::new(expr, span))
     }
 }
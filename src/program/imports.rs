@@ -0,0 +1,148 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeSet, HashMap},
+    env,
+    error::Error,
+    fs, iter,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use crate::typechecker::TypeCheckP;
+
+use super::{errors, program::Program};
+
+/// Environment variable listing extra directories to look for imported files in.
+pub const SEARCH_PATH_VAR: &str = "DAP_PATH";
+
+/// Finds and loads the files named by `import` statements.
+/// A path is first looked up relative to the directory of the importing file,
+/// and then in each directory of the search path, in order.
+/// Every resolver made from the same root shares one module cache.
+#[derive(Debug, Clone, Default)]
+pub struct ImportResolver {
+    base_dir: PathBuf,
+    search_path: Rc<Vec<PathBuf>>,
+    modules: Rc<RefCell<ModuleCache>>,
+}
+
+/// Imported programs by canonical path, each is parsed and typechecked once per run.
+#[derive(Debug, Default)]
+struct ModuleCache {
+    loaded: HashMap<PathBuf, Program>,
+    /// Files currently being loaded, as (canonical path, path shown to the user), outermost first
+    loading: Vec<(PathBuf, PathBuf)>,
+    /// Every file that was found, including the root and files that failed to load
+    files: BTreeSet<PathBuf>,
+}
+
+impl ImportResolver {
+    /// Resolver for the imports of the file at `path`, searching the directories in `DAP_PATH`.
+    /// The file itself counts as being loaded, so importing it again is reported as a cycle.
+    pub fn for_file(path: &Path) -> Self {
+        let search_path = env::var_os(SEARCH_PATH_VAR)
+            .map(|dirs| env::split_paths(&dirs).collect())
+            .unwrap_or_default();
+        let mut modules = ModuleCache::default();
+        if let Ok(canonical) = fs::canonicalize(path) {
+            modules.files.insert(canonical.clone());
+            modules.loading.push((canonical, path.to_path_buf()));
+        }
+
+        ImportResolver {
+            base_dir: Self::parent_dir(path),
+            search_path: Rc::new(search_path),
+            modules: Rc::new(RefCell::new(modules)),
+        }
+    }
+
+    /// Adds directories that are searched before the current search path, e.g. from `-I` flags.
+    pub fn with_search_dirs(&self, dirs: &[PathBuf]) -> Self {
+        let search_path = dirs.iter().chain(self.search_path.iter()).cloned().collect();
+
+        ImportResolver {
+            base_dir: self.base_dir.clone(),
+            search_path: Rc::new(search_path),
+            modules: self.modules.clone(),
+        }
+    }
+
+    pub fn resolve(&self, path: &str) -> Result<PathBuf, Box<dyn Error>> {
+        let candidates: Vec<PathBuf> = iter::once(&self.base_dir)
+            .chain(self.search_path.iter())
+            .map(|dir| dir.join(path))
+            .collect();
+
+        match candidates.iter().find(|candidate| candidate.is_file()) {
+            Some(found) => Ok(found.clone()),
+            None => Err(errors::ImportNotFound(path.to_owned(), candidates).into()),
+        }
+    }
+
+    /// Returns the parsed and typechecked program of an imported file.
+    /// Its own imports are resolved relative to it, using the same search path and cache.
+    pub fn load(&self, path: &str) -> Result<Program, Box<dyn Error>> {
+        let file = self.resolve(path)?;
+        let canonical = fs::canonicalize(&file)?;
+        self.modules.borrow_mut().files.insert(canonical.clone());
+
+        if let Some(program) = self.modules.borrow().loaded.get(&canonical) {
+            return Ok(program.clone());
+        }
+        self.check_cycle(&canonical, &file)?;
+
+        self.modules
+            .borrow_mut()
+            .loading
+            .push((canonical.clone(), file.clone()));
+        let program = self.parse_and_check(&file);
+        self.modules.borrow_mut().loading.pop();
+
+        let program = program?;
+        self.modules
+            .borrow_mut()
+            .loaded
+            .insert(canonical, program.clone());
+        Ok(program)
+    }
+
+    /// Canonical paths of the root file and every file reached through imports so far.
+    pub fn files(&self) -> Vec<PathBuf> {
+        self.modules.borrow().files.iter().cloned().collect()
+    }
+
+    fn parse_and_check(&self, file: &Path) -> Result<Program, Box<dyn Error>> {
+        let programstr = fs::read_to_string(file)?;
+        let mut program = Program::from_source(&programstr, &file.display().to_string())?;
+
+        program.imports_set(ImportResolver {
+            base_dir: Self::parent_dir(file),
+            search_path: self.search_path.clone(),
+            modules: self.modules.clone(),
+        });
+        program.type_check()?;
+        Ok(program)
+    }
+
+    fn check_cycle(&self, canonical: &Path, file: &Path) -> Result<(), Box<dyn Error>> {
+        let modules = self.modules.borrow();
+        let Some(start) = modules
+            .loading
+            .iter()
+            .position(|(loading, _)| loading == canonical)
+        else {
+            return Ok(());
+        };
+
+        let chain = modules.loading[start..]
+            .iter()
+            .map(|(_, shown)| shown.clone())
+            .chain(iter::once(file.to_path_buf()))
+            .collect();
+        Err(errors::ImportCycle(chain).into())
+    }
+
+    fn parent_dir(path: &Path) -> PathBuf {
+        path.parent().map(Path::to_path_buf).unwrap_or_default()
+    }
+}
@@ -4,5 +4,7 @@ pub mod statement;
 pub mod operators;
 pub mod r#type;
 pub mod errors;
+pub mod span;
+pub mod imports;
 
 pub mod tests;
\ No newline at end of file
@@ -1,4 +1,4 @@
-use std::error::Error;
+use std::{error::Error, fmt};
 
 use hime_redist::symbols::Symbol;
 
@@ -39,4 +39,25 @@ impl BinaryOperator {
         };
         Ok(operator)
     }
-}
\ No newline at end of file
+}
+/// Shows the operator the way it is written in DAP source code
+impl fmt::Display for BinaryOperator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbol = match self {
+            Self::Add => "+",
+            Self::Subtract => "-",
+            Self::Multiply => "*",
+            Self::Divide => "/",
+            Self::Modulus => "%",
+            Self::LessThan => "<",
+            Self::GreaterThan => ">",
+            Self::LessThanOrEquals => "<=",
+            Self::GreaterThanOrEquals => ">=",
+            Self::NotEquals => "!=",
+            Self::Equals => "==",
+            Self::LogicalAnd => "&&",
+            Self::LogicalOr => "||",
+        };
+        write!(f, "{}", symbol)
+    }
+}
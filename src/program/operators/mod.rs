@@ -2,3 +2,4 @@ pub mod binaryoperator;
 pub mod pathoperator;
 pub mod unaryoperator;
 pub mod polyoperator;
+pub mod shapeoperator;
@@ -2,10 +2,19 @@ use std::error::Error;
 
 use hime_redist::symbols::Symbol;
 
+use crate::program::{expression::Expr, span::Spanned};
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum PathOperator {
     Line,
     Curve,
+    /// `arc(radius, large, sweep)`, an elliptical arc like SVG's `A` command.
+    /// The radius is a number for a circle or a point with the radius along x and y.
+    Arc {
+        radius: Box<Spanned<Expr>>,
+        large: Box<Spanned<Expr>>,
+        sweep: Box<Spanned<Expr>>,
+    },
 }
 
 impl PathOperator {
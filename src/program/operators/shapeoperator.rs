@@ -0,0 +1,36 @@
+use std::error::Error;
+
+use hime_redist::symbols::Symbol;
+
+/// Boolean operations on the areas covered by two shapes
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ShapeOperator {
+    Union,
+    Intersect,
+    Subtract,
+    Xor,
+}
+
+impl ShapeOperator {
+    pub fn new(operator: Symbol) -> Result<Self, Box<dyn Error>> {
+        let operator = match operator.name {
+            "union" => Self::Union,
+            "intersect" => Self::Intersect,
+            "subtract" => Self::Subtract,
+            "xor" => Self::Xor,
+            _ => unreachable!(),
+        };
+        Ok(operator)
+    }
+
+    /// Whether a point is in the result, given whether it is in the base shape and the second shape.
+    /// `subtract` removes the second shape from the base shape.
+    pub fn apply(&self, in_base: bool, in_second: bool) -> bool {
+        match self {
+            Self::Union => in_base || in_second,
+            Self::Intersect => in_base && in_second,
+            Self::Subtract => in_base && !in_second,
+            Self::Xor => in_base != in_second,
+        }
+    }
+}
@@ -1,41 +1,55 @@
-use std::{error::Error, fs, path::Path};
-use hime_redist::symbols::SemanticElementTrait;
+use std::{error::Error, fs, path::{Path, PathBuf}, rc::Rc};
+use hime_redist::{
+    errors::{ParseError, ParseErrorDataTrait},
+    parsers::MAX_ERROR_COUNT,
+    symbols::SemanticElementTrait,
+    text::Text,
+};
 use crate::{interpreter::environment::IEnvironment, lexer_parser::grammar::cfg, typechecker::environment::TEnvironment};
-use super::{errors, statement::Stmt};
+use super::{errors, imports::ImportResolver, span::Spanned, statement::Stmt};
 
 #[derive(Debug, Clone)]
 pub struct Program {
-    pub decl_f: Vec<Stmt>,
-    pub stmts: Vec<Stmt>,
+    pub decl_f: Vec<Spanned<Stmt>>,
+    pub stmts: Vec<Spanned<Stmt>>,
     pub tenvironment: TEnvironment,
     pub ienvironment: IEnvironment,
 }
 
 impl Program {
     pub fn new(programstr: &String) -> Result<Self, Box<dyn Error>> {
-        let mut decl_f: Vec<Stmt> = Vec::new();
-        let mut stmts: Vec<Stmt> = Vec::new();
+        Self::from_source(programstr, "<input>")
+    }
+
+    /// Parses the source code of a program, `file_name` is only used when reporting syntax errors.
+    pub fn from_source(programstr: &String, file_name: &str) -> Result<Self, Box<dyn Error>> {
+        let mut decl_f: Vec<Spanned<Stmt>> = Vec::new();
+        let mut stmts: Vec<Spanned<Stmt>> = Vec::new();
         let tenvironment = TEnvironment::new();
         let ienvironment = IEnvironment::new();
 
         let parsed = cfg::parse_string(programstr.into());
+        if !parsed.errors.errors.is_empty() {
+            return Err(errors::SyntaxErrors(Self::syntax_errors(programstr, file_name)).into());
+        }
         if !parsed.is_success() {
             return Err(errors::HimeParseMalfunction.into());
         }
         let ast = parsed.get_ast();
         let root_node = ast.get_root();
+        let file: Rc<str> = file_name.into();
 
 
         for node in root_node.children() {
             match node.get_symbol().name {
                 "DeclS" => {
                     for decl in node.children() {
-                        decl_f.push(Stmt::new(decl)?);
+                        decl_f.push(Stmt::new(decl, &file)?);
                     }
                 }
                 "StmtS" => {
                     for stmt in node.children() {
-                        stmts.push(Stmt::new(stmt)?);
+                        stmts.push(Stmt::new(stmt, &file)?);
                     }
                 }
                 _ => unreachable!(),
@@ -53,6 +67,93 @@ impl Program {
     pub fn from_file(path: &Path) -> Result<Self, Box<dyn Error>> {
         let programstr = fs::read_to_string(path)?;
 
-        Self::new(&programstr)
+        let mut program = Self::from_source(&programstr, &path.display().to_string())?;
+        program.imports_set(ImportResolver::for_file(path));
+        Ok(program)
+    }
+
+    /// Sets how the program's `import` statements are resolved, for both typechecking and interpreting.
+    pub fn imports_set(&mut self, imports: ImportResolver) {
+        self.tenvironment.imports_set(imports.clone());
+        self.ienvironment.imports_set(imports);
+    }
+
+    /// Adds directories to search for imported files, before those from `DAP_PATH`.
+    pub fn add_import_dirs(&mut self, dirs: &[PathBuf]) {
+        let imports = self.tenvironment.imports_get().with_search_dirs(dirs);
+        self.imports_set(imports);
     }
-}
\ No newline at end of file
+
+    /// Collects every syntax error in the source code. Hime stops at the first unexpected token,
+    /// and errors after an unexpected character are often caused by the lexer's own recovery.
+    /// So only the first error is kept, the statement containing it is blanked out,
+    /// and the source is parsed again until no new errors are found.
+    fn syntax_errors(programstr: &str, file_name: &str) -> Vec<errors::SyntaxError> {
+        let original = Text::from_str(programstr);
+        let mut source = programstr.to_string();
+        let mut syntax_errors: Vec<errors::SyntaxError> = Vec::new();
+
+        while syntax_errors.len() < MAX_ERROR_COUNT {
+            let parsed = cfg::parse_string(source.clone());
+            let Some(err) = parsed.errors.errors.iter().min_by_key(|err| err.get_position()) else {
+                break;
+            };
+            let position = err.get_position();
+            let error_idx = parsed.text.get_index_at(position);
+
+            //After recovery, a block or file ending unexpectedly is most likely caused by the blanking
+            if !syntax_errors.is_empty()
+                && matches!(err, ParseError::UnexpectedToken(_))
+                && (error_idx >= source.len() || source[error_idx..].starts_with('}'))
+            {
+                break;
+            }
+            //Stop when blanking did not move the parser past the previous error
+            if syntax_errors.last().is_some_and(|last| (last.1, last.2) >= (position.line, position.column)) {
+                break;
+            }
+
+            let context = original.get_context_for(position, err.get_length());
+            syntax_errors.push(errors::SyntaxError(
+                file_name.into(),
+                position.line,
+                position.column,
+                err.to_string(),
+                context.content.into(),
+                context.pointer,
+            ));
+
+            let stmt_start = source[..error_idx]
+                .rfind([';', '{', '}'])
+                .map(|idx| idx + 1)
+                .max(source[..error_idx].rfind("begin").map(|idx| idx + "begin".len()))
+                .unwrap_or(0);
+            let stmt_end = Self::statement_end(&source, error_idx);
+            let blanked: String = source[stmt_start..stmt_end]
+                .chars()
+                .map(|c| if c == '\n' || c == '\r' { c } else { ' ' })
+                .collect();
+            source.replace_range(stmt_start..stmt_end, &blanked);
+        }
+        syntax_errors
+    }
+
+    //Index after the ';' ending the statement at idx, a block opened before it is skipped entirely
+    fn statement_end(source: &str, idx: usize) -> usize {
+        let mut depth = 0;
+        for (offset, c) in source[idx..].char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' if depth > 0 => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return idx + offset + 1;
+                    }
+                }
+                ';' if depth == 0 => return idx + offset + 1,
+                _ => (),
+            }
+        }
+        source.len()
+    }
+}
@@ -0,0 +1,85 @@
+use std::{error::Error, fmt, rc::Rc};
+
+use hime_redist::ast::AstNode;
+
+use super::errors;
+
+/// Position of an AST node in its source file.
+/// Spans made with `Default` (e.g. nodes built by hand) have line 0 and are never reported.
+#[derive(Debug, Clone, Default)]
+pub struct Span {
+    pub file: Rc<str>,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Span {
+    pub fn new(node: &AstNode, file: &Rc<str>) -> Self {
+        let (line, column) = node
+            .get_total_position_and_span()
+            .map(|(position, _)| (position.line, position.column))
+            .unwrap_or_default();
+        Self {
+            file: file.clone(),
+            line,
+            column,
+        }
+    }
+
+    pub fn is_known(&self) -> bool {
+        self.line > 0
+    }
+
+    /// Prefixes an error with this position.
+    /// Errors that already have a position keep it, so the innermost node is the one reported.
+    pub fn locate(&self, err: Box<dyn Error>) -> Box<dyn Error> {
+        if !self.is_known() || err.is::<errors::LocatedError>() {
+            return err;
+        }
+        errors::LocatedError(self.clone(), err).into()
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+/// An AST node together with its position in the source.
+/// The position is not part of equality, so parsed and hand-built trees compare equal.
+#[derive(Debug, Clone)]
+pub struct Spanned<T> {
+    pub node: T,
+    pub span: Span,
+}
+
+impl<T> Spanned<T> {
+    pub fn new(node: T, span: Span) -> Self {
+        Self { node, span }
+    }
+}
+
+impl<T> From<T> for Spanned<T> {
+    fn from(node: T) -> Self {
+        Self::new(node, Span::default())
+    }
+}
+
+impl<T> From<T> for Box<Spanned<T>> {
+    fn from(node: T) -> Self {
+        Box::new(Spanned::from(node))
+    }
+}
+
+impl<T: PartialEq> PartialEq for Spanned<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.node == other.node
+    }
+}
+
+impl<T: PartialEq> PartialEq<T> for Spanned<T> {
+    fn eq(&self, other: &T) -> bool {
+        self.node == *other
+    }
+}
@@ -1,4 +1,4 @@
-use std::error::Error;
+use std::{error::Error, rc::Rc};
 
 use hime_redist::ast::AstNode;
 use hime_redist::symbols::SemanticElementTrait;
@@ -6,57 +6,65 @@ use hime_redist::symbols::SemanticElementTrait;
 use super::expression::Expr;
 use crate::program::errors;
 use crate::program::r#type::Type;
+use crate::program::span::{Span, Spanned};
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Stmt {
     VarDecl {
         name: String,
         declared_type: Type,
-        value: Expr,
+        value: Spanned<Expr>,
     },
     FuncDecl {
         name: String,
         return_type: Type,
         parameters: Vec<(String, Type)>,
-        statements: Vec<Stmt>,
+        statements: Vec<Spanned<Stmt>>,
     },
-    Return(Expr),
+    Return(Spanned<Expr>),
     Decl {
         name: String,
         declared_type: Type,
-        value: Option<Expr>,
+        value: Option<Spanned<Expr>>,
     },
     Import {
         name: String,
         path: String,
     },
     Draw {
-        shape: Expr,
-        point: Option<Expr>,
+        shape: Spanned<Expr>,
+        point: Option<Spanned<Expr>>,
     },
     Assign {
         name: String,
-        value: Expr,
+        value: Spanned<Expr>,
     },
     ArrayAssign {
         name: String,
-        value: Expr,
-        index: Expr,
+        value: Spanned<Expr>,
+        index: Spanned<Expr>,
     },
     For {
         counter: String,
-        from: Expr,
-        to: Expr,
-        body: Vec<Stmt>,
+        from: Spanned<Expr>,
+        to: Spanned<Expr>,
+        body: Vec<Spanned<Stmt>>,
     },
     Fork {
-        branches: Vec<(Expr, Vec<Stmt>)>,
-        otherwise: Option<Vec<Stmt>>,
+        branches: Vec<(Spanned<Expr>, Vec<Spanned<Stmt>>)>,
+        otherwise: Option<Vec<Spanned<Stmt>>>,
     },
+    While {
+        condition: Spanned<Expr>,
+        body: Vec<Spanned<Stmt>>,
+    },
+    Break,
+    Continue,
 }
 
 impl Stmt {
-    pub fn new(stmt: AstNode) -> Result<Self, Box<dyn Error>> {
+    pub fn new(stmt: AstNode, file: &Rc<str>) -> Result<Spanned<Self>, Box<dyn Error>> {
+        let span = Span::new(&stmt, file);
         let stmt = match stmt.get_symbol().name {
             //-----
             // Decl
@@ -82,7 +90,7 @@ impl Stmt {
                         errors::ASTNodeValueInvalid(stmt.child(1).get_symbol().name.to_owned())
                     })?)?,
                     value: if stmt.children_count() > 2 {
-                        Some(Expr::new(stmt.child(2))?)
+                        Some(Expr::new(stmt.child(2), file)?)
                     } else {
                         None
                     },
@@ -131,7 +139,7 @@ impl Stmt {
                     declared_type: Type::new(stmt.child(1).get_value().ok_or_else(|| {
                         errors::ASTNodeValueInvalid(stmt.child(0).get_symbol().name.to_owned())
                     })?)?,
-                    value: Expr::new(stmt.child(2))?,
+                    value: Expr::new(stmt.child(2), file)?,
                 }
             }
             "Assign" => {
@@ -148,7 +156,7 @@ impl Stmt {
                             errors::ASTNodeValueInvalid(stmt.child(0).get_symbol().name.to_owned())
                         })?
                         .into(),
-                    value: Expr::new(stmt.child(1))?,
+                    value: Expr::new(stmt.child(1), file)?,
                 }
             }
             "ArrayAssign" => {
@@ -165,8 +173,8 @@ impl Stmt {
                             errors::ASTNodeValueInvalid(stmt.child(0).get_symbol().name.to_owned())
                         })?
                         .into()),
-                    value: (Expr::new(stmt.child(2))?),
-                    index: (Expr::new(stmt.child(1))?),
+                    value: (Expr::new(stmt.child(2), file)?),
+                    index: (Expr::new(stmt.child(1), file)?),
                 }
             }
             "FuncDecl" => {
@@ -175,7 +183,7 @@ impl Stmt {
                     let mut statements = Vec::new();
 
                     for stmt in stmt.child(2).children() {
-                        statements.push(Stmt::new(stmt)?);
+                        statements.push(Stmt::new(stmt, file)?);
                     }
 
                     Stmt::FuncDecl {
@@ -232,7 +240,7 @@ impl Stmt {
                     }
 
                     for stmt in stmt.child(3).children() {
-                        statements.push(Stmt::new(stmt)?);
+                        statements.push(Stmt::new(stmt, file)?);
                     }
 
                     Stmt::FuncDecl {
@@ -264,19 +272,19 @@ impl Stmt {
                         errors::ASTNodeChildrenCountInvalid(1, stmt.children_count()).into(),
                     );
                 }
-                Stmt::Return(Expr::new(stmt.child(0))?)
+                Stmt::Return(Expr::new(stmt.child(0), file)?)
             }
 
             "Draw" => {
                 if stmt.children_count() == 1 {
                     Stmt::Draw {
-                        shape: Expr::new(stmt.child(0))?,
+                        shape: Expr::new(stmt.child(0), file)?,
                         point: None,
                     }
                 } else if stmt.children_count() == 2 {
                     Stmt::Draw {
-                        shape: Expr::new(stmt.child(0))?,
-                        point: Some(Expr::new(stmt.child(1))?),
+                        shape: Expr::new(stmt.child(0), file)?,
+                        point: Some(Expr::new(stmt.child(1), file)?),
                     }
                 } else {
                     return Err(errors::ASTNodeChildrenCountInvalidEither(
@@ -295,7 +303,7 @@ impl Stmt {
                 }
                 let mut statements = vec![];
                 for stmt in stmt.child(3).children() {
-                    statements.push(Stmt::new(stmt)?);
+                    statements.push(Stmt::new(stmt, file)?);
                 }
 
                 Stmt::For {
@@ -306,28 +314,46 @@ impl Stmt {
                             errors::ASTNodeValueInvalid(stmt.child(0).get_symbol().name.to_owned())
                         })?
                         .into(),
-                    from: Expr::new(stmt.child(1))?,
-                    to: Expr::new(stmt.child(2))?,
+                    from: Expr::new(stmt.child(1), file)?,
+                    to: Expr::new(stmt.child(2), file)?,
+                    body: statements,
+                }
+            }
+            "While" => {
+                if stmt.children_count() != 2 {
+                    return Err(
+                        errors::ASTNodeChildrenCountInvalid(2, stmt.children_count()).into(),
+                    );
+                }
+                let mut statements = vec![];
+                for stmt in stmt.child(1).children() {
+                    statements.push(Stmt::new(stmt, file)?);
+                }
+
+                Stmt::While {
+                    condition: Expr::new(stmt.child(0), file)?,
                     body: statements,
                 }
             }
+            "Break" => Stmt::Break,
+            "Continue" => Stmt::Continue,
             "Fork" => {
                 let mut branchs = vec![];
                 let mut otherwise = None;
-                let mut statements: Vec<Stmt>;
+                let mut statements: Vec<Spanned<Stmt>>;
                 for forkexpr in stmt.children() {
                     match forkexpr.to_string().as_str() {
                         "ForkCase" => {
                             statements = vec![];
                             for stmt in forkexpr.child(1).children() {
-                                statements.push(Stmt::new(stmt)?);
+                                statements.push(Stmt::new(stmt, file)?);
                             }
-                            branchs.push((Expr::new(forkexpr.child(0))?, statements));
+                            branchs.push((Expr::new(forkexpr.child(0), file)?, statements));
                         }
                         "Otherwise" => {
                             statements = vec![];
                             for stmt in forkexpr.child(0).children() {
-                                statements.push(Stmt::new(stmt)?);
+                                statements.push(Stmt::new(stmt, file)?);
                             }
                             otherwise = Some(statements);
                         }
@@ -343,6 +369,6 @@ impl Stmt {
             _ => unreachable!(),
         };
 
-        Ok(stmt)
+        Ok(Spanned::new(stmt, span))
     }
 }
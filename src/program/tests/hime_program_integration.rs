@@ -2,10 +2,10 @@ use std::{collections::HashMap, vec};
 
 use crate::{
     program::{
-        expression::Expr,
+        expression::{Anchor, Expr, MirrorAxis},
         operators::{
             binaryoperator::BinaryOperator, pathoperator::PathOperator, polyoperator::PolyOperator,
-            unaryoperator::UnaryOperator,
+            shapeoperator::ShapeOperator, unaryoperator::UnaryOperator,
         },
         program,
         r#type::Type,
@@ -30,7 +30,7 @@ fn test_program_new_converts_ast_to_program_fcall() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Int);
@@ -38,7 +38,7 @@ fn test_program_new_converts_ast_to_program_fcall() {
             value,
             &Expr::FCall {
                 name: "f".to_string(),
-                args: vec![Expr::Integer(1)]
+                args: vec![Expr::Integer(1).into()]
             }
         );
     }
@@ -57,7 +57,7 @@ fn test_program_new_converts_ast_to_program_fcall_without_params() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Int);
@@ -84,7 +84,7 @@ fn test_program_new_converts_ast_to_program_var_decl_int() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Int);
@@ -105,17 +105,17 @@ fn test_program_new_converts_ast_to_program_var_decl_color() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Color);
         assert_eq!(
             value,
             &Expr::Color(
-                Box::new(Expr::Integer(0)),
-                Box::new(Expr::Integer(0)),
-                Box::new(Expr::Integer(0)),
-                Box::new(Expr::Integer(0))
+                Box::new(Expr::Integer(0).into()),
+                Box::new(Expr::Integer(0).into()),
+                Box::new(Expr::Integer(0).into()),
+                Box::new(Expr::Integer(0).into())
             )
         );
     }
@@ -134,13 +134,13 @@ fn test_program_new_converts_ast_to_program_var_decl_point() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Point);
         assert_eq!(
             value,
-            &Expr::Point(Box::new(Expr::Integer(0)), Box::new(Expr::Integer(0)))
+            &Expr::Point(Box::new(Expr::Integer(0).into()), Box::new(Expr::Integer(0).into()))
         );
     }
 }
@@ -158,7 +158,7 @@ fn test_program_new_converts_ast_to_program_var_decl_float() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Float);
@@ -166,6 +166,36 @@ fn test_program_new_converts_ast_to_program_var_decl_float() {
     }
 }
 
+#[test]
+fn test_program_new_converts_ast_to_program_var_decl_string() {
+    let code = "begin
+    x: string = \"hello, \" + \"\";";
+
+    let program = program::Program::new(&code.to_string()).unwrap();
+
+    assert_eq!(program.stmts.len(), 1);
+
+    if let Stmt::VarDecl {
+        name,
+        declared_type,
+        value,
+    } = &program.stmts[0].node
+    {
+        assert_eq!(name, "x");
+        assert_eq!(declared_type, &Type::String);
+        assert_eq!(
+            value,
+            &Expr::BinaryOperation {
+                lhs: Expr::String("hello, ".into()).into(),
+                rhs: Expr::String("".into()).into(),
+                operator: BinaryOperator::Add,
+            }
+        );
+    } else {
+        panic!("Expected a variable declaration");
+    }
+}
+
 #[test]
 fn test_program_new_converts_ast_to_program_var_decl_bool() {
     let code = "begin
@@ -179,7 +209,7 @@ fn test_program_new_converts_ast_to_program_var_decl_bool() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Bool);
@@ -198,7 +228,7 @@ fn test_program_new_converts_ast_to_program_var_decl_scall_with_params() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "myShape");
         assert_eq!(declared_type, &Type::Shape);
@@ -207,8 +237,8 @@ fn test_program_new_converts_ast_to_program_var_decl_scall_with_params() {
             &Expr::SCall {
                 name: Some("dummy".into()),
                 args: HashMap::from([
-                    ("x".to_string(), Expr::Integer(1)),
-                    ("b".to_string(), Expr::Boolean(true))
+                    ("x".to_string(), Expr::Integer(1).into()),
+                    ("b".to_string(), Expr::Boolean(true).into())
                 ]),
                 path_poly: None
             }
@@ -227,7 +257,7 @@ fn test_program_new_converts_ast_to_program_scall_path_with_params() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "myShape");
         assert_eq!(declared_type, &Type::Shape);
@@ -242,7 +272,7 @@ fn test_program_new_converts_ast_to_program_scall_path_with_params() {
                         Expr::Integer(1).into(),
                         Expr::Integer(1).into(),
                         Expr::Integer(1).into()
-                    )
+                    ).into()
                 ),]),
                 path_poly: Some(
                     Expr::PathOperation {
@@ -267,7 +297,7 @@ fn test_program_new_converts_ast_to_program_scall_polygon_with_params() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "myShape");
         assert_eq!(declared_type, &Type::Shape);
@@ -282,19 +312,19 @@ fn test_program_new_converts_ast_to_program_scall_polygon_with_params() {
                         Expr::Integer(1).into(),
                         Expr::Integer(1).into(),
                         Expr::Integer(1).into()
-                    )
+                    ).into()
                 ),]),
                 path_poly: Some(
                     Expr::PolygonOperation {
                         path: Expr::PathOperation {
                             lhs: Box::new(Expr::Point(
-                                Box::new(Expr::Integer(1)),
-                                Box::new(Expr::Integer(2))
-                            )),
+                                Box::new(Expr::Integer(1).into()),
+                                Box::new(Expr::Integer(2).into())
+                            ).into()),
                             rhs: Box::new(Expr::Point(
-                                Box::new(Expr::Integer(3)),
-                                Box::new(Expr::Integer(4))
-                            )),
+                                Box::new(Expr::Integer(3).into()),
+                                Box::new(Expr::Integer(4).into())
+                            ).into()),
                             operator: PathOperator::Line
                         }.into(),
                         operator: PolyOperator::Straight
@@ -317,7 +347,7 @@ fn test_program_new_converts_ast_to_program_var_decl_scall_without_params() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "myShape");
         assert_eq!(declared_type, &Type::Shape);
@@ -343,7 +373,7 @@ fn test_program_new_converts_ast_to_program_var_decl_path_straight() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Path);
@@ -351,13 +381,13 @@ fn test_program_new_converts_ast_to_program_var_decl_path_straight() {
             value,
             &Expr::PathOperation {
                 lhs: Box::new(Expr::Point(
-                    Box::new(Expr::Integer(1)),
-                    Box::new(Expr::Integer(2))
-                )),
+                    Box::new(Expr::Integer(1).into()),
+                    Box::new(Expr::Integer(2).into())
+                ).into()),
                 rhs: Box::new(Expr::Point(
-                    Box::new(Expr::Integer(3)),
-                    Box::new(Expr::Integer(4))
-                )),
+                    Box::new(Expr::Integer(3).into()),
+                    Box::new(Expr::Integer(4).into())
+                ).into()),
                 operator: PathOperator::Line
             }
         );
@@ -375,7 +405,7 @@ fn test_program_new_converts_ast_to_program_var_decl_path_curved() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Path);
@@ -383,13 +413,13 @@ fn test_program_new_converts_ast_to_program_var_decl_path_curved() {
             value,
             &Expr::PathOperation {
                 lhs: Box::new(Expr::Point(
-                    Box::new(Expr::Integer(1)),
-                    Box::new(Expr::Integer(2))
-                )),
+                    Box::new(Expr::Integer(1).into()),
+                    Box::new(Expr::Integer(2).into())
+                ).into()),
                 rhs: Box::new(Expr::Point(
-                    Box::new(Expr::Integer(3)),
-                    Box::new(Expr::Integer(4))
-                )),
+                    Box::new(Expr::Integer(3).into()),
+                    Box::new(Expr::Integer(4).into())
+                ).into()),
                 operator: PathOperator::Curve
             }
         );
@@ -407,7 +437,7 @@ fn test_program_new_converts_ast_to_program_var_decl_polygon_straight() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Polygon);
@@ -416,13 +446,13 @@ fn test_program_new_converts_ast_to_program_var_decl_polygon_straight() {
             &Expr::PolygonOperation {
                 path: Expr::PathOperation {
                     lhs: Box::new(Expr::Point(
-                        Box::new(Expr::Integer(1)),
-                        Box::new(Expr::Integer(2))
-                    )),
+                        Box::new(Expr::Integer(1).into()),
+                        Box::new(Expr::Integer(2).into())
+                    ).into()),
                     rhs: Box::new(Expr::Point(
-                        Box::new(Expr::Integer(3)),
-                        Box::new(Expr::Integer(4))
-                    )),
+                        Box::new(Expr::Integer(3).into()),
+                        Box::new(Expr::Integer(4).into())
+                    ).into()),
                     operator: PathOperator::Line
                 }.into(),
                 operator: PolyOperator::Straight
@@ -442,7 +472,7 @@ fn test_program_new_converts_ast_to_program_var_decl_polygon_curve() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Polygon);
@@ -451,13 +481,13 @@ fn test_program_new_converts_ast_to_program_var_decl_polygon_curve() {
             &Expr::PolygonOperation {
                 path: Expr::PathOperation {
                     lhs: Box::new(Expr::Point(
-                        Box::new(Expr::Integer(1)),
-                        Box::new(Expr::Integer(2))
-                    )),
+                        Box::new(Expr::Integer(1).into()),
+                        Box::new(Expr::Integer(2).into())
+                    ).into()),
                     rhs: Box::new(Expr::Point(
-                        Box::new(Expr::Integer(3)),
-                        Box::new(Expr::Integer(4))
-                    )),
+                        Box::new(Expr::Integer(3).into()),
+                        Box::new(Expr::Integer(4).into())
+                    ).into()),
                     operator: PathOperator::Curve
                 }.into(),
                 operator: PolyOperator::Curved
@@ -477,7 +507,7 @@ fn test_program_new_converts_ast_to_program_var_decl_array_empty() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::IntArray);
@@ -496,11 +526,11 @@ fn test_program_new_converts_ast_to_program_var_decl_array_not_empty() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::IntArray);
-        assert_eq!(value, &Expr::Array(vec![Expr::Integer(1)]));
+        assert_eq!(value, &Expr::Array(vec![Expr::Integer(1).into()]));
     }
 }
 
@@ -515,7 +545,7 @@ fn test_program_new_converts_ast_to_program_member_access_color() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "y");
         assert_eq!(declared_type, &Type::Int);
@@ -540,7 +570,7 @@ fn test_program_new_converts_ast_to_program_member_access_shape() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "y");
         assert_eq!(declared_type, &Type::Shape);
@@ -565,7 +595,7 @@ fn test_program_new_converts_ast_to_program_member_access_point() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "y");
         assert_eq!(declared_type, &Type::Float);
@@ -590,7 +620,7 @@ fn test_program_new_converts_ast_to_program_member_access_array() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "y");
         assert_eq!(declared_type, &Type::Int);
@@ -615,7 +645,7 @@ fn test_program_new_converts_ast_to_program_place() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "z");
         assert_eq!(declared_type, &Type::Shape);
@@ -626,7 +656,7 @@ fn test_program_new_converts_ast_to_program_place() {
                 second_shape: Expr::Variable("y".to_string()).into(),
                 place_at: "ontop".to_string(),
                 point: Some(
-                    Expr::Point(Box::new(Expr::Integer(1)), Box::new(Expr::Integer(2))).into()
+                    Expr::Point(Box::new(Expr::Integer(1).into()), Box::new(Expr::Integer(2).into())).into()
                 )
             }
         );
@@ -644,7 +674,7 @@ fn test_program_new_converts_ast_to_program_place_without_point() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "z");
         assert_eq!(declared_type, &Type::Shape);
@@ -671,7 +701,7 @@ fn test_program_new_converts_ast_to_program_rotate() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "z");
         assert_eq!(declared_type, &Type::Shape);
@@ -679,7 +709,8 @@ fn test_program_new_converts_ast_to_program_rotate() {
             value,
             &Expr::Rotate {
                 base_shape: Expr::Variable("x".to_string()).into(),
-                factor: Expr::Integer(5).into()
+                factor: Expr::Integer(5).into(),
+                anchor: None,
             }
         );
     }
@@ -696,7 +727,7 @@ fn test_program_new_converts_ast_to_program_scale() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "z");
         assert_eq!(declared_type, &Type::Shape);
@@ -704,7 +735,8 @@ fn test_program_new_converts_ast_to_program_scale() {
             value,
             &Expr::Scale {
                 base_shape: Expr::Variable("x".to_string()).into(),
-                factor: Expr::Integer(5).into()
+                factor: Expr::Integer(5).into(),
+                anchor: None,
             }
         );
     }
@@ -726,7 +758,7 @@ fn test_program_new_converts_ast_to_program_decl_int_with_default_value() {
         name,
         declared_type,
         value,
-    } = &program.decl_f[0]
+    } = &program.decl_f[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Int);
@@ -748,7 +780,7 @@ fn test_program_new_converts_ast_to_program_decl_int_without_default_value() {
         name,
         declared_type,
         value,
-    } = &program.decl_f[0]
+    } = &program.decl_f[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Int);
@@ -770,7 +802,7 @@ fn test_program_new_converts_ast_to_program_decl_float_with_default_value() {
         name,
         declared_type,
         value,
-    } = &program.decl_f[0]
+    } = &program.decl_f[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Float);
@@ -792,7 +824,7 @@ fn test_program_new_converts_ast_to_program_decl_float_without_default_value() {
         name,
         declared_type,
         value,
-    } = &program.decl_f[0]
+    } = &program.decl_f[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Float);
@@ -814,7 +846,7 @@ fn test_program_new_converts_ast_to_program_decl_bool_with_default_value_true()
         name,
         declared_type,
         value,
-    } = &program.decl_f[0]
+    } = &program.decl_f[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Bool);
@@ -836,7 +868,7 @@ fn test_program_new_converts_ast_to_program_decl_bool_with_default_value_false()
         name,
         declared_type,
         value,
-    } = &program.decl_f[0]
+    } = &program.decl_f[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Bool);
@@ -858,7 +890,7 @@ fn test_program_new_converts_ast_to_program_decl_bool_without_default_value() {
         name,
         declared_type,
         value,
-    } = &program.decl_f[0]
+    } = &program.decl_f[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Bool);
@@ -880,7 +912,7 @@ fn test_program_new_converts_ast_to_program_decl_color_without_default_value() {
         name,
         declared_type,
         value,
-    } = &program.decl_f[0]
+    } = &program.decl_f[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Color);
@@ -933,7 +965,7 @@ fn test_program_new_converts_ast_to_program_decl_import() {
 
     assert_eq!(program.decl_f.len(), 1);
 
-    if let Stmt::Import { name, path } = &program.decl_f[0] {
+    if let Stmt::Import { name, path } = &program.decl_f[0].node {
         assert_eq!(name, "x");
         assert_eq!(path, "x.extension");
     }
@@ -955,15 +987,15 @@ fn test_program_new_converts_ast_to_program_addition_int_int() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Int);
         assert_eq!(
             value,
             &Expr::BinaryOperation {
-                lhs: Box::new(Expr::Integer(1)),
-                rhs: Box::new(Expr::Integer(1)),
+                lhs: Box::new(Expr::Integer(1).into()),
+                rhs: Box::new(Expr::Integer(1).into()),
                 operator: BinaryOperator::Add
             }
         );
@@ -983,15 +1015,15 @@ fn test_program_new_converts_ast_to_program_addition_float_float() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Float);
         assert_eq!(
             value,
             &Expr::BinaryOperation {
-                lhs: Box::new(Expr::Float(1.0)),
-                rhs: Box::new(Expr::Float(1.0)),
+                lhs: Box::new(Expr::Float(1.0).into()),
+                rhs: Box::new(Expr::Float(1.0).into()),
                 operator: BinaryOperator::Add
             }
         );
@@ -1011,15 +1043,15 @@ fn test_program_new_converts_ast_to_program_addition_int_float() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Float);
         assert_eq!(
             value,
             &Expr::BinaryOperation {
-                lhs: Box::new(Expr::Integer(1)),
-                rhs: Box::new(Expr::Float(1.0)),
+                lhs: Box::new(Expr::Integer(1).into()),
+                rhs: Box::new(Expr::Float(1.0).into()),
                 operator: BinaryOperator::Add
             }
         );
@@ -1039,15 +1071,15 @@ fn test_program_new_converts_ast_to_program_subtraction_int_int() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Int);
         assert_eq!(
             value,
             &Expr::BinaryOperation {
-                lhs: Box::new(Expr::Integer(1)),
-                rhs: Box::new(Expr::Integer(1)),
+                lhs: Box::new(Expr::Integer(1).into()),
+                rhs: Box::new(Expr::Integer(1).into()),
                 operator: BinaryOperator::Subtract
             }
         );
@@ -1067,15 +1099,15 @@ fn test_program_new_converts_ast_to_program_subtraction_float_float() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Float);
         assert_eq!(
             value,
             &Expr::BinaryOperation {
-                lhs: Box::new(Expr::Float(1.0)),
-                rhs: Box::new(Expr::Float(1.0)),
+                lhs: Box::new(Expr::Float(1.0).into()),
+                rhs: Box::new(Expr::Float(1.0).into()),
                 operator: BinaryOperator::Subtract
             }
         );
@@ -1095,15 +1127,15 @@ fn test_program_new_converts_ast_to_program_subtraction_int_float() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Float);
         assert_eq!(
             value,
             &Expr::BinaryOperation {
-                lhs: Box::new(Expr::Integer(1)),
-                rhs: Box::new(Expr::Float(1.0)),
+                lhs: Box::new(Expr::Integer(1).into()),
+                rhs: Box::new(Expr::Float(1.0).into()),
                 operator: BinaryOperator::Subtract
             }
         );
@@ -1123,15 +1155,15 @@ fn test_program_new_converts_ast_to_program_multiplication_int_int() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Int);
         assert_eq!(
             value,
             &Expr::BinaryOperation {
-                lhs: Box::new(Expr::Integer(1)),
-                rhs: Box::new(Expr::Integer(1)),
+                lhs: Box::new(Expr::Integer(1).into()),
+                rhs: Box::new(Expr::Integer(1).into()),
                 operator: BinaryOperator::Multiply
             }
         );
@@ -1151,15 +1183,15 @@ fn test_program_new_converts_ast_to_program_multiplication_float_float() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Float);
         assert_eq!(
             value,
             &Expr::BinaryOperation {
-                lhs: Box::new(Expr::Float(1.0)),
-                rhs: Box::new(Expr::Float(1.0)),
+                lhs: Box::new(Expr::Float(1.0).into()),
+                rhs: Box::new(Expr::Float(1.0).into()),
                 operator: BinaryOperator::Multiply
             }
         );
@@ -1179,15 +1211,15 @@ fn test_program_new_converts_ast_to_program_multiplication_int_float() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Float);
         assert_eq!(
             value,
             &Expr::BinaryOperation {
-                lhs: Box::new(Expr::Integer(1)),
-                rhs: Box::new(Expr::Float(1.0)),
+                lhs: Box::new(Expr::Integer(1).into()),
+                rhs: Box::new(Expr::Float(1.0).into()),
                 operator: BinaryOperator::Multiply
             }
         );
@@ -1207,15 +1239,15 @@ fn test_program_new_converts_ast_to_program_modulus_int_int() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Int);
         assert_eq!(
             value,
             &Expr::BinaryOperation {
-                lhs: Box::new(Expr::Integer(1)),
-                rhs: Box::new(Expr::Integer(1)),
+                lhs: Box::new(Expr::Integer(1).into()),
+                rhs: Box::new(Expr::Integer(1).into()),
                 operator: BinaryOperator::Modulus
             }
         );
@@ -1235,15 +1267,15 @@ fn test_program_new_converts_ast_to_program_modulus_float_float() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Float);
         assert_eq!(
             value,
             &Expr::BinaryOperation {
-                lhs: Box::new(Expr::Float(1.0)),
-                rhs: Box::new(Expr::Float(1.0)),
+                lhs: Box::new(Expr::Float(1.0).into()),
+                rhs: Box::new(Expr::Float(1.0).into()),
                 operator: BinaryOperator::Modulus
             }
         );
@@ -1263,15 +1295,15 @@ fn test_program_new_converts_ast_to_program_modulus_int_float() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Float);
         assert_eq!(
             value,
             &Expr::BinaryOperation {
-                lhs: Box::new(Expr::Integer(1)),
-                rhs: Box::new(Expr::Float(1.0)),
+                lhs: Box::new(Expr::Integer(1).into()),
+                rhs: Box::new(Expr::Float(1.0).into()),
                 operator: BinaryOperator::Modulus
             }
         );
@@ -1291,15 +1323,15 @@ fn test_program_new_converts_ast_to_program_divide_int_int() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Int);
         assert_eq!(
             value,
             &Expr::BinaryOperation {
-                lhs: Box::new(Expr::Integer(1)),
-                rhs: Box::new(Expr::Integer(1)),
+                lhs: Box::new(Expr::Integer(1).into()),
+                rhs: Box::new(Expr::Integer(1).into()),
                 operator: BinaryOperator::Divide
             }
         );
@@ -1319,15 +1351,15 @@ fn test_program_new_converts_ast_to_program_divide_float_float() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Float);
         assert_eq!(
             value,
             &Expr::BinaryOperation {
-                lhs: Box::new(Expr::Float(1.0)),
-                rhs: Box::new(Expr::Float(1.0)),
+                lhs: Box::new(Expr::Float(1.0).into()),
+                rhs: Box::new(Expr::Float(1.0).into()),
                 operator: BinaryOperator::Divide
             }
         );
@@ -1347,15 +1379,15 @@ fn test_program_new_converts_ast_to_program_divide_int_float() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Float);
         assert_eq!(
             value,
             &Expr::BinaryOperation {
-                lhs: Box::new(Expr::Integer(1)),
-                rhs: Box::new(Expr::Float(1.0)),
+                lhs: Box::new(Expr::Integer(1).into()),
+                rhs: Box::new(Expr::Float(1.0).into()),
                 operator: BinaryOperator::Divide
             }
         );
@@ -1379,15 +1411,15 @@ fn test_program_new_converts_ast_to_program_ge_int_int() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Bool);
         assert_eq!(
             value,
             &Expr::BinaryOperation {
-                lhs: Box::new(Expr::Integer(1)),
-                rhs: Box::new(Expr::Integer(1)),
+                lhs: Box::new(Expr::Integer(1).into()),
+                rhs: Box::new(Expr::Integer(1).into()),
                 operator: BinaryOperator::GreaterThanOrEquals
             }
         );
@@ -1407,15 +1439,15 @@ fn test_program_new_converts_ast_to_program_ge_float_float() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Bool);
         assert_eq!(
             value,
             &Expr::BinaryOperation {
-                lhs: Box::new(Expr::Float(1.0)),
-                rhs: Box::new(Expr::Float(1.0)),
+                lhs: Box::new(Expr::Float(1.0).into()),
+                rhs: Box::new(Expr::Float(1.0).into()),
                 operator: BinaryOperator::GreaterThanOrEquals
             }
         );
@@ -1435,15 +1467,15 @@ fn test_program_new_converts_ast_to_program_ge_int_float() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Bool);
         assert_eq!(
             value,
             &Expr::BinaryOperation {
-                lhs: Box::new(Expr::Integer(1)),
-                rhs: Box::new(Expr::Float(1.0)),
+                lhs: Box::new(Expr::Integer(1).into()),
+                rhs: Box::new(Expr::Float(1.0).into()),
                 operator: BinaryOperator::GreaterThanOrEquals
             }
         );
@@ -1463,15 +1495,15 @@ fn test_program_new_converts_ast_to_program_great_int_int() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Bool);
         assert_eq!(
             value,
             &Expr::BinaryOperation {
-                lhs: Box::new(Expr::Integer(1)),
-                rhs: Box::new(Expr::Integer(1)),
+                lhs: Box::new(Expr::Integer(1).into()),
+                rhs: Box::new(Expr::Integer(1).into()),
                 operator: BinaryOperator::GreaterThan
             }
         );
@@ -1491,15 +1523,15 @@ fn test_program_new_converts_ast_to_program_less_int_int() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Bool);
         assert_eq!(
             value,
             &Expr::BinaryOperation {
-                lhs: Box::new(Expr::Integer(1)),
-                rhs: Box::new(Expr::Integer(1)),
+                lhs: Box::new(Expr::Integer(1).into()),
+                rhs: Box::new(Expr::Integer(1).into()),
                 operator: BinaryOperator::LessThan
             }
         );
@@ -1519,15 +1551,15 @@ fn test_program_new_converts_ast_to_program_le_int_int() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Bool);
         assert_eq!(
             value,
             &Expr::BinaryOperation {
-                lhs: Box::new(Expr::Integer(1)),
-                rhs: Box::new(Expr::Integer(1)),
+                lhs: Box::new(Expr::Integer(1).into()),
+                rhs: Box::new(Expr::Integer(1).into()),
                 operator: BinaryOperator::LessThanOrEquals
             }
         );
@@ -1547,15 +1579,15 @@ fn test_program_new_converts_ast_to_program_ne_int_int() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Bool);
         assert_eq!(
             value,
             &Expr::BinaryOperation {
-                lhs: Box::new(Expr::Integer(1)),
-                rhs: Box::new(Expr::Integer(1)),
+                lhs: Box::new(Expr::Integer(1).into()),
+                rhs: Box::new(Expr::Integer(1).into()),
                 operator: BinaryOperator::NotEquals
             }
         );
@@ -1575,15 +1607,15 @@ fn test_program_new_converts_ast_to_program_equals_int_int() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Bool);
         assert_eq!(
             value,
             &Expr::BinaryOperation {
-                lhs: Box::new(Expr::Integer(1)),
-                rhs: Box::new(Expr::Integer(1)),
+                lhs: Box::new(Expr::Integer(1).into()),
+                rhs: Box::new(Expr::Integer(1).into()),
                 operator: BinaryOperator::Equals
             }
         );
@@ -1603,15 +1635,15 @@ fn test_program_new_converts_ast_to_program_log_and() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Bool);
         assert_eq!(
             value,
             &Expr::BinaryOperation {
-                lhs: Box::new(Expr::Boolean(true)),
-                rhs: Box::new(Expr::Boolean(false)),
+                lhs: Box::new(Expr::Boolean(true).into()),
+                rhs: Box::new(Expr::Boolean(false).into()),
                 operator: BinaryOperator::LogicalAnd
             }
         );
@@ -1631,15 +1663,15 @@ fn test_program_new_converts_ast_to_program_log_or() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Bool);
         assert_eq!(
             value,
             &Expr::BinaryOperation {
-                lhs: Box::new(Expr::Boolean(true)),
-                rhs: Box::new(Expr::Boolean(false)),
+                lhs: Box::new(Expr::Boolean(true).into()),
+                rhs: Box::new(Expr::Boolean(false).into()),
                 operator: BinaryOperator::LogicalOr
             }
         );
@@ -1659,7 +1691,7 @@ fn test_program_new_converts_ast_to_program_negate() {
         name,
         declared_type,
         value,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "x");
         assert_eq!(declared_type, &Type::Bool);
@@ -1667,7 +1699,7 @@ fn test_program_new_converts_ast_to_program_negate() {
             value,
             &Expr::UnaryOperation {
                 operator: UnaryOperator::Negate,
-                expr: Box::new(Expr::Boolean(true))
+                expr: Box::new(Expr::Boolean(true).into())
             }
         );
     }
@@ -1690,7 +1722,7 @@ fn test_program_new_converts_ast_to_program_function_decl_with_return_type() {
         return_type,
         parameters,
         statements,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "func");
         assert_eq!(return_type, &Type::Int);
@@ -1701,9 +1733,9 @@ fn test_program_new_converts_ast_to_program_function_decl_with_return_type() {
                 Stmt::VarDecl {
                     name: "f".into(),
                     declared_type: Type::Int,
-                    value: Expr::Integer(1)
+                    value: Expr::Integer(1).into()
                 },
-                Stmt::Return(Expr::Variable("f".into()))
+                Stmt::Return(Expr::Variable("f".into()).into())
             ]
         );
     }
@@ -1726,7 +1758,7 @@ fn test_program_new_converts_ast_to_program_function_without_param() {
         return_type,
         parameters,
         statements,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(name, "func");
         assert_eq!(return_type, &Type::Int);
@@ -1737,9 +1769,9 @@ fn test_program_new_converts_ast_to_program_function_without_param() {
                 Stmt::VarDecl {
                     name: "f".into(),
                     declared_type: Type::Int,
-                    value: Expr::Integer(1)
+                    value: Expr::Integer(1).into()
                 },
-                Stmt::Return(Expr::Variable("f".into()))
+                Stmt::Return(Expr::Variable("f".into()).into())
             ]
         );
     }
@@ -1759,7 +1791,7 @@ fn test_program_draw_without_point() {
 
     assert_eq!(program.stmts.len(), 1);
 
-    if let Stmt::Draw { shape, point } = &program.stmts[0] {
+    if let Stmt::Draw { shape, point } = &program.stmts[0].node {
         assert_eq!(shape, &Expr::Variable("x".to_string()));
         assert_eq!(point, &None);
     }
@@ -1774,7 +1806,7 @@ fn test_program_draw_with_point() {
 
     assert_eq!(program.stmts.len(), 1);
 
-    if let Stmt::Draw { shape, point } = &program.stmts[0] {
+    if let Stmt::Draw { shape, point } = &program.stmts[0].node {
         assert_eq!(shape, &Expr::Variable("x".to_string()));
         assert_eq!(
             point,
@@ -1795,7 +1827,7 @@ fn test_program_assign() {
 
     assert_eq!(program.stmts.len(), 1);
 
-    if let Stmt::Assign { name, value } = &program.stmts[0] {
+    if let Stmt::Assign { name, value } = &program.stmts[0].node {
         assert_eq!(name, &"x".to_string());
         assert_eq!(value, &Expr::Integer(10));
     }
@@ -1813,7 +1845,7 @@ fn test_program_array_assign() {
 
     assert_eq!(program.stmts.len(), 1);
 
-    if let Stmt::ArrayAssign { name, value, index } = &program.stmts[0] {
+    if let Stmt::ArrayAssign { name, value, index } = &program.stmts[0].node {
         assert_eq!(name, &"x".to_string());
         assert_eq!(value, &Expr::Integer(10));
         assert_eq!(index, &Expr::Integer(1));
@@ -1832,11 +1864,11 @@ fn test_program_array_indexing() {
     let Stmt::Assign {
         name: _,
         value,
-    } = &program.stmts[0] else {
+    } = &program.stmts[0].node else {
         panic!()
     };
     
-    let Expr::ArrayIndex { identifier, index } = value else {
+    let Expr::ArrayIndex { identifier, index } = &value.node else {
         panic!()
     };
     assert_eq!(identifier, &Expr::Variable("x".into()).into());
@@ -1864,7 +1896,7 @@ fn test_program_for_loop() {
         from,
         to,
         body,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(counter, &"i".to_string());
         assert_eq!(from, &Expr::Integer(1));
@@ -1873,7 +1905,7 @@ fn test_program_for_loop() {
             body,
             &vec![Stmt::Assign {
                 name: "x".to_string(),
-                value: Expr::Variable("i".to_string())
+                value: Expr::Variable("i".to_string()).into()
             }]
         );
     }
@@ -1896,18 +1928,19 @@ fn test_program_fork() {
     if let Stmt::Fork {
         branches: branch,
         otherwise,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(otherwise, &None);
         assert_eq!(
             branch,
             &vec![(
-                Expr::Boolean(true),
+                Expr::Boolean(true).into(),
                 vec![Stmt::VarDecl {
                     name: "x".to_string(),
                     declared_type: Type::Int,
-                    value: Expr::Integer(2)
-                }]
+                    value: Expr::Integer(2).into()
+                }
+                .into()]
             )]
         );
     }
@@ -1928,26 +1961,216 @@ fn test_program_fork_otherwise() {
     if let Stmt::Fork {
         branches: branch,
         otherwise,
-    } = &program.stmts[0]
+    } = &program.stmts[0].node
     {
         assert_eq!(
             otherwise,
             &Some(vec![Stmt::VarDecl {
                 name: "y".to_string(),
                 declared_type: Type::Int,
-                value: Expr::Integer(3)
-            }])
+                value: Expr::Integer(3).into()
+            }
+            .into()])
         );
         assert_eq!(
             branch,
             &vec![(
-                Expr::Boolean(true),
+                Expr::Boolean(true).into(),
                 vec![Stmt::VarDecl {
                     name: "x".to_string(),
                     declared_type: Type::Int,
-                    value: Expr::Integer(2)
-                }]
+                    value: Expr::Integer(2).into()
+                }
+                .into()]
             )]
         );
     }
 }
+
+#[test]
+fn test_program_new_converts_ast_to_program_combine() {
+    let code = "begin
+    z:shape = subtract x from union y with x;
+    ";
+    let program = program::Program::new(&code.to_string()).unwrap();
+
+    if let Stmt::VarDecl {
+        name,
+        declared_type,
+        value,
+    } = &program.stmts[0].node
+    {
+        assert_eq!(name, "z");
+        assert_eq!(declared_type, &Type::Shape);
+        assert_eq!(
+            value,
+            &Expr::Combine {
+                base_shape: Expr::Combine {
+                    base_shape: Expr::Variable("y".to_string()).into(),
+                    second_shape: Expr::Variable("x".to_string()).into(),
+                    operator: ShapeOperator::Union,
+                }
+                .into(),
+                second_shape: Expr::Variable("x".to_string()).into(),
+                operator: ShapeOperator::Subtract,
+            }
+        );
+    }
+}
+
+#[test]
+fn test_program_new_converts_ast_to_program_mirror() {
+    let code = "begin
+    y:shape = mirror x horizontally;
+    z:shape = mirror x across (0, 0) to (1, 1);
+    ";
+    let program = program::Program::new(&code.to_string()).unwrap();
+    let values: Vec<&Expr> = program
+        .stmts
+        .iter()
+        .filter_map(|stmt| match &stmt.node {
+            Stmt::VarDecl { value, .. } => Some(&value.node),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(
+        values,
+        vec![
+            &Expr::Mirror {
+                base_shape: Expr::Variable("x".to_string()).into(),
+                axis: MirrorAxis::Horizontal,
+            },
+            &Expr::Mirror {
+                base_shape: Expr::Variable("x".to_string()).into(),
+                axis: MirrorAxis::Line(
+                    Expr::Point(Expr::Integer(0).into(), Expr::Integer(0).into()).into(),
+                    Expr::Point(Expr::Integer(1).into(), Expr::Integer(1).into()).into(),
+                ),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_program_new_converts_ast_to_program_move() {
+    let code = "begin
+    y:shape = move x by (1, 2);
+    z:shape = move x to p;
+    ";
+    let program = program::Program::new(&code.to_string()).unwrap();
+    let values: Vec<&Expr> = program
+        .stmts
+        .iter()
+        .filter_map(|stmt| match &stmt.node {
+            Stmt::VarDecl { value, .. } => Some(&value.node),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(
+        values,
+        vec![
+            &Expr::Move {
+                base_shape: Expr::Variable("x".to_string()).into(),
+                point: Expr::Point(Expr::Integer(1).into(), Expr::Integer(2).into()).into(),
+                absolute: false,
+            },
+            &Expr::Move {
+                base_shape: Expr::Variable("x".to_string()).into(),
+                point: Expr::Variable("p".to_string()).into(),
+                absolute: true,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_program_new_converts_ast_to_program_scale_around() {
+    let code = "begin
+    y:shape = scale x by (2, 1) around center;
+    z:shape = scale x by 2 around (0, 0);
+    ";
+    let program = program::Program::new(&code.to_string()).unwrap();
+    let values: Vec<&Expr> = program
+        .stmts
+        .iter()
+        .filter_map(|stmt| match &stmt.node {
+            Stmt::VarDecl { value, .. } => Some(&value.node),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(
+        values,
+        vec![
+            &Expr::Scale {
+                base_shape: Expr::Variable("x".to_string()).into(),
+                factor: Expr::Point(Expr::Integer(2).into(), Expr::Integer(1).into()).into(),
+                anchor: Some(Anchor::Center),
+            },
+            &Expr::Scale {
+                base_shape: Expr::Variable("x".to_string()).into(),
+                factor: Expr::Integer(2).into(),
+                anchor: Some(Anchor::Point(
+                    Expr::Point(Expr::Integer(0).into(), Expr::Integer(0).into()).into()
+                )),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_program_new_converts_ast_to_program_rotate_around() {
+    let code = "begin
+    y:shape = rotate x by 90 around topleft;
+    z:shape = rotate x by 90 around p;
+    ";
+    let program = program::Program::new(&code.to_string()).unwrap();
+    let values: Vec<&Expr> = program
+        .stmts
+        .iter()
+        .filter_map(|stmt| match &stmt.node {
+            Stmt::VarDecl { value, .. } => Some(&value.node),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(
+        values,
+        vec![
+            &Expr::Rotate {
+                base_shape: Expr::Variable("x".to_string()).into(),
+                factor: Expr::Integer(90).into(),
+                anchor: Some(Anchor::TopLeft),
+            },
+            &Expr::Rotate {
+                base_shape: Expr::Variable("x".to_string()).into(),
+                factor: Expr::Integer(90).into(),
+                anchor: Some(Anchor::Point(Expr::Variable("p".to_string()).into())),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_program_new_converts_ast_to_program_apply() {
+    let code = "begin
+    t:transform = rotation(45);
+    z:shape = apply t to x;
+    ";
+    let program = program::Program::new(&code.to_string()).unwrap();
+
+    if let Stmt::VarDecl { declared_type, .. } = &program.stmts[0].node {
+        assert_eq!(declared_type, &Type::Transform);
+    }
+    if let Stmt::VarDecl { value, .. } = &program.stmts[1].node {
+        assert_eq!(
+            value,
+            &Expr::Apply {
+                transform: Expr::Variable("t".to_string()).into(),
+                base_shape: Expr::Variable("x".to_string()).into(),
+            }
+        );
+    }
+}
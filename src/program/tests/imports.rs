@@ -0,0 +1,107 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    program::{
+        errors::{ImportCycle, ImportNotFound, LocatedError},
+        imports::ImportResolver,
+        program::Program,
+    },
+    typechecker::TypeCheckP,
+};
+
+#[test]
+fn imports_resolve_relative_to_importing_file() {
+    let mut program = Program::from_file(Path::new("dap-lib/lines/dottedSqaure.dap")).unwrap();
+
+    assert!(program.type_check().is_ok());
+}
+
+#[test]
+fn importing_file_directory_is_searched_first() {
+    let resolver = ImportResolver::for_file(Path::new("dap-lib/digital-font/string.dap"))
+        .with_search_dirs(&["dap-lib/basic-shapes".into()]);
+
+    assert_eq!(
+        resolver.resolve("letters/A.dap").unwrap(),
+        PathBuf::from("dap-lib/digital-font/letters/A.dap")
+    );
+}
+
+#[test]
+fn search_dirs_are_used_when_not_next_to_importing_file() {
+    let resolver = ImportResolver::default()
+        .with_search_dirs(&["src/typechecker/tests/test_imports".into()]);
+
+    assert_eq!(
+        resolver.resolve("square.extension").unwrap(),
+        PathBuf::from("src/typechecker/tests/test_imports/square.extension")
+    );
+}
+
+#[test]
+fn missing_import_lists_searched_paths() {
+    let resolver = ImportResolver::for_file(Path::new("dap-lib/lines/dottedSqaure.dap"))
+        .with_search_dirs(&["dap-lib/basic-shapes".into()]);
+
+    let err = resolver.resolve("nothing.dap").unwrap_err();
+
+    let not_found = err.downcast_ref::<ImportNotFound>().unwrap();
+    assert_eq!(
+        not_found.1,
+        vec![
+            PathBuf::from("dap-lib/lines/nothing.dap"),
+            PathBuf::from("dap-lib/basic-shapes/nothing.dap")
+        ]
+    );
+}
+
+#[test]
+fn import_cycle_reports_chain() {
+    let mut program =
+        Program::from_file(Path::new("src/program/tests/test_imports/cycle_a.extension")).unwrap();
+
+    let err = program.type_check().unwrap_err();
+
+    let located = err.downcast_ref::<LocatedError>().unwrap();
+    let cycle = located.1.downcast_ref::<ImportCycle>().unwrap();
+    assert_eq!(
+        cycle.0,
+        vec![
+            PathBuf::from("src/program/tests/test_imports/cycle_a.extension"),
+            PathBuf::from("src/program/tests/test_imports/cycle_b.extension"),
+            PathBuf::from("src/program/tests/test_imports/cycle_a.extension")
+        ]
+    );
+}
+
+#[test]
+fn module_imported_twice_is_not_a_cycle() {
+    let mut program =
+        Program::from_file(Path::new("src/program/tests/test_imports/diamond.extension")).unwrap();
+
+    assert!(program.type_check().is_ok());
+}
+
+#[test]
+fn resolver_lists_every_imported_file() {
+    let mut program =
+        Program::from_file(Path::new("src/program/tests/test_imports/diamond.extension")).unwrap();
+    program.type_check().unwrap();
+
+    let names: Vec<String> = program
+        .tenvironment
+        .imports_get()
+        .files()
+        .iter()
+        .map(|file| file.file_name().unwrap().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            "diamond.extension",
+            "diamond_left.extension",
+            "diamond_right.extension",
+            "shared.extension"
+        ]
+    );
+}
@@ -1,2 +1,8 @@
 #[cfg(test)]
-pub mod hime_program_integration;
\ No newline at end of file
+pub mod hime_program_integration;
+#[cfg(test)]
+pub mod syntax_errors;
+#[cfg(test)]
+pub mod spans;
+#[cfg(test)]
+pub mod imports;
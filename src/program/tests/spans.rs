@@ -0,0 +1,61 @@
+use crate::{
+    interpreter::{errors::ArrayOutOfBoundsWithNumbers, InterpretP},
+    program::{errors::LocatedError, program::Program, statement::Stmt},
+    typechecker::{errors::VariableExpressionTypeNotMatch, TypeCheckP},
+};
+
+#[test]
+fn statements_record_their_position() {
+    let code = "begin
+x: int = 2;
+  y: int = x;";
+
+    let program = Program::from_source(&code.to_string(), "test.dap").unwrap();
+
+    let span = &program.stmts[1].span;
+    assert_eq!(span.file.as_ref(), "test.dap");
+    assert_eq!((span.line, span.column), (3, 3));
+    let Stmt::VarDecl { value, .. } = &program.stmts[1].node else {
+        panic!("Expected a variable declaration")
+    };
+    assert_eq!((value.span.line, value.span.column), (3, 12));
+}
+
+#[test]
+fn typecheck_error_reports_position() {
+    let code = "begin
+x: int = 2;
+y: bool = x;";
+
+    let err = Program::from_source(&code.to_string(), "house.dap")
+        .unwrap()
+        .type_check()
+        .unwrap_err();
+
+    let located = err.downcast_ref::<LocatedError>().unwrap();
+    assert_eq!((located.0.line, located.0.column), (3, 1));
+    assert!(located
+        .1
+        .downcast_ref::<VariableExpressionTypeNotMatch>()
+        .is_some());
+    assert!(err.to_string().starts_with("house.dap:3:1: "));
+}
+
+#[test]
+fn runtime_error_reports_innermost_node() {
+    let code = "begin
+a: int[] = [1, 2];
+b: int = 1 + a[5];";
+
+    let err = Program::from_source(&code.to_string(), "house.dap")
+        .unwrap()
+        .interpret()
+        .unwrap_err();
+
+    let located = err.downcast_ref::<LocatedError>().unwrap();
+    assert_eq!((located.0.line, located.0.column), (3, 14));
+    assert!(located
+        .1
+        .downcast_ref::<ArrayOutOfBoundsWithNumbers>()
+        .is_some());
+}
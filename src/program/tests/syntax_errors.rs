@@ -0,0 +1,65 @@
+use crate::program::{errors::SyntaxErrors, program::Program};
+
+fn syntax_errors(code: &str) -> SyntaxErrors {
+    let err = Program::from_source(&code.to_string(), "test.dap").unwrap_err();
+    err.downcast_ref::<SyntaxErrors>()
+        .expect("Expected syntax errors")
+        .clone()
+}
+
+#[test]
+fn reports_position_and_expected_terminals() {
+    let code = "begin
+    x: int = 2 +;
+    return 0;";
+
+    let errors = syntax_errors(code);
+
+    assert_eq!(errors.0.len(), 1);
+    let error = &errors.0[0];
+    assert_eq!(error.0, "test.dap");
+    assert_eq!((error.1, error.2), (2, 17));
+    assert!(error.3.contains("expected: INTEGER"));
+    assert!(error
+        .to_string()
+        .starts_with("test.dap:2:17: Unexpected token \";\""));
+}
+
+#[test]
+fn caret_under_offending_token() {
+    let code = "begin
+    x: int = (1, 2;
+    return 0;";
+
+    let errors = syntax_errors(code);
+
+    assert_eq!(errors.0[0].4, "x: int = (1, 2;");
+    assert_eq!(errors.0[0].5, "              ^");
+}
+
+#[test]
+fn reports_all_errors() {
+    let code = "x: int;
+begin
+    y: int = 2 +;
+    z: int = (1, 2;
+    w: int = 1 $ 2;
+    return 0;";
+
+    let errors = syntax_errors(code);
+
+    let positions: Vec<(usize, usize)> = errors.0.iter().map(|err| (err.1, err.2)).collect();
+    assert_eq!(positions, vec![(3, 17), (4, 19), (5, 16)]);
+    assert!(errors.to_string().starts_with(
+        "Could not parse the program source code, found 3 syntax error(s):"
+    ));
+}
+
+#[test]
+fn valid_program_has_no_errors() {
+    let code = "begin
+    x: int = 2 + 2;
+    return 0;";
+
+    assert!(Program::from_source(&code.to_string(), "test.dap").is_ok());
+}
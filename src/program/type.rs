@@ -1,4 +1,4 @@
-use std::error::Error;
+use std::{error::Error, fmt};
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Type {
@@ -10,6 +10,7 @@ pub enum Type {
     Color,
     Path,
     Polygon,
+    String,
     IntArray,
     BoolArray,
     FloatArray,
@@ -18,6 +19,8 @@ pub enum Type {
     ColorArray,
     PathArray,
     PolygonArray,
+    StringArray,
+    Transform,
     Empty,
 }
 
@@ -32,6 +35,7 @@ impl Type {
             "color" => Self::Color,
             "path" => Self::Path,
             "polygon" => Self::Polygon,
+            "string" => Self::String,
             "int[]" => Self::IntArray,
             "bool[]" => Self::BoolArray,
             "float[]" => Self::FloatArray,
@@ -40,8 +44,52 @@ impl Type {
             "color[]" => Self::ColorArray,
             "path[]" => Self::PathArray,
             "polygon[]" => Self::PolygonArray,
+            "string[]" => Self::StringArray,
+            "transform" => Self::Transform,
             _ => unreachable!(),
         };
         Ok(r#type)
     }
+
+    /// Numeric promotion, the type of an arithmetic operation or comparison on two numbers.
+    /// An int with an int stays an int, as soon as one side is a float the int side is
+    /// converted to a float. Nothing else is converted implicitly, use `int(x)`, `float(x)`
+    /// or `round(x)` instead. `None` if one of the sides is not a number.
+    /// The interpreter follows the same rule in `Value::promote`.
+    pub fn promote(self, other: Type) -> Option<Type> {
+        match (self, other) {
+            (Type::Int, Type::Int) => Some(Type::Int),
+            (Type::Int | Type::Float, Type::Int | Type::Float) => Some(Type::Float),
+            _ => None,
+        }
+    }
+}
+
+/// Shows the type the way it is written in DAP source code
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Int => "int",
+            Self::Bool => "bool",
+            Self::Float => "float",
+            Self::Shape => "shape",
+            Self::Point => "point",
+            Self::Color => "color",
+            Self::Path => "path",
+            Self::Polygon => "polygon",
+            Self::String => "string",
+            Self::IntArray => "int[]",
+            Self::BoolArray => "bool[]",
+            Self::FloatArray => "float[]",
+            Self::ShapeArray => "shape[]",
+            Self::PointArray => "point[]",
+            Self::ColorArray => "color[]",
+            Self::PathArray => "path[]",
+            Self::PolygonArray => "polygon[]",
+            Self::StringArray => "string[]",
+            Self::Transform => "transform",
+            Self::Empty => "empty",
+        };
+        write!(f, "{}", name)
+    }
 }
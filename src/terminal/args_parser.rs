@@ -1,20 +1,30 @@
-use std::env::Args;
-
 use crate::{
-    interpreter::InterpretE,
-    program::{program::Program, statement::Stmt},
+    interpreter::{value::Value, InterpretE, InterpretS},
+    program::{program::Program, r#type::Type, statement::Stmt},
     typechecker::TypeCheckE,
 };
 
+/// A top level declaration, which can be given a value on the command line with `-name value`
+#[derive(Debug, Clone)]
+pub struct Param {
+    pub name: String,
+    pub declared_type: Type,
+    /// False if the declaration has a default value
+    pub required: bool,
+    /// The default value, `None` if it is required or depends on a required parameter
+    pub default: Option<Value>,
+}
+
 impl Program {
-    pub fn parse_terminal_args(&mut self, mut args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    /// Sets parameters from `(name, value)` pairs, where the value is DAP source code.
+    /// The program must be typechecked first, so the declared types are known.
+    pub fn parse_terminal_args(
+        &mut self,
+        args: impl Iterator<Item = (String, String)>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
         let mut checkprogramargs = String::from("begin\n");
-        while let (Some(mut arg_name), Some(arg_value)) = (args.next(), args.next()) {
-            if arg_name.starts_with("-") {
-                // only parse if on correct form
-                arg_name = arg_name.replacen("-", "", 1); // Remove -
-                checkprogramargs.extend(format!("{} = {};\n", arg_name, arg_value).chars());
-            }
+        for (arg_name, arg_value) in args {
+            checkprogramargs.extend(format!("{} = {};\n", arg_name, arg_value).chars());
         }
         checkprogramargs.push_str("return 0;");
 
@@ -26,7 +36,7 @@ impl Program {
             let Stmt::Assign {
                 name: identifier,
                 value: expr,
-            } = stmt
+            } = &stmt.node
             else {
                 unreachable!()
             };
@@ -68,3 +78,33 @@ impl Program {
         Ok(())
     }
 }
+
+impl Program {
+    /// Lists the top level declarations with their default values.
+    /// The program must be typechecked first, so its imports are loaded.
+    pub fn params(&mut self) -> Vec<Param> {
+        let mut params = Vec::new();
+        for stmt in &self.decl_f {
+            match &stmt.node {
+                Stmt::Decl {
+                    name,
+                    declared_type,
+                    value,
+                } => params.push(Param {
+                    name: name.clone(),
+                    declared_type: *declared_type,
+                    required: value.is_none(),
+                    // Defaults can use earlier declarations, so they are evaluated in order
+                    default: stmt
+                        .interpret(&mut self.ienvironment)
+                        .ok()
+                        .and_then(|_| self.ienvironment.vtable_find(name.clone()).cloned()),
+                }),
+                _ => {
+                    let _ = stmt.interpret(&mut self.ienvironment);
+                }
+            }
+        }
+        params
+    }
+}
@@ -0,0 +1,204 @@
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+};
+
+pub const USAGE: &str = "Usage: codebase <command> [input] [options] [-parameter value]...
+       codebase <input.dap> [svg,png] [options] [-parameter value]...   same as build --format svg,png
+
+Commands:
+  build <input>          typecheck and run the input, then generate output files
+  check <input>          only typecheck the input
+  run <input>            typecheck and run the input without generating output files
+  list-params <input>    list the parameters the input accepts
+  repl                   start an interactive session
+  help                   show this message
+
+Options:
+  --format <svg,png>     output formats, svg if not given
+  --out-dir <dir>        directory to write output files to, next to the input if not given
+  --output <file>        output file, its extension is used as format if --format is not given
+  -I <dir>               also look for imported files in dir, can be given more than once
+  --max-iterations <n>   stop while loops after n iterations
+  --tolerance <t>        furthest curves may move when flattened by union, intersect, subtract
+                         and xor, 0.1 if not given
+  --watch                rebuild whenever the input or a file it imports changes
+  --quiet                only print errors
+  -h, --help             show this message
+
+Parameters:
+  -name value            set a top level declaration, e.g. -width 10 or -fill \"(255,0,0,255)\"";
+
+/// What the command line asked to do
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Command {
+    Build,
+    Check,
+    Run,
+    ListParams,
+    Repl,
+    Help,
+}
+
+/// Parsed command line
+#[derive(Debug, Clone)]
+pub struct Cli {
+    pub command: Command,
+    /// The file to work on, empty for commands that don't take one
+    pub input: PathBuf,
+    pub formats: Vec<String>,
+    pub out_dir: Option<PathBuf>,
+    pub output: Option<PathBuf>,
+    pub import_dirs: Vec<PathBuf>,
+    pub iteration_limit: Option<u64>,
+    pub flatten_tolerance: Option<f64>,
+    pub watch: bool,
+    pub quiet: bool,
+    /// Values given with `-name value`, without the `-`
+    pub params: Vec<(String, String)>,
+}
+
+impl Cli {
+    /// Parses the arguments after the executable name.
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, Box<dyn Error>> {
+        let mut input = None;
+        let command = match args.next().as_deref() {
+            Some("build") => Command::Build,
+            Some("check") => Command::Check,
+            Some("run") => Command::Run,
+            Some("list-params") => Command::ListParams,
+            Some("repl") => Command::Repl,
+            Some("help" | "--help" | "-h") => Command::Help,
+            //The form from before there were commands, `codebase file.dap svg,png`
+            Some(file) if Self::is_dap_file(file) => {
+                input = Some(PathBuf::from(file));
+                Command::Build
+            }
+            Some(command) => {
+                return Err(format!("Unknown command: {}\n\n{}", command, USAGE).into())
+            }
+            None => return Err(USAGE.into()),
+        };
+
+        let mut cli = Cli {
+            command,
+            input: PathBuf::new(),
+            formats: Vec::new(),
+            out_dir: None,
+            output: None,
+            import_dirs: Vec::new(),
+            iteration_limit: None,
+            flatten_tolerance: None,
+            watch: false,
+            quiet: false,
+            params: Vec::new(),
+        };
+        //Without a command the formats can follow the input, like `--format`
+        let mut positional_formats = input.is_some();
+
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| format!("Missing value after {}", name))
+            };
+            match arg.as_str() {
+                "-h" | "--help" => cli.command = Command::Help,
+                "--format" => cli.formats = Self::formats(&value(&arg)?),
+                "--out-dir" => cli.out_dir = Some(value(&arg)?.into()),
+                "--output" => cli.output = Some(value(&arg)?.into()),
+                "-I" => cli.import_dirs.push(value(&arg)?.into()),
+                "--max-iterations" => {
+                    let limit = value(&arg)?;
+                    cli.iteration_limit = Some(
+                        limit
+                            .parse::<u64>()
+                            .map_err(|_| format!("Invalid number for --max-iterations: {}", limit))?,
+                    );
+                }
+                "--tolerance" => {
+                    let tolerance = value(&arg)?;
+                    cli.flatten_tolerance = Some(
+                        tolerance
+                            .parse::<f64>()
+                            .ok()
+                            .filter(|tolerance| tolerance.is_finite() && *tolerance > 0.)
+                            .ok_or_else(|| format!("Invalid number for --tolerance: {}", tolerance))?,
+                    );
+                }
+                "--watch" => cli.watch = true,
+                "--quiet" => cli.quiet = true,
+                flag if flag.starts_with("--") => {
+                    return Err(format!("Unknown option: {}", flag).into())
+                }
+                param if param.starts_with('-') => {
+                    let name = &param[1..];
+                    if !Self::is_identifier(name) {
+                        return Err(format!("Invalid parameter name: {}", param).into());
+                    }
+                    cli.params.push((name.to_string(), value(param)?));
+                }
+                _ if input.is_none() => input = Some(PathBuf::from(arg)),
+                _ if positional_formats => {
+                    cli.formats = Self::formats(&arg);
+                    positional_formats = false;
+                }
+                _ => return Err(format!("Unexpected argument: {}", arg).into()),
+            }
+        }
+
+        match (cli.command, input) {
+            (Command::Help, _) => (),
+            (Command::Repl, None) => (),
+            (Command::Repl, Some(input)) => {
+                return Err(format!("Unexpected argument: {}", input.display()).into())
+            }
+            (_, Some(input)) => cli.input = input,
+            (_, None) => return Err(format!("Missing input file\n\n{}", USAGE).into()),
+        }
+        if cli.formats.is_empty() {
+            let from_output = cli
+                .output
+                .as_ref()
+                .and_then(|output| output.extension())
+                .map(|extension| extension.to_string_lossy().into_owned());
+            cli.formats = vec![from_output.unwrap_or_else(|| "svg".into())];
+        }
+        Ok(cli)
+    }
+
+    /// Path of the output files without extension, each generator adds its own.
+    pub fn output_stem(&self) -> PathBuf {
+        let file = match &self.output {
+            Some(output) => output.with_extension(""),
+            None => self.input.with_extension(""),
+        };
+        match &self.out_dir {
+            Some(dir) => match &self.output {
+                Some(_) => dir.join(file),
+                None => dir.join(file.file_name().unwrap_or_default()),
+            },
+            None => file,
+        }
+    }
+
+    fn formats(list: &str) -> Vec<String> {
+        list.split(',')
+            .map(|format| format.trim().to_string())
+            .filter(|format| !format.is_empty())
+            .collect()
+    }
+
+    fn is_dap_file(arg: &str) -> bool {
+        Path::new(arg)
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("dap"))
+    }
+
+    fn is_identifier(name: &str) -> bool {
+        let mut chars = name.chars();
+        chars
+            .next()
+            .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+            && chars.all(|char| char.is_ascii_alphanumeric() || char == '_')
+    }
+}
@@ -1 +1,7 @@
-pub mod args_parser;
\ No newline at end of file
+pub mod args_parser;
+pub mod cli;
+pub mod repl;
+pub mod status;
+pub mod watch;
+
+mod tests;
@@ -0,0 +1,265 @@
+use std::{
+    error::Error,
+    io::{self, BufRead, Write},
+    path::Path,
+};
+
+use crate::{
+    generators::{generator::Generator, svggenerator::SvgGenerator},
+    interpreter::{environment::IEnvironment, InterpretE, InterpretS},
+    program::{
+        errors::{LocatedError, SyntaxError, SyntaxErrors},
+        expression::Expr,
+        imports::ImportResolver,
+        program::Program,
+        span::Spanned,
+        statement::Stmt,
+    },
+    typechecker::{environment::TEnvironment, TypeCheckE, TypeCheckS},
+};
+
+/// File name used when reporting errors in typed input
+const SOURCE_NAME: &str = "<repl>";
+
+/// What is put before the input to parse it as statements or as an expression
+const STMTS_PREFIX: &str = "begin\n";
+const EXPR_PREFIX: &str = "begin\nreturn ";
+
+const HELP: &str = "Enter declarations, statements or expressions. Commands:
+  :load file.dap   run a program in the current session
+  :type expr       show the type of an expression without evaluating it
+  :svg out.svg     write everything drawn so far to an SVG file
+  :help            show this message
+  :quit            leave the REPL";
+
+/// What a line of input was parsed as. DAP only parses whole programs,
+/// so the input is wrapped in a program for each kind until one of them parses.
+/// Positions in errors are moved back from the wrapped program to the input by `relocate`.
+enum Input {
+    Decls(Vec<Spanned<Stmt>>),
+    Stmts(Vec<Spanned<Stmt>>),
+    Expr(Spanned<Expr>),
+}
+
+/// Interactive session, everything declared stays alive between inputs.
+pub struct Repl {
+    tenvironment: TEnvironment,
+    ienvironment: IEnvironment,
+}
+
+impl Default for Repl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Repl {
+    pub fn new() -> Self {
+        Repl {
+            tenvironment: TEnvironment::new(),
+            ienvironment: IEnvironment::new(),
+        }
+    }
+
+    pub fn run(&mut self) -> Result<(), Box<dyn Error>> {
+        println!("DAP REPL, type :help for commands");
+        let stdin = io::stdin();
+        let mut lines = stdin.lock().lines();
+
+        while let Some(input) = Self::read_input(&mut lines)? {
+            if matches!(input.trim(), ":quit" | ":q") {
+                break;
+            }
+            match self.eval(&input) {
+                Ok(output) if output.is_empty() => (),
+                Ok(output) => println!("{}", output),
+                Err(err) => eprintln!("Error: {}", err),
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads one input, continuing on the next lines while a `{` is left open.
+    fn read_input(lines: &mut impl Iterator<Item = io::Result<String>>) -> io::Result<Option<String>> {
+        let mut input = String::new();
+        loop {
+            print!("{}", if input.is_empty() { "dap> " } else { "...> " });
+            io::stdout().flush()?;
+
+            let Some(line) = lines.next() else {
+                return Ok(Some(input).filter(|input| !input.is_empty()));
+            };
+            input.push_str(&line?);
+            input.push('\n');
+            if input.matches('{').count() <= input.matches('}').count() {
+                return Ok(Some(input));
+            }
+        }
+    }
+
+    /// Evaluates one input and returns what should be printed.
+    /// If anything fails, the session is left as it was before the input.
+    pub fn eval(&mut self, input: &str) -> Result<String, Box<dyn Error>> {
+        let snapshot = (self.tenvironment.clone(), self.ienvironment.clone());
+
+        let result = self.eval_input(input.trim());
+        if result.is_err() {
+            (self.tenvironment, self.ienvironment) = snapshot;
+        }
+        self.ienvironment.rvalue_clear();
+        result
+    }
+
+    fn eval_input(&mut self, input: &str) -> Result<String, Box<dyn Error>> {
+        if let Some(command) = input.strip_prefix(':') {
+            return self.command(command.trim());
+        }
+        if input.is_empty() {
+            return Ok(String::new());
+        }
+
+        match Self::parse(input)? {
+            Input::Expr(expr) => {
+                let result = expr.type_check(&mut self.tenvironment).and_then(|r#type| {
+                    let value = expr.interpret(&mut self.ienvironment)?;
+                    Ok(format!("{} : {}", value, r#type))
+                });
+                result.map_err(|err| relocate(err, EXPR_PREFIX, input))
+            }
+            Input::Decls(stmts) => self.execute(&stmts).map(|_| String::new()),
+            Input::Stmts(stmts) => self
+                .execute(&stmts)
+                .map(|_| String::new())
+                .map_err(|err| relocate(err, STMTS_PREFIX, input)),
+        }
+    }
+
+    fn command(&mut self, command: &str) -> Result<String, Box<dyn Error>> {
+        let (name, argument) = command.split_once(' ').unwrap_or((command, ""));
+        let argument = argument.trim();
+
+        match name {
+            "load" => {
+                let mut program = Program::from_file(Path::new(argument))?;
+
+                //Imports in the loaded file are relative to it
+                let imports = self.tenvironment.imports_get().clone();
+                self.set_imports(program.tenvironment.imports_get().clone());
+                program.decl_f.append(&mut program.stmts);
+                let result = self.execute(&program.decl_f);
+                self.set_imports(imports);
+
+                result.map(|_| format!("Loaded {}", argument))
+            }
+            "type" => {
+                let expr = Self::parse_expr(argument)?;
+                let r#type = expr
+                    .type_check(&mut self.tenvironment)
+                    .map_err(|err| relocate(err, EXPR_PREFIX, argument))?;
+                Ok(r#type.to_string())
+            }
+            "svg" => {
+                let file_name = argument.strip_suffix(".svg").unwrap_or(argument);
+                if file_name.is_empty() {
+                    return Err("Usage: :svg out.svg".into());
+                }
+                SvgGenerator::new()
+                    .generate(self.ienvironment.darray_get().clone(), file_name.into())?;
+                Ok(format!("Wrote {}.svg", file_name))
+            }
+            "help" => Ok(HELP.into()),
+            _ => Err(format!("Unknown command :{}, type :help for commands", name).into()),
+        }
+    }
+
+    /// Typechecks all statements before any of them run, the same way a program does.
+    fn execute(&mut self, stmts: &[Spanned<Stmt>]) -> Result<(), Box<dyn Error>> {
+        for stmt in stmts {
+            stmt.type_check(&mut self.tenvironment)?;
+        }
+        for stmt in stmts {
+            stmt.interpret(&mut self.ienvironment)?;
+        }
+        Ok(())
+    }
+
+    fn set_imports(&mut self, imports: ImportResolver) {
+        self.tenvironment.imports_set(imports.clone());
+        self.ienvironment.imports_set(imports);
+    }
+
+    fn parse(input: &str) -> Result<Input, Box<dyn Error>> {
+        let stmts = Program::from_source(&format!("{}{}", STMTS_PREFIX, input), SOURCE_NAME);
+        let stmts = match stmts {
+            Ok(program) => return Ok(Input::Stmts(program.stmts)),
+            Err(err) => relocate(err, STMTS_PREFIX, input),
+        };
+        let expr = match Self::parse_expr(input.trim_end_matches(';')) {
+            Ok(expr) => return Ok(Input::Expr(expr)),
+            Err(err) => err,
+        };
+        //A statement is needed after begin, it is never run
+        if let Ok(program) = Program::from_source(&format!("{}\nbegin\nreturn 0;", input), SOURCE_NAME) {
+            if !program.decl_f.is_empty() {
+                return Ok(Input::Decls(program.decl_f));
+            }
+        }
+
+        //Report the error for what the input most likely was meant to be
+        if input.ends_with(';') || input.ends_with('}') {
+            Err(stmts)
+        } else {
+            Err(expr)
+        }
+    }
+
+    fn parse_expr(input: &str) -> Result<Spanned<Expr>, Box<dyn Error>> {
+        let mut program = Program::from_source(&format!("{}{};", EXPR_PREFIX, input), SOURCE_NAME)
+            .map_err(|err| relocate(err, EXPR_PREFIX, input))?;
+        match program.stmts.pop() {
+            Some(Spanned { node: Stmt::Return(expr), .. }) if program.stmts.is_empty() => Ok(expr),
+            _ => Err(format!("Not an expression: {}", input).into()),
+        }
+    }
+}
+
+/// Moves the positions in an error from the program the input was wrapped in
+/// with `prefix` back to the input itself.
+fn relocate(err: Box<dyn Error>, prefix: &str, input: &str) -> Box<dyn Error> {
+    let lines = prefix.matches('\n').count();
+    let columns = prefix.len() - prefix.rfind('\n').map_or(0, |idx| idx + 1);
+    let position = |line: usize, column: usize| {
+        let skipped = if line == lines + 1 { columns } else { 0 };
+        let line = line.saturating_sub(lines).max(1);
+        (line, column.saturating_sub(skipped).max(1))
+    };
+
+    let err = match err.downcast::<SyntaxErrors>() {
+        Ok(errors) => {
+            let errors = errors.0.into_iter().map(|err| {
+                let SyntaxError(file, line, column, msg, text, caret) = err;
+                if line <= lines {
+                    return SyntaxError(file, line, column, msg, text, caret);
+                }
+                //The caret is aligned to the line without its leading white space
+                let skipped = if line == lines + 1 { columns } else { 0 };
+                let caret = caret.chars().skip(skipped).collect();
+                let (line, column) = position(line, column);
+                let text = input.lines().nth(line - 1).unwrap_or_default().trim_start();
+                SyntaxError(file, line, column, msg, text.into(), caret)
+            });
+            return SyntaxErrors(errors.collect()).into();
+        }
+        Err(err) => err,
+    };
+    match err.downcast::<LocatedError>() {
+        Ok(located) => {
+            let LocatedError(mut span, inner) = *located;
+            if &*span.file == SOURCE_NAME {
+                (span.line, span.column) = position(span.line, span.column);
+            }
+            LocatedError(span, inner).into()
+        }
+        Err(err) => err,
+    }
+}
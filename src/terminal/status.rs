@@ -0,0 +1,19 @@
+use std::{
+    fmt::Display,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Turns the `[Typechecker]`/`[Interpreter]` progress lines off, e.g. for `--quiet`.
+/// Errors are always reported.
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Prints a progress line unless quiet mode is on.
+pub fn print(line: impl Display) {
+    if !QUIET.load(Ordering::Relaxed) {
+        println!("{}", line);
+    }
+}
@@ -0,0 +1,63 @@
+use std::path::Path;
+
+use crate::{program::program::Program, program::r#type::Type, typechecker::TypeCheckP};
+
+#[test]
+fn params_lists_declarations_with_defaults() {
+    let mut program = Program::from_file(Path::new("dap-lib/basic-shapes/square.dap")).unwrap();
+    program.type_check().unwrap();
+
+    let params = program.params();
+
+    let described: Vec<(String, Type, bool, Option<String>)> = params
+        .into_iter()
+        .map(|param| {
+            (
+                param.name,
+                param.declared_type,
+                param.required,
+                param.default.map(|value| value.to_string()),
+            )
+        })
+        .collect();
+    assert_eq!(
+        described,
+        vec![
+            ("size".into(), Type::Int, true, None),
+            ("fill".into(), Type::Color, false, Some("(0, 0, 0, 255)".into())),
+            ("stroke".into(), Type::Color, false, Some("(0, 0, 0, 255)".into())),
+            ("thickness".into(), Type::Int, false, Some("1".into())),
+        ]
+    );
+}
+
+#[test]
+fn terminal_args_set_declarations() {
+    let code = "size: int;
+begin
+return size;";
+    let mut program = Program::new(&code.to_string()).unwrap();
+    program.type_check().unwrap();
+
+    program
+        .parse_terminal_args([("size".to_string(), "3".to_string())].into_iter())
+        .unwrap();
+
+    assert_eq!(program.params()[0].default.as_ref().unwrap().to_string(), "3");
+}
+
+#[test]
+fn listed_float_defaults_are_valid_arguments() {
+    let code = "arrowSize: float = 7.0;
+begin
+return 0;";
+    let mut program = Program::new(&code.to_string()).unwrap();
+    program.type_check().unwrap();
+
+    //The listed default can be passed back as the value of the parameter
+    let listed = program.params()[0].default.as_ref().unwrap().to_string();
+    assert_eq!(listed, "7.0");
+    program
+        .parse_terminal_args([("arrowSize".to_string(), listed)].into_iter())
+        .unwrap();
+}
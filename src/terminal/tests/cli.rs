@@ -0,0 +1,102 @@
+use std::path::PathBuf;
+
+use crate::terminal::cli::{Cli, Command};
+
+fn parse(args: &[&str]) -> Result<Cli, Box<dyn std::error::Error>> {
+    Cli::parse(args.iter().map(|arg| arg.to_string()))
+}
+
+#[test]
+fn build_with_options_and_params() {
+    let cli = parse(&[
+        "build", "house.dap", "-width", "10", "--format", "svg,png", "--quiet", "-I", "lib",
+        "-fill", "(1,2,3,255)",
+    ])
+    .unwrap();
+
+    assert_eq!(cli.command, Command::Build);
+    assert_eq!(cli.input, PathBuf::from("house.dap"));
+    assert_eq!(cli.formats, vec!["svg", "png"]);
+    assert!(cli.quiet);
+    assert_eq!(cli.import_dirs, vec![PathBuf::from("lib")]);
+    assert_eq!(
+        cli.params,
+        vec![
+            ("width".to_string(), "10".to_string()),
+            ("fill".to_string(), "(1,2,3,255)".to_string())
+        ]
+    );
+}
+
+#[test]
+fn input_without_command_is_a_build() {
+    let cli = parse(&["house.DAP", "svg,png", "-width", "10"]).unwrap();
+
+    assert_eq!(cli.command, Command::Build);
+    assert_eq!(cli.input, PathBuf::from("house.DAP"));
+    assert_eq!(cli.formats, vec!["svg", "png"]);
+    assert_eq!(cli.params, vec![("width".to_string(), "10".to_string())]);
+
+    assert_eq!(parse(&["house.dap"]).unwrap().formats, vec!["svg"]);
+    assert!(parse(&["house.dap", "svg", "png"]).is_err());
+    assert!(parse(&["house.svg"]).is_err());
+}
+
+#[test]
+fn parameter_without_value_is_an_error() {
+    let err = parse(&["build", "house.dap", "-width"]).unwrap_err();
+
+    assert_eq!(err.to_string(), "Missing value after -width");
+}
+
+#[test]
+fn stray_argument_is_an_error() {
+    let err = parse(&["check", "house.dap", "10"]).unwrap_err();
+
+    assert_eq!(err.to_string(), "Unexpected argument: 10");
+}
+
+#[test]
+fn unknown_option_is_an_error() {
+    assert!(parse(&["run", "house.dap", "--colour"]).is_err());
+}
+
+#[test]
+fn help_flag_overrides_command() {
+    let cli = parse(&["build", "--help"]).unwrap();
+
+    assert_eq!(cli.command, Command::Help);
+}
+
+#[test]
+fn output_is_written_next_to_input_by_default() {
+    let cli = parse(&["build", "maps/denmark.dap"]).unwrap();
+
+    assert_eq!(cli.formats, vec!["svg"]);
+    assert_eq!(cli.output_stem(), PathBuf::from("maps/denmark"));
+}
+
+#[test]
+fn out_dir_replaces_input_directory() {
+    let cli = parse(&["build", "maps/denmark.dap", "--out-dir", "out"]).unwrap();
+
+    assert_eq!(cli.output_stem(), PathBuf::from("out/denmark"));
+}
+
+#[test]
+fn output_extension_sets_format() {
+    let cli = parse(&["build", "maps/denmark.dap", "--output", "map.png", "--out-dir", "out"])
+        .unwrap();
+
+    assert_eq!(cli.formats, vec!["png"]);
+    assert_eq!(cli.output_stem(), PathBuf::from("out/map"));
+}
+
+#[test]
+fn tolerance_must_be_positive() {
+    let cli = parse(&["build", "house.dap", "--tolerance", "0.01"]).unwrap();
+    assert_eq!(cli.flatten_tolerance, Some(0.01));
+
+    let err = parse(&["build", "house.dap", "--tolerance", "0"]).unwrap_err();
+    assert_eq!(err.to_string(), "Invalid number for --tolerance: 0");
+}
@@ -0,0 +1,8 @@
+#[cfg(test)]
+mod args_parser;
+#[cfg(test)]
+mod cli;
+#[cfg(test)]
+mod repl;
+#[cfg(test)]
+mod watch;
@@ -0,0 +1,95 @@
+use std::fs;
+
+use crate::terminal::repl::Repl;
+
+#[test]
+fn variables_persist_between_inputs() {
+    let mut repl = Repl::new();
+
+    assert_eq!(repl.eval("x: int = 2;").unwrap(), "");
+    assert_eq!(repl.eval("x = x * 3;").unwrap(), "");
+
+    assert_eq!(repl.eval("x + 1").unwrap(), "7 : int");
+}
+
+#[test]
+fn functions_can_be_called_in_later_inputs() {
+    let mut repl = Repl::new();
+
+    repl.eval("double(n: int): int -> {\nreturn n * 2;\n}")
+        .unwrap();
+
+    assert_eq!(repl.eval("double(4);").unwrap(), "8 : int");
+}
+
+#[test]
+fn shape_expression_prints_bounds() {
+    let mut repl = Repl::new();
+
+    let output = repl.eval("(0,0)--(4,0)--(4,2)--*(||)").unwrap();
+
+    assert_eq!(
+        output,
+        "shape of 1 figure(s), width: 4, height: 2, top left: (0, 2) : shape"
+    );
+}
+
+#[test]
+fn type_command_does_not_evaluate() {
+    let mut repl = Repl::new();
+    repl.eval("a: int[] = [1, 2];").unwrap();
+
+    assert_eq!(repl.eval(":type a[5]").unwrap(), "int");
+    assert_eq!(repl.eval(":type a").unwrap(), "int[]");
+}
+
+#[test]
+fn failed_input_leaves_session_unchanged() {
+    let mut repl = Repl::new();
+    repl.eval("x: int = 1;").unwrap();
+
+    assert!(repl.eval("y: int = 2;\nx = true;").is_err());
+
+    assert!(repl.eval("y").is_err());
+    assert_eq!(repl.eval("x").unwrap(), "1 : int");
+}
+
+#[test]
+fn load_runs_file_in_session() {
+    let mut repl = Repl::new();
+
+    repl.eval(":load dap-lib/basic-shapes/circle.dap").unwrap();
+
+    assert_eq!(repl.eval("radius").unwrap(), "1 : int");
+}
+
+#[test]
+fn svg_command_writes_draw_array() {
+    let mut repl = Repl::new();
+    repl.eval("draw (0,0)--(1,1)(||);").unwrap();
+
+    assert_eq!(repl.eval(":svg replTest.svg").unwrap(), "Wrote replTest.svg");
+
+    let svg = fs::read_to_string("replTest.svg").expect("Failed to read the generated SVG file");
+    fs::remove_file("replTest.svg").expect("Failed to delete the generated SVG file");
+    assert!(svg.contains("<path"));
+}
+
+#[test]
+fn errors_point_into_the_typed_input() {
+    let mut repl = Repl::new();
+
+    let err = repl.eval("1 + ").unwrap_err().to_string();
+    assert!(err.contains("<repl>:1:"), "{}", err);
+    assert!(err.contains("\n    1 +"), "{}", err);
+    assert!(!err.contains("return"), "{}", err);
+
+    let err = repl.eval("x: int = 1;\ny: int = true;").unwrap_err().to_string();
+    assert!(err.starts_with("<repl>:2:1:"), "{}", err);
+
+    let err = repl.eval("1 + true").unwrap_err().to_string();
+    assert!(err.starts_with("<repl>:1:1:"), "{}", err);
+
+    let err = repl.eval(":type 2 * false").unwrap_err().to_string();
+    assert!(err.starts_with("<repl>:1:1:"), "{}", err);
+}
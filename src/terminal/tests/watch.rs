@@ -0,0 +1,66 @@
+use std::{
+    env, fs,
+    time::{Duration, SystemTime},
+};
+
+use crate::terminal::watch::Watcher;
+
+#[test]
+fn unchanged_files_are_not_reported() {
+    let watcher = Watcher::new(vec!["dap-lib/basic-shapes/square.dap".into()]);
+
+    assert!(!watcher.changed());
+}
+
+#[test]
+fn modified_file_is_reported() {
+    let file = env::temp_dir().join("dap_watch_modified.dap");
+    fs::write(&file, "begin\nreturn 0;").unwrap();
+    let watcher = Watcher::new(vec![file.clone()]);
+
+    fs::File::options()
+        .write(true)
+        .open(&file)
+        .unwrap()
+        .set_modified(SystemTime::now() + Duration::from_secs(10))
+        .unwrap();
+
+    let changed = watcher.changed();
+    fs::remove_file(&file).unwrap();
+    assert!(changed);
+}
+
+#[test]
+fn created_file_is_reported() {
+    let file = env::temp_dir().join("dap_watch_created.dap");
+    let _ = fs::remove_file(&file);
+    let watcher = Watcher::new(vec![file.clone()]);
+
+    fs::write(&file, "begin\nreturn 0;").unwrap();
+
+    let changed = watcher.changed();
+    fs::remove_file(&file).unwrap();
+    assert!(changed);
+}
+
+#[test]
+fn file_saved_during_build_is_reported() {
+    let file = env::temp_dir().join("dap_watch_during_build.dap");
+    fs::write(&file, "begin\nreturn 0;").unwrap();
+    let started = SystemTime::now();
+
+    //Saved after the build started but before the watcher was made
+    fs::File::options()
+        .write(true)
+        .open(&file)
+        .unwrap()
+        .set_modified(started + Duration::from_secs(1))
+        .unwrap();
+    let watcher = Watcher::new(vec![file.clone()]);
+
+    let modified = watcher.modified_since(started);
+    let unmodified = watcher.modified_since(started + Duration::from_secs(2));
+    let changed = watcher.changed();
+    fs::remove_file(&file).unwrap();
+    assert!(modified && !unmodified && !changed);
+}
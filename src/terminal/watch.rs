@@ -0,0 +1,57 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+/// How often watched files are checked for changes.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// What a file looked like when it was last seen, `None` if it could not be read
+type Stamp = Option<(SystemTime, u64)>;
+
+/// Notices changes to a set of files by polling their modification time and size,
+/// so it works without any platform specific notification API.
+pub struct Watcher {
+    files: Vec<(PathBuf, Stamp)>,
+}
+
+impl Watcher {
+    pub fn new(files: Vec<PathBuf>) -> Self {
+        let files = files
+            .into_iter()
+            .map(|file| {
+                let stamp = Self::stamp(&file);
+                (file, stamp)
+            })
+            .collect();
+        Watcher { files }
+    }
+
+    /// True if a file was modified, created or removed since the watcher was made.
+    pub fn changed(&self) -> bool {
+        self.files
+            .iter()
+            .any(|(file, stamp)| Self::stamp(file) != *stamp)
+    }
+
+    /// True if a file was modified at or after `time`, such as while a build that read it was running.
+    pub fn modified_since(&self, time: SystemTime) -> bool {
+        self.files
+            .iter()
+            .any(|(_, stamp)| stamp.is_some_and(|(modified, _)| modified >= time))
+    }
+
+    /// Blocks until one of the files changes.
+    pub fn wait(&self, interval: Duration) {
+        while !self.changed() {
+            thread::sleep(interval);
+        }
+    }
+
+    fn stamp(file: &Path) -> Stamp {
+        let metadata = fs::metadata(file).ok()?;
+        Some((metadata.modified().ok()?, metadata.len()))
+    }
+}
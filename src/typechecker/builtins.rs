@@ -0,0 +1,294 @@
+use std::{collections::HashMap, error::Error};
+
+use crate::program::r#type::Type;
+
+use super::{environment::EType, errors};
+
+/// Builtin math functions with their number of arguments.
+pub const MATH_FUNCTIONS: [(&str, usize); 17] = [
+    ("int", 1),
+    ("float", 1),
+    ("sqrt", 1),
+    ("pow", 2),
+    ("abs", 1),
+    ("min", 2),
+    ("max", 2),
+    ("floor", 1),
+    ("ceil", 1),
+    ("round", 1),
+    ("sin", 1),
+    ("cos", 1),
+    ("tan", 1),
+    ("atan2", 2),
+    ("hypot", 2),
+    ("clamp", 3),
+    ("lerp", 3),
+];
+
+/// Builtin array functions with their number of arguments, they work on arrays of every type.
+pub const ARRAY_FUNCTIONS: [(&str, usize); 10] = [
+    ("push", 2),
+    ("remove", 2),
+    ("insert", 3),
+    ("concat", 2),
+    ("slice", 3),
+    ("reverse", 1),
+    ("contains", 2),
+    ("index_of", 2),
+    ("sort", 1),
+    ("fill", 2),
+];
+
+/// Builtin functions making a `transform` with their number of arguments, they are combined with `*`.
+/// `rotate` and `scale` are already manipulations, so the functions are named after the transform they give.
+pub const TRANSFORM_FUNCTIONS: [(&str, usize); 4] = [
+    ("translation", 1),
+    ("rotation", 1),
+    ("scaling", 1),
+    ("skew", 2),
+];
+
+/// Builtin functions combining a `shape[]` into one `shape`, with their number of arguments.
+pub const LAYOUT_FUNCTIONS: [(&str, usize); 4] =
+    [("row", 3), ("column", 3), ("grid", 3), ("distribute", 2)];
+
+/// Builtin functions measuring shapes and points, with their number of arguments.
+/// `contains` is also an array function, it is one or the other depending on its first argument.
+pub const GEOMETRY_FUNCTIONS: [(&str, usize); 3] =
+    [("contains", 2), ("overlaps", 2), ("distance", 2)];
+
+/// The kinds of builtin functions, each kind is typechecked by its `*_call_type`
+/// and run by the matching `call_*` of the interpreter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Builtin {
+    Math,
+    Array,
+    Transform,
+    Layout,
+    Geometry,
+}
+
+/// Geometry comes before array, so `contains` is looked up as a geometry function
+const BUILTINS: [(Builtin, &[(&str, usize)]); 5] = [
+    (Builtin::Math, &MATH_FUNCTIONS),
+    (Builtin::Geometry, &GEOMETRY_FUNCTIONS),
+    (Builtin::Array, &ARRAY_FUNCTIONS),
+    (Builtin::Transform, &TRANSFORM_FUNCTIONS),
+    (Builtin::Layout, &LAYOUT_FUNCTIONS),
+];
+
+/// Builtin shapes made with a shape call, such as `circle(|radius = 2.0|)`, they need no import.
+/// An imported shape with the same name is used instead.
+pub const PRIMITIVE_SHAPES: [&str; 4] = ["ellipse", "circle", "rect", "regular_polygon"];
+
+/// The kind of builtin function `name` is, `None` if there is no such builtin.
+/// A function declared in the program with the same name is used instead.
+pub fn lookup(name: &str) -> Option<Builtin> {
+    BUILTINS
+        .iter()
+        .find(|(_, functions)| functions.iter().any(|(function, _)| *function == name))
+        .map(|(builtin, _)| *builtin)
+}
+
+impl Builtin {
+    /// Type of a call to the builtin function `name` of this kind
+    pub fn call_type(self, name: &str, args: &[Type]) -> Result<Type, Box<dyn Error>> {
+        match self {
+            Self::Math => math_call_type(name, args),
+            Self::Array => array_call_type(name, args),
+            Self::Transform => transform_call_type(name, args),
+            Self::Layout => layout_call_type(name, args),
+            Self::Geometry => geometry_call_type(name, args),
+        }
+    }
+}
+
+pub fn is_primitive_shape(name: &str) -> bool {
+    PRIMITIVE_SHAPES.contains(&name)
+}
+
+/// Parameters of a builtin shape in the same form as the parameters of an imported shape.
+/// The sizes are required, `corner_radius` and the colors and thickness of a polygon are optional.
+/// An int is accepted for every float size, see the `Expr::SCall` type check.
+pub fn primitive_parameters(name: &str) -> Option<HashMap<String, EType>> {
+    let required: &[(&str, Type)] = match name {
+        "ellipse" => &[("rx", Type::Float), ("ry", Type::Float)],
+        "circle" => &[("radius", Type::Float)],
+        "rect" => &[("width", Type::Float), ("height", Type::Float)],
+        "regular_polygon" => &[("sides", Type::Int), ("radius", Type::Float)],
+        _ => return None,
+    };
+    let mut parameters: HashMap<String, EType> = required
+        .iter()
+        .map(|(parameter, t)| (parameter.to_string(), EType::DeclNonDefault(*t)))
+        .collect();
+    if name == "rect" {
+        parameters.insert("corner_radius".into(), EType::DeclDefault(Type::Float));
+    }
+    parameters.insert("fill".into(), EType::DeclDefault(Type::Color));
+    parameters.insert("stroke".into(), EType::DeclDefault(Type::Color));
+    parameters.insert("thickness".into(), EType::DeclDefault(Type::Int));
+    Some(parameters)
+}
+
+/// Type of a builtin constant, a variable with the same name is used instead.
+pub fn constant_type(name: &str) -> Option<Type> {
+    match name {
+        "PI" | "E" => Some(Type::Float),
+        _ => None,
+    }
+}
+
+/// Every argument can be an int or a float.
+/// `abs`, `min`, `max`, `clamp` and `pow` give an int when all arguments are ints,
+/// `int`, `floor`, `ceil` and `round` always give an int and the rest always give a float.
+pub fn math_call_type(name: &str, args: &[Type]) -> Result<Type, Box<dyn Error>> {
+    check_arity(&MATH_FUNCTIONS, name, args)?;
+    if !args.iter().all(|t| matches!(t, Type::Int | Type::Float)) {
+        return Err(errors::FCallParametersIncompatible(name.to_owned()).into());
+    }
+
+    let all_int = args.iter().all(|t| *t == Type::Int);
+    Ok(match name {
+        "abs" | "min" | "max" | "clamp" | "pow" if all_int => Type::Int,
+        "int" | "floor" | "ceil" | "round" => Type::Int,
+        _ => Type::Float,
+    })
+}
+
+/// Type of a call to a builtin array function.
+/// The empty array `[]` can be used as an array of any type.
+pub fn array_call_type(name: &str, args: &[Type]) -> Result<Type, Box<dyn Error>> {
+    check_arity(&ARRAY_FUNCTIONS, name, args)?;
+    let incompatible = || errors::FCallParametersIncompatible(name.to_owned()).into();
+    let is_array = |t: &Type| *t == Type::Empty || element_type(*t).is_some();
+    // The array type after adding `value` to `array`
+    let with_element = |array: Type, value: Type| match array {
+        Type::Empty => array_type(value),
+        _ => element_type(array)
+            .filter(|element| *element == value)
+            .map(|_| array),
+    };
+
+    match (name, args) {
+        ("push", [array, value]) => {
+            with_element(*array, *value).ok_or_else(|| errors::ErrorInPush(*array, *value).into())
+        }
+        ("remove", [array, index]) => match (element_type(*array), index) {
+            (Some(_), Type::Int) => Ok(*array),
+            _ => Err(errors::ErrorInRemove(*array, *index).into()),
+        },
+        ("insert", [array, Type::Int, value]) => {
+            with_element(*array, *value).ok_or_else(incompatible)
+        }
+        ("concat", [Type::Empty, other] | [other, Type::Empty]) if is_array(other) => Ok(*other),
+        ("concat", [lhs, rhs]) if lhs == rhs && is_array(lhs) => Ok(*lhs),
+        ("slice", [array, Type::Int, Type::Int]) | ("reverse", [array]) if is_array(array) => {
+            Ok(*array)
+        }
+        ("contains" | "index_of", [array, value])
+            if *array == Type::Empty || element_type(*array) == Some(*value) =>
+        {
+            Ok(if name == "contains" {
+                Type::Bool
+            } else {
+                Type::Int
+            })
+        }
+        ("sort", [array @ (Type::IntArray | Type::FloatArray | Type::Empty)]) => Ok(*array),
+        ("fill", [Type::Int, value]) => array_type(*value).ok_or_else(incompatible),
+        _ => Err(incompatible()),
+    }
+}
+
+/// `translation` takes a point, `rotation` an angle in degrees, `scaling` a factor or a point
+/// with a factor for x and y, and `skew` the angles to shear x and y by.
+pub fn transform_call_type(name: &str, args: &[Type]) -> Result<Type, Box<dyn Error>> {
+    check_arity(&TRANSFORM_FUNCTIONS, name, args)?;
+    let is_number = |t: &Type| matches!(t, Type::Int | Type::Float);
+    match (name, args) {
+        ("translation", [Type::Point]) | ("scaling", [Type::Point]) => Ok(Type::Transform),
+        ("rotation" | "scaling", [t]) if is_number(t) => Ok(Type::Transform),
+        ("skew", [t1, t2]) if is_number(t1) && is_number(t2) => Ok(Type::Transform),
+        _ => Err(errors::FCallParametersIncompatible(name.to_owned()).into()),
+    }
+}
+
+/// `row` and `column` take the shapes, the gap between them and the alignment as a string,
+/// `grid` the shapes, the number of columns and the gap, and `distribute` the shapes and the total width.
+pub fn layout_call_type(name: &str, args: &[Type]) -> Result<Type, Box<dyn Error>> {
+    check_arity(&LAYOUT_FUNCTIONS, name, args)?;
+    let is_number = |t: &Type| matches!(t, Type::Int | Type::Float);
+    let is_shapes = |t: &Type| matches!(t, Type::ShapeArray | Type::Empty);
+    match (name, args) {
+        ("row" | "column", [shapes, gap, Type::String]) if is_shapes(shapes) && is_number(gap) => {
+            Ok(Type::Shape)
+        }
+        ("grid", [shapes, Type::Int, gap]) if is_shapes(shapes) && is_number(gap) => {
+            Ok(Type::Shape)
+        }
+        ("distribute", [shapes, width]) if is_shapes(shapes) && is_number(width) => Ok(Type::Shape),
+        _ => Err(errors::FCallParametersIncompatible(name.to_owned()).into()),
+    }
+}
+
+/// `contains` takes a shape and a point, `overlaps` two shapes and `distance` two points.
+/// A `contains` on anything but a shape is the array function.
+pub fn geometry_call_type(name: &str, args: &[Type]) -> Result<Type, Box<dyn Error>> {
+    check_arity(&GEOMETRY_FUNCTIONS, name, args)?;
+    match (name, args) {
+        ("contains", [Type::Shape, Type::Point]) => Ok(Type::Bool),
+        ("contains", _) => array_call_type(name, args),
+        ("overlaps", [Type::Shape, Type::Shape]) => Ok(Type::Bool),
+        ("distance", [Type::Point, Type::Point]) => Ok(Type::Float),
+        _ => Err(errors::FCallParametersIncompatible(name.to_owned()).into()),
+    }
+}
+
+/// Type of the elements of an array type, `None` if it is not an array
+pub fn element_type(array: Type) -> Option<Type> {
+    match array {
+        Type::IntArray => Some(Type::Int),
+        Type::BoolArray => Some(Type::Bool),
+        Type::FloatArray => Some(Type::Float),
+        Type::ShapeArray => Some(Type::Shape),
+        Type::PointArray => Some(Type::Point),
+        Type::ColorArray => Some(Type::Color),
+        Type::PathArray => Some(Type::Path),
+        Type::PolygonArray => Some(Type::Polygon),
+        Type::StringArray => Some(Type::String),
+        _ => None,
+    }
+}
+
+/// Type of an array with elements of the given type, `None` if there is no such array type
+pub fn array_type(element: Type) -> Option<Type> {
+    match element {
+        Type::Int => Some(Type::IntArray),
+        Type::Bool => Some(Type::BoolArray),
+        Type::Float => Some(Type::FloatArray),
+        Type::Shape => Some(Type::ShapeArray),
+        Type::Point => Some(Type::PointArray),
+        Type::Color => Some(Type::ColorArray),
+        Type::Path => Some(Type::PathArray),
+        Type::Polygon => Some(Type::PolygonArray),
+        Type::String => Some(Type::StringArray),
+        _ => None,
+    }
+}
+
+fn check_arity(
+    functions: &[(&str, usize)],
+    name: &str,
+    args: &[Type],
+) -> Result<(), Box<dyn Error>> {
+    let arity = functions
+        .iter()
+        .find(|(function, _)| *function == name)
+        .map(|(_, arity)| *arity)
+        .ok_or_else(|| errors::IdentifierNotFound(name.to_owned()))?;
+    if args.len() != arity {
+        return Err(errors::FCallParametersCountError(name.to_owned()).into());
+    }
+    Ok(())
+}
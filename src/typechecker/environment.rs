@@ -1,6 +1,6 @@
 use std::{collections::HashMap, error::Error};
 
-use crate::program::r#type::Type;
+use crate::program::{imports::ImportResolver, r#type::Type};
 
 use super::errors;
 
@@ -10,6 +10,8 @@ pub struct TEnvironment {
     f_table: HashMap<String, (Vec<Type>, Type)>,
     s_table: HashMap<String, HashMap<String, EType>>,
     r_type: Type,
+    in_loop: bool,
+    imports: ImportResolver,
 }
 
 impl TEnvironment {
@@ -20,19 +22,15 @@ impl TEnvironment {
             f_table: HashMap::new(),
             s_table: HashMap::new(),
             r_type: Type::Int,
+            in_loop: false,
+            imports: ImportResolver::default(),
         };
 
         env.stable_init();
-        env.ftable_init();
 
         env
     }
 
-    pub fn ftable_init(&mut self){
-        self.ftable_set("push".to_string(), vec![Type::IntArray,Type::Int],Type::IntArray);
-        self.ftable_set("remove".to_string(), vec![Type::IntArray,Type::Int],Type::IntArray);
-    }
-
     pub fn stable_init(&mut self){
         let mut path_param = HashMap::new();
         path_param.insert("stroke".to_string(), EType::DeclDefault(Type::Color));
@@ -44,6 +42,12 @@ impl TEnvironment {
         poly_param.insert("stroke".to_string(), EType::DeclDefault(Type::Color));
         poly_param.insert("thickness".to_string(), EType::DeclDefault(Type::Int));
         self.stable_set("Polygon".to_string(), poly_param);
+
+        let mut text_param = HashMap::new();
+        text_param.insert("content".to_string(), EType::DeclNonDefault(Type::String));
+        text_param.insert("size".to_string(), EType::DeclDefault(Type::Int));
+        text_param.insert("fill".to_string(), EType::DeclDefault(Type::Color));
+        self.stable_set("text".to_string(), text_param);
     }
     
     pub fn vtable_lookup(&self, identifier: &String) -> Result<&Type, Box<dyn Error>> {
@@ -143,6 +147,23 @@ impl TEnvironment {
         self.r_type
     }
 
+    /// Whether the statements being checked are inside a loop, where `break` and `continue` are allowed
+    pub fn loop_set(&mut self, in_loop: bool) {
+        self.in_loop = in_loop;
+    }
+
+    pub fn loop_lookup(&self) -> bool {
+        self.in_loop
+    }
+
+    pub fn imports_get(&self) -> &ImportResolver {
+        &self.imports
+    }
+
+    pub fn imports_set(&mut self, imports: ImportResolver) {
+        self.imports = imports;
+    }
+
     pub fn clear(&mut self) {
         self.v_table.clear();
         self.f_table.clear();
@@ -172,6 +172,19 @@ impl fmt::Display for PathOperationTypeNotCompatible {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct PathArcTypeFault(pub Type, pub Type, pub Type);
+impl Error for PathArcTypeFault {}
+impl fmt::Display for PathArcTypeFault {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "An arc takes a radius as a number or a point and two bools, got {:?}, {:?} and {:?}",
+            self.0, self.1, self.2
+        )
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PolyOperationTypeNotCompatible(pub Type);
 impl Error for PolyOperationTypeNotCompatible {}
@@ -269,7 +282,19 @@ impl fmt::Display for MemberAccessShape {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(
             f,
-            "You can use \"width\" or \"height\" as member access on a shape"
+            "You can use \"width\", \"height\", \"x\", \"y\", \"area\", \"perimeter\" or \"centroid\" as member access on a shape"
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MemberAccessFigure();
+impl Error for MemberAccessFigure {}
+impl fmt::Display for MemberAccessFigure {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "You can use \"width\", \"height\", \"area\", \"perimeter\" or \"centroid\" as member access on a path or a polygon"
         )
     }
 }
@@ -293,7 +318,7 @@ impl fmt::Display for MemberAccessArray {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(
             f,
-            "You can use \"size\" as member access on a array type"
+            "You can use \"size\" as member access on a array or string type"
         )
     }
 }
@@ -305,7 +330,42 @@ impl fmt::Display for ManipulationScaleTypeFault {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(
             f,
-            "Was unable to scale: \nExpected type Shape got {:?}\nExpected type Int or Float got {:?}", 
+            "Was unable to scale: \nExpected type Shape got {:?}\nExpected type Int, Float or Point got {:?}", 
+            self.0, self.1
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ManipulationAnchorTypeFault(pub Type);
+impl Error for ManipulationAnchorTypeFault {}
+impl fmt::Display for ManipulationAnchorTypeFault {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Expected type Point after around got {:?}", self.0)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ManipulationApplyTypeFault(pub Type, pub Type);
+impl Error for ManipulationApplyTypeFault {}
+impl fmt::Display for ManipulationApplyTypeFault {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Was unable to apply: \nExpected type Transform got {:?}\nExpected type Shape got {:?}",
+            self.0, self.1
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ManipulationMoveTypeFault(pub Type, pub Type);
+impl Error for ManipulationMoveTypeFault {}
+impl fmt::Display for ManipulationMoveTypeFault {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Was unable to move: \nExpected type Shape got {:?}\nExpected type Point got {:?}",
             self.0, self.1
         )
     }
@@ -324,6 +384,32 @@ impl fmt::Display for ManipulationRotateTypeFault {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct ManipulationCombineTypeFault(pub Type, pub Type);
+impl Error for ManipulationCombineTypeFault {}
+impl fmt::Display for ManipulationCombineTypeFault {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Was unable to combine shapes: \nExpected type Shape got {:?}\nExpected type Shape got {:?}",
+            self.0, self.1
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ManipulationMirrorTypeFault(pub Type, pub Type);
+impl Error for ManipulationMirrorTypeFault {}
+impl fmt::Display for ManipulationMirrorTypeFault {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Was unable to mirror: Expected type {:?} got {:?}",
+            self.0, self.1
+        )
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ManipulationPlaceTypeFault(pub Type, pub Type);
 impl Error for ManipulationPlaceTypeFault {}
@@ -436,3 +522,27 @@ impl fmt::Display for ErrorInRemove {
     }
 }
 
+
+#[derive(Debug, Clone)]
+pub struct WhileNotBooltypeError(pub Type);
+impl Error for WhileNotBooltypeError {}
+impl fmt::Display for WhileNotBooltypeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "The condition of the while loop was not a Bool, found: {:?}", self.0
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LoopControlOutsideLoop(pub String);
+impl Error for LoopControlOutsideLoop {}
+impl fmt::Display for LoopControlOutsideLoop {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} can only be used inside a loop", self.0
+        )
+    }
+}
@@ -1,15 +1,24 @@
 use std::error::Error;
 
 use crate::program::{
-    expression::Expr,
+    expression::{Anchor, Expr, MirrorAxis},
     operators::{
         binaryoperator::BinaryOperator, pathoperator::PathOperator, polyoperator::PolyOperator,
         unaryoperator::UnaryOperator,
     },
     r#type::Type,
+    span::Spanned,
 };
 
-use super::{environment::TEnvironment, errors, TypeCheckE};
+use super::{builtins, environment::TEnvironment, errors, TypeCheckE};
+
+impl TypeCheckE for Spanned<Expr> {
+    fn type_check(&self, environment: &mut TEnvironment) -> Result<Type, Box<dyn Error>> {
+        self.node
+            .type_check(environment)
+            .map_err(|err| self.span.locate(err))
+    }
+}
 
 impl TypeCheckE for Expr {
     fn type_check(
@@ -20,6 +29,7 @@ impl TypeCheckE for Expr {
             Expr::Integer(_) => Ok(Type::Int),
             Expr::Boolean(_) => Ok(Type::Bool),
             Expr::Float(_) => Ok(Type::Float),
+            Expr::String(_) => Ok(Type::String),
             Expr::Point(x, y) => {
                 let t1 = x.type_check(environment)?;
                 let t2 = y.type_check(environment)?;
@@ -43,20 +53,40 @@ impl TypeCheckE for Expr {
                     _ => Err(errors::ColorTypeNotCompatible(t1, t2, t3, t4).into()),
                 }
             }
-            Expr::Variable(identifier) => environment.vtable_lookup(identifier).cloned(),
+            Expr::Variable(identifier) => match environment.vtable_lookup(identifier) {
+                Ok(r#type) => Ok(*r#type),
+                Err(err) => builtins::constant_type(identifier).ok_or(err),
+            },
             Expr::PathOperation { lhs, rhs, operator } => {
                 let t1 = lhs.type_check(environment)?;
                 let t2 = rhs.type_check(environment)?;
 
-                match operator {
-                    PathOperator::Line | PathOperator::Curve => match (t1, t2) {
-                        //implementation of typing rule "Path"
-                        (Type::Point, Type::Point)
-                        | (Type::Path, Type::Path)
-                        | (Type::Point, Type::Path)
-                        | (Type::Path, Type::Point) => Ok(Type::Path),
-                        _ => Err(errors::PathOperationTypeNotCompatible(t1, t2).into()),
-                    },
+                if let PathOperator::Arc {
+                    radius,
+                    large,
+                    sweep,
+                } = operator
+                {
+                    let (t3, t4, t5) = (
+                        radius.type_check(environment)?,
+                        large.type_check(environment)?,
+                        sweep.type_check(environment)?,
+                    );
+                    if !matches!(t3, Type::Int | Type::Float | Type::Point)
+                        || t4 != Type::Bool
+                        || t5 != Type::Bool
+                    {
+                        return Err(errors::PathArcTypeFault(t3, t4, t5).into());
+                    }
+                }
+
+                match (t1, t2) {
+                    //implementation of typing rule "Path"
+                    (Type::Point, Type::Point)
+                    | (Type::Path, Type::Path)
+                    | (Type::Point, Type::Path)
+                    | (Type::Path, Type::Point) => Ok(Type::Path),
+                    _ => Err(errors::PathOperationTypeNotCompatible(t1, t2).into()),
                 }
             }
             Expr::PolygonOperation { path, operator } => {
@@ -94,6 +124,7 @@ impl TypeCheckE for Expr {
                         Type::Point => Ok(Type::PointArray),
                         Type::Polygon => Ok(Type::PolygonArray),
                         Type::Color => Ok(Type::ColorArray),
+                        Type::String => Ok(Type::StringArray),
                         _ => Err(errors::ArrayElementsTypeNotCompatible(t_for_array).into()),
                     }
                 }
@@ -102,29 +133,29 @@ impl TypeCheckE for Expr {
                 let t1 = lhs.type_check(environment)?;
                 let t2 = rhs.type_check(environment)?;
                 match operator {
+                    BinaryOperator::Add if (t1, t2) == (Type::String, Type::String) => {
+                        Ok(Type::String)
+                    }
+                    //Composes two transforms, the right one is applied first
+                    BinaryOperator::Multiply if (t1, t2) == (Type::Transform, Type::Transform) => {
+                        Ok(Type::Transform)
+                    }
                     BinaryOperator::Add
                     | BinaryOperator::Subtract
                     | BinaryOperator::Divide
                     | BinaryOperator::Multiply
-                    | BinaryOperator::Modulus => match (t1, t2) {
-                        (Type::Int, Type::Int) => Ok(Type::Int),
-                        (Type::Float, Type::Float)
-                        | (Type::Int, Type::Float)
-                        | (Type::Float, Type::Int) => Ok(Type::Float),
-                        (t1, t2) => Err(errors::BinaryOperationTypeNotCompatible(t1, t2).into()),
-                    },
+                    | BinaryOperator::Modulus => t1
+                        .promote(t2)
+                        .ok_or_else(|| errors::BinaryOperationTypeNotCompatible(t1, t2).into()),
                     BinaryOperator::LessThan
                     | BinaryOperator::LessThanOrEquals
                     | BinaryOperator::GreaterThan
                     | BinaryOperator::GreaterThanOrEquals
                     | BinaryOperator::NotEquals
-                    | BinaryOperator::Equals => match (t1, t2) {
-                        (Type::Int, Type::Int)
-                        | (Type::Float, Type::Int)
-                        | (Type::Int, Type::Float)
-                        | (Type::Float, Type::Float) => Ok(Type::Bool),
-                        (t1, t2) => Err(errors::BinaryOperationTypeNotCompatible(t1, t2).into()),
-                    },
+                    | BinaryOperator::Equals => t1
+                        .promote(t2)
+                        .map(|_| Type::Bool)
+                        .ok_or_else(|| errors::BinaryOperationTypeNotCompatible(t1, t2).into()),
                     BinaryOperator::LogicalAnd | BinaryOperator::LogicalOr => {
                         if t1.eq(&Type::Bool) && t2.eq(&Type::Bool) {
                             Ok(Type::Bool)
@@ -156,65 +187,13 @@ impl TypeCheckE for Expr {
                 }
             }
             Expr::FCall { name, args } => {
-                match name.as_str() {
-                    "remove" => {
-                        if 2 != args.len() {
-                            return Err(errors::FCallParametersCountError(name.to_owned()).into());
-                        }
-
-                        let t1 = args[0].type_check(environment)?;
-                        let t2 = args[1].type_check(environment)?;
-                        match t1 {
-                            Type::IntArray
-                            | Type::BoolArray
-                            | Type::FloatArray
-                            | Type::ShapeArray
-                            | Type::PointArray
-                            | Type::ColorArray
-                            | Type::PathArray
-                            | Type::PolygonArray => {
-                                if t2 == Type::Int {
-                                    Ok(t1)
-                                } else {
-                                    Err(errors::ErrorInRemove(t1, t2).into())
-                                }
-                            }
-                            _ => Err(errors::ErrorInRemove(t1, t2).into()),
-                        }
-                    }
-                    "push" => {
-                        if 2 != args.len() {
-                            return Err(errors::FCallParametersCountError(name.to_owned()).into());
-                        }
-                        let t1 = args[0].type_check(environment)?;
-                        let t2 = args[1].type_check(environment)?;
-                        let t3 = match t1 {
-                            Type::IntArray => Type::Int,
-                            Type::BoolArray => Type::Bool,
-                            Type::FloatArray => Type::Float,
-                            Type::ShapeArray => Type::Shape,
-                            Type::PointArray => Type::Point,
-                            Type::ColorArray => Type::Color,
-                            Type::PathArray => Type::Path,
-                            Type::PolygonArray => Type::Polygon,
-                            Type::Empty => match t2 {
-                                Type::Int => return Ok(Type::IntArray),
-                                Type::Bool => return Ok(Type::BoolArray),
-                                Type::Float => return Ok(Type::FloatArray),
-                                Type::Shape => return Ok(Type::ShapeArray),
-                                Type::Point => return Ok(Type::PointArray),
-                                Type::Color => return Ok(Type::ColorArray),
-                                Type::Path => return Ok(Type::PathArray),
-                                Type::Polygon => return Ok(Type::PolygonArray),
-                                _ => return Err(errors::ErrorInPush(t1, t2).into()),
-                            },
-                            _ => return Err(errors::ErrorInPush(t1, t2).into()),
-                        };
-                        if t2 == t3 {
-                            Ok(t1)
-                        } else {
-                            Err(errors::ErrorInPush(t1, t2).into())
-                        }
+                match builtins::lookup(name) {
+                    Some(builtin) if environment.ftable_lookup(name).is_err() => {
+                        let arg_types = args
+                            .iter()
+                            .map(|arg| arg.type_check(environment))
+                            .collect::<Result<Vec<Type>, _>>()?;
+                        builtin.call_type(name, &arg_types)
                     }
                     _ => {
                         let (parameters, return_type) = environment.ftable_lookup(name)?.clone();
@@ -244,8 +223,11 @@ impl TypeCheckE for Expr {
             } => {
                 match name {
                     Some(name) => {
-                        //Type checks the Shape call
-                        let expected_types = environment.stable_lookup(name)?.clone();
+                        //Type checks the Shape call, an imported shape is used over a builtin one
+                        let (expected_types, builtin) = match environment.stable_lookup(name) {
+                            Ok(expected_types) => (expected_types.clone(), false),
+                            Err(err) => (builtins::primitive_parameters(name).ok_or(err)?, true),
+                        };
 
                         for (key, value) in args.iter() {
                             if !expected_types.contains_key(key) {
@@ -264,7 +246,9 @@ impl TypeCheckE for Expr {
                                 super::environment::EType::Normal(_) => unreachable!(),
                             };
 
-                            if t1 != shape_t {
+                            //Builtin shapes promote an int size to a float like the other builtins
+                            let promoted = builtin && t1 == Type::Int && shape_t == Type::Float;
+                            if t1 != shape_t && !promoted {
                                 return Err(errors::SCallParametersIncompatible(
                                     name.to_owned(),
                                     key.clone(),
@@ -275,9 +259,10 @@ impl TypeCheckE for Expr {
                             }
                         }
                         // non default params are only checked on name, since type was chekced above.
-                        let non_def = environment.stable_get_hashmap_non_default(name)?;
-                        non_def.iter().try_for_each(|(key, _)| {
-                            if !args.contains_key(key) {
+                        expected_types.iter().try_for_each(|(key, etype)| {
+                            if matches!(etype, super::environment::EType::DeclNonDefault(_))
+                                && !args.contains_key(key)
+                            {
                                 Err(errors::SCallParameterNotFound(key.into(), name.into()))
                             } else {
                                 Ok(())
@@ -331,8 +316,19 @@ impl TypeCheckE for Expr {
                         "height" => Ok(Type::Float),
                         "x" => Ok(Type::Float),
                         "y" => Ok(Type::Float),
+                        "area" => Ok(Type::Float),
+                        "perimeter" => Ok(Type::Float),
+                        "centroid" => Ok(Type::Point),
                         _ => Err(errors::MemberAccessShape().into()),
                     },
+                    Type::Path | Type::Polygon => match member_access.as_str() {
+                        "width" => Ok(Type::Float),
+                        "height" => Ok(Type::Float),
+                        "area" => Ok(Type::Float),
+                        "perimeter" => Ok(Type::Float),
+                        "centroid" => Ok(Type::Point),
+                        _ => Err(errors::MemberAccessFigure().into()),
+                    },
                     Type::Point => match member_access.as_str() {
                         "x" => Ok(Type::Float),
                         "y" => Ok(Type::Float),
@@ -345,7 +341,9 @@ impl TypeCheckE for Expr {
                     | Type::PathArray
                     | Type::PointArray
                     | Type::PolygonArray
-                    | Type::ShapeArray => match member_access.as_str() {
+                    | Type::ShapeArray
+                    | Type::StringArray
+                    | Type::String => match member_access.as_str() {
                         "size" => Ok(Type::Int),
                         _ => Err(errors::MemberAccessArray().into()),
                     },
@@ -378,19 +376,34 @@ impl TypeCheckE for Expr {
 
                 Ok(Type::Shape)
             }
-            Expr::Scale { base_shape, factor } => {
+            Expr::Scale {
+                base_shape,
+                factor,
+                anchor,
+            } => {
                 let t1 = base_shape.type_check(environment)?;
                 let t2 = factor.type_check(environment)?;
+                if let Some(anchor) = anchor {
+                    anchor.type_check(environment)?;
+                }
 
-                if t1 == Type::Shape && (t2 == Type::Int || t2 == Type::Float) {
+                //A point scales x and y independently
+                if t1 == Type::Shape && (t2 == Type::Int || t2 == Type::Float || t2 == Type::Point) {
                     Ok(Type::Shape)
                 } else {
                     Err(errors::ManipulationScaleTypeFault(t1, t2).into())
                 }
             }
-            Expr::Rotate { base_shape, factor } => {
+            Expr::Rotate {
+                base_shape,
+                factor,
+                anchor,
+            } => {
                 let t1 = base_shape.type_check(environment)?;
                 let t2 = factor.type_check(environment)?;
+                if let Some(anchor) = anchor {
+                    anchor.type_check(environment)?;
+                }
 
                 if t1 == Type::Shape && (t2 == Type::Int || t2 == Type::Float) {
                     Ok(Type::Shape)
@@ -398,6 +411,60 @@ impl TypeCheckE for Expr {
                     Err(errors::ManipulationRotateTypeFault(t1, t2).into())
                 }
             }
+            Expr::Combine {
+                base_shape,
+                second_shape,
+                ..
+            } => {
+                let t1 = base_shape.type_check(environment)?;
+                let t2 = second_shape.type_check(environment)?;
+
+                if t1 == Type::Shape && t2 == Type::Shape {
+                    Ok(Type::Shape)
+                } else {
+                    Err(errors::ManipulationCombineTypeFault(t1, t2).into())
+                }
+            }
+            Expr::Mirror { base_shape, axis } => {
+                let t1 = base_shape.type_check(environment)?;
+                if t1 != Type::Shape {
+                    return Err(errors::ManipulationMirrorTypeFault(Type::Shape, t1).into());
+                }
+                if let MirrorAxis::Line(from, to) = axis {
+                    for point in [from, to] {
+                        let t2 = point.type_check(environment)?;
+                        if t2 != Type::Point {
+                            return Err(errors::ManipulationMirrorTypeFault(Type::Point, t2).into());
+                        }
+                    }
+                }
+                Ok(Type::Shape)
+            }
+            Expr::Move {
+                base_shape, point, ..
+            } => {
+                let t1 = base_shape.type_check(environment)?;
+                let t2 = point.type_check(environment)?;
+
+                if t1 == Type::Shape && t2 == Type::Point {
+                    Ok(Type::Shape)
+                } else {
+                    Err(errors::ManipulationMoveTypeFault(t1, t2).into())
+                }
+            }
+            Expr::Apply {
+                transform,
+                base_shape,
+            } => {
+                let t1 = transform.type_check(environment)?;
+                let t2 = base_shape.type_check(environment)?;
+
+                if t1 == Type::Transform && t2 == Type::Shape {
+                    Ok(Type::Shape)
+                } else {
+                    Err(errors::ManipulationApplyTypeFault(t1, t2).into())
+                }
+            }
             Expr::ArrayIndex { identifier, index } => {
                 let t1 = identifier.type_check(environment)?;
                 let t2 = index.type_check(environment)?;
@@ -415,9 +482,24 @@ impl TypeCheckE for Expr {
                     Type::ColorArray => Ok(Type::Color),
                     Type::PathArray => Ok(Type::Path),
                     Type::PolygonArray => Ok(Type::Polygon),
+                    Type::StringArray => Ok(Type::String),
+                    //Indexing a string gives the character at that position
+                    Type::String => Ok(Type::String),
                     _ => return Err(errors::NotAnArrayToIndex(t1).into()),
                 }
             }
         }
     }
 }
+
+impl TypeCheckE for Anchor {
+    fn type_check(&self, environment: &mut TEnvironment) -> Result<Type, Box<dyn Error>> {
+        if let Anchor::Point(point) = self {
+            let t1 = point.type_check(environment)?;
+            if t1 != Type::Point {
+                return Err(errors::ManipulationAnchorTypeFault(t1).into());
+            }
+        }
+        Ok(Type::Point)
+    }
+}
@@ -4,6 +4,7 @@ use environment::TEnvironment;
 
 use crate::program::r#type::Type;
 
+pub mod builtins;
 pub mod expression;
 pub mod program;
 pub mod statement;
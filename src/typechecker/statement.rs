@@ -1,12 +1,21 @@
-use std::{collections::HashMap, error::Error, path::Path};
+use std::{collections::HashMap, error::Error};
 
 use crate::{
-    program::{expression::Expr, program::Program, r#type::Type, statement::Stmt},
-    typechecker::{environment::EType, TypeCheckP},
+    program::{expression::Expr, r#type::Type, span::Spanned, statement::Stmt},
+    terminal::status,
+    typechecker::environment::EType,
 };
 
 use super::{environment::TEnvironment, errors, TypeCheckE, TypeCheckS};
 
+impl TypeCheckS for Spanned<Stmt> {
+    fn type_check(&self, environment: &mut TEnvironment) -> Result<(), Box<dyn Error>> {
+        self.node
+            .type_check(environment)
+            .map_err(|err| self.span.locate(err))
+    }
+}
+
 impl TypeCheckS for Stmt {
     fn type_check(&self, environment: &mut TEnvironment) -> Result<(), Box<dyn Error>> {
         match self {
@@ -15,20 +24,17 @@ impl TypeCheckS for Stmt {
                     return Err(errors::ImportAlreadyDeclared(name.to_owned()).into());
                 }
 
-                let mut subprogram =
-                    Program::from_file(Path::new(path)).map_err(|err| format!("{}", err))?;
-
-                match subprogram.type_check() {
-                    Ok(subprogram_environment) => {
-                        println!("[Typechecker] Path: {} - OK", path);
+                match environment.imports_get().load(path) {
+                    Ok(subprogram) => {
+                        status::print(format!("[Typechecker] Path: {} - OK", path));
                         let parameters: HashMap<String, EType> =
-                            subprogram_environment.vdtable_get_hashmap();
+                            subprogram.tenvironment.vdtable_get_hashmap();
 
                         environment.stable_set(name.clone(), parameters);
                         Ok(())
                     }
                     Err(err) => {
-                        println!("[Typechecker] Path: {} - ERROR", path);
+                        status::print(format!("[Typechecker] Path: {} - ERROR", path));
                         Err(err)
                     }
                 }
@@ -114,7 +120,8 @@ impl TypeCheckS for Stmt {
                     Type::ColorArray => array_type = Type::Color,
                     Type::PathArray => array_type = Type::Path,
                     Type::PolygonArray => array_type = Type::Polygon,
-                    _ => unreachable!(), //return Err(errors::AssignTypesNoMatch(t1, t2).into())
+                    Type::StringArray => array_type = Type::String,
+                    _ => return Err(errors::NotAnArrayToIndex(t1).into()),
                 }
 
                 if array_type == t2 {
@@ -139,6 +146,7 @@ impl TypeCheckS for Stmt {
                 }
                 let mut new_environment = environment.clone_and_clear_vtable();
                 new_environment.return_set(*return_type);
+                new_environment.loop_set(false);
 
                 for (param_name, param_type) in parameters {
                     new_environment.vtable_set(param_name.clone(), *param_type);
@@ -201,6 +209,21 @@ impl TypeCheckS for Stmt {
                 let mut new_environment = environment.clone();
 
                 new_environment.vtable_set(counter.to_string(), Type::Int);
+                new_environment.loop_set(true);
+
+                for stmt in body {
+                    stmt.type_check(&mut new_environment)?;
+                }
+
+                Ok(())
+            }
+            Stmt::While { condition, body } => {
+                let t1 = condition.type_check(environment)?;
+                if t1 != Type::Bool {
+                    return Err(errors::WhileNotBooltypeError(t1).into());
+                }
+                let mut new_environment = environment.clone();
+                new_environment.loop_set(true);
 
                 for stmt in body {
                     stmt.type_check(&mut new_environment)?;
@@ -208,6 +231,14 @@ impl TypeCheckS for Stmt {
 
                 Ok(())
             }
+            Stmt::Break | Stmt::Continue => {
+                if environment.loop_lookup() {
+                    Ok(())
+                } else {
+                    let keyword = if *self == Stmt::Break { "break" } else { "continue" };
+                    Err(errors::LoopControlOutsideLoop(keyword.into()).into())
+                }
+            }
             Stmt::Fork {
                 branches,
                 otherwise,
@@ -254,7 +285,8 @@ fn checks_empty_array(array: Type, empty: Type) -> bool {
         | Type::PointArray
         | Type::ShapeArray
         | Type::PolygonArray
-        | Type::ColorArray => true,
+        | Type::ColorArray
+        | Type::StringArray => true,
         _ => false,
     }
 }
@@ -1,11 +1,12 @@
 use crate::{
     program::{
-        expression::Expr,
+        expression::{Anchor, Expr, MirrorAxis},
         operators::{
             binaryoperator::BinaryOperator, pathoperator::PathOperator, polyoperator::PolyOperator,
-            unaryoperator::UnaryOperator,
+            shapeoperator::ShapeOperator, unaryoperator::UnaryOperator,
         },
         r#type::Type,
+        span::Spanned,
     },
     typechecker::{
         environment::{EType, TEnvironment},
@@ -38,10 +39,10 @@ fn float() {
 fn color() {
     let mut env = TEnvironment::new();
     let t1 = Expr::Color(
-        Box::new(Expr::Integer(1)),
-        Box::new(Expr::Integer(2)),
-        Box::new(Expr::Integer(3)),
-        Box::new(Expr::Integer(4)),
+        Box::new(Expr::Integer(1).into()),
+        Box::new(Expr::Integer(2).into()),
+        Box::new(Expr::Integer(3).into()),
+        Box::new(Expr::Integer(4).into()),
     )
     .type_check(&mut env)
     .unwrap();
@@ -52,10 +53,10 @@ fn color() {
 fn color_invalid() {
     let mut env = TEnvironment::new();
     let invalid = Expr::Color(
-        Box::new(Expr::Integer(1)),
-        Box::new(Expr::Integer(2)),
-        Box::new(Expr::Integer(3)),
-        Box::new(Expr::Float(4.2)),
+        Box::new(Expr::Integer(1).into()),
+        Box::new(Expr::Integer(2).into()),
+        Box::new(Expr::Integer(3).into()),
+        Box::new(Expr::Float(4.2).into()),
     )
     .type_check(&mut env);
     assert!(invalid
@@ -67,7 +68,7 @@ fn color_invalid() {
 #[test]
 fn point() {
     let mut env = TEnvironment::new();
-    let t1 = Expr::Point(Box::new(Expr::Integer(1)), Box::new(Expr::Integer(2)))
+    let t1 = Expr::Point(Box::new(Expr::Integer(1).into()), Box::new(Expr::Integer(2).into()))
         .type_check(&mut env)
         .unwrap();
     assert_eq!(t1, Type::Point)
@@ -77,7 +78,7 @@ fn point() {
 fn point_invalid() {
     let mut env = TEnvironment::new();
     let invalid =
-        Expr::Point(Box::new(Expr::Integer(1)), Box::new(Expr::Boolean(true))).type_check(&mut env);
+        Expr::Point(Box::new(Expr::Integer(1).into()), Box::new(Expr::Boolean(true).into())).type_check(&mut env);
     assert!(invalid
         .unwrap_err()
         .downcast_ref::<errors::PointTypeNotCompatible>()
@@ -89,13 +90,13 @@ fn path_with_points() {
     let mut env = TEnvironment::new();
     let t1 = Expr::PathOperation {
         lhs: Box::new(Expr::Point(
-            Box::new(Expr::Integer(1)),
-            Box::new(Expr::Integer(2)),
-        )),
+            Box::new(Expr::Integer(1).into()),
+            Box::new(Expr::Integer(2).into()),
+        ).into()),
         rhs: Box::new(Expr::Point(
-            Box::new(Expr::Integer(3)),
-            Box::new(Expr::Integer(4)),
-        )),
+            Box::new(Expr::Integer(3).into()),
+            Box::new(Expr::Integer(4).into()),
+        ).into()),
         operator: PathOperator::Line,
     }
     .type_check(&mut env)
@@ -109,19 +110,19 @@ fn path_with_path_and_point() {
     let t1 = Expr::PathOperation {
         lhs: Box::new(Expr::PathOperation {
             lhs: Box::new(Expr::Point(
-                Box::new(Expr::Integer(1)),
-                Box::new(Expr::Integer(2)),
-            )),
+                Box::new(Expr::Integer(1).into()),
+                Box::new(Expr::Integer(2).into()),
+            ).into()),
             rhs: Box::new(Expr::Point(
-                Box::new(Expr::Integer(3)),
-                Box::new(Expr::Integer(4)),
-            )),
+                Box::new(Expr::Integer(3).into()),
+                Box::new(Expr::Integer(4).into()),
+            ).into()),
             operator: PathOperator::Line,
-        }),
+        }.into()),
         rhs: Box::new(Expr::Point(
-            Box::new(Expr::Integer(3)),
-            Box::new(Expr::Integer(4)),
-        )),
+            Box::new(Expr::Integer(3).into()),
+            Box::new(Expr::Integer(4).into()),
+        ).into()),
         operator: PathOperator::Line,
     }
     .type_check(&mut env)
@@ -133,11 +134,11 @@ fn path_with_path_and_point() {
 fn path_invalid() {
     let mut env = TEnvironment::new();
     let invalid = Expr::PathOperation {
-        lhs: Box::new(Expr::Integer(1)),
+        lhs: Box::new(Expr::Integer(1).into()),
         rhs: Box::new(Expr::Point(
-            Box::new(Expr::Integer(3)),
-            Box::new(Expr::Integer(4)),
-        )),
+            Box::new(Expr::Integer(3).into()),
+            Box::new(Expr::Integer(4).into()),
+        ).into()),
         operator: PathOperator::Line,
     }
     .type_check(&mut env);
@@ -147,21 +148,46 @@ fn path_invalid() {
         .is_some());
 }
 
+#[test]
+fn path_arc() {
+    let point = |x: i64, y: i64| -> Box<Spanned<Expr>> {
+        Box::new(Expr::Point(Box::new(Expr::Integer(x).into()), Box::new(Expr::Integer(y).into())).into())
+    };
+    let arc = |radius: Expr, sweep: Expr| Expr::PathOperation {
+        lhs: point(0, 0),
+        rhs: point(4, 0),
+        operator: PathOperator::Arc {
+            radius: Box::new(radius.into()),
+            large: Box::new(Expr::Boolean(false).into()),
+            sweep: Box::new(sweep.into()),
+        },
+    };
+    let mut env = TEnvironment::new();
+    assert_eq!(arc(Expr::Integer(2), Expr::Boolean(true)).type_check(&mut env).unwrap(), Type::Path);
+    let radii = Expr::Point(Box::new(Expr::Float(2.).into()), Box::new(Expr::Integer(1).into()));
+    assert_eq!(arc(radii, Expr::Boolean(true)).type_check(&mut env).unwrap(), Type::Path);
+    assert!(arc(Expr::Integer(2), Expr::Integer(1))
+        .type_check(&mut env)
+        .unwrap_err()
+        .downcast_ref::<errors::PathArcTypeFault>()
+        .is_some());
+}
+
 #[test]
 fn polygon() {
     let mut env = TEnvironment::new();
     let t1 = Expr::PolygonOperation {
         path: Box::new(Expr::PathOperation {
             lhs: Box::new(Expr::Point(
-                Box::new(Expr::Integer(1)),
-                Box::new(Expr::Integer(2)),
-            )),
+                Box::new(Expr::Integer(1).into()),
+                Box::new(Expr::Integer(2).into()),
+            ).into()),
             rhs: Box::new(Expr::Point(
-                Box::new(Expr::Integer(3)),
-                Box::new(Expr::Integer(4)),
-            )),
+                Box::new(Expr::Integer(3).into()),
+                Box::new(Expr::Integer(4).into()),
+            ).into()),
             operator: PathOperator::Line,
-        }),
+        }.into()),
         operator: PolyOperator::Straight,
     }
     .type_check(&mut env)
@@ -173,7 +199,7 @@ fn polygon() {
 fn polygon_invalid() {
     let mut env = TEnvironment::new();
     let invalid = Expr::PolygonOperation {
-        path: Box::new(Expr::Integer(1)),
+        path: Box::new(Expr::Integer(1).into()),
         operator: PolyOperator::Straight,
     }
     .type_check(&mut env);
@@ -186,7 +212,7 @@ fn polygon_invalid() {
 #[test]
 fn array_with_elements() {
     let mut env = TEnvironment::new();
-    let t1 = Expr::Array(vec![Expr::Integer(1), Expr::Integer(1), (Expr::Integer(1))])
+    let t1 = Expr::Array(vec![Expr::Integer(1).into(), Expr::Integer(1).into(), (Expr::Integer(1)).into()])
         .type_check(&mut env)
         .unwrap();
     assert_eq!(t1, Type::IntArray)
@@ -202,7 +228,7 @@ fn array_empty() {
 #[test]
 fn array_invalid() {
     let mut env = TEnvironment::new();
-    let invalid = Expr::Array(vec![Expr::Integer(1), Expr::Integer(1), (Expr::Float(1.2))])
+    let invalid = Expr::Array(vec![Expr::Integer(1).into(), Expr::Integer(1).into(), (Expr::Float(1.2)).into()])
         .type_check(&mut env);
     assert!(invalid
         .unwrap_err()
@@ -444,7 +470,7 @@ fn fcall() {
     env.ftable_set("x".into(), vec![Type::Int], Type::Bool);
     let t1 = Expr::FCall {
         name: "x".into(),
-        args: vec![Expr::Integer(5)],
+        args: vec![Expr::Integer(5).into()],
     }
     .type_check(&mut env)
     .unwrap();
@@ -456,7 +482,7 @@ fn fcall_invalid_identifier() {
     let mut env = TEnvironment::new();
     let invalid_identifier = Expr::FCall {
         name: "x".into(),
-        args: vec![Expr::Integer(5)],
+        args: vec![Expr::Integer(5).into()],
     }
     .type_check(&mut env);
     assert!(invalid_identifier
@@ -486,7 +512,7 @@ fn fcall_parameters_incompatible() {
     env.ftable_set("x".into(), vec![Type::Int], Type::Bool);
     let incompatible_parameter = Expr::FCall {
         name: "x".into(),
-        args: vec![Expr::Float(5.0)],
+        args: vec![Expr::Float(5.0).into()],
     }
     .type_check(&mut env);
     assert!(incompatible_parameter
@@ -500,7 +526,7 @@ fn fcall_push_intarray() {
     let mut env = TEnvironment::new();
     let t1 = Expr::FCall {
         name: "push".into(),
-        args: vec![Expr::Array(vec![Expr::Integer(5)]), Expr::Integer(5)],
+        args: vec![Expr::Array(vec![Expr::Integer(5).into()]).into(), Expr::Integer(5).into()],
     }
     .type_check(&mut env)
     .unwrap();
@@ -512,7 +538,7 @@ fn fcall_push_empty() {
     let mut env = TEnvironment::new();
     let t1 = Expr::FCall {
         name: "push".into(),
-        args: vec![Expr::Array(vec![]), Expr::Integer(5)],
+        args: vec![Expr::Array(vec![]).into(), Expr::Integer(5).into()],
     }
     .type_check(&mut env)
     .unwrap();
@@ -524,7 +550,7 @@ fn fcall_push_typeconflict() {
     let mut env = TEnvironment::new();
     let invalid_identifier = Expr::FCall {
         name: "push".into(),
-        args: vec![Expr::Array(vec![Expr::Integer(5)]), Expr::Float(5.0)],
+        args: vec![Expr::Array(vec![Expr::Integer(5).into()]).into(), Expr::Float(5.0).into()],
     }
     .type_check(&mut env);
     assert!(invalid_identifier
@@ -538,7 +564,7 @@ fn fcall_push_empty_array_with_array() {
     let mut env = TEnvironment::new();
     let invalid_identifier = Expr::FCall {
         name: "push".into(),
-        args: vec![Expr::Array(vec![]), Expr::Array(vec![Expr::Integer(5)])],
+        args: vec![Expr::Array(vec![]).into(), Expr::Array(vec![Expr::Integer(5).into()]).into()],
     }
     .type_check(&mut env);
     assert!(invalid_identifier
@@ -552,7 +578,7 @@ fn fcall_push_not_array() {
     let mut env = TEnvironment::new();
     let invalid_identifier = Expr::FCall {
         name: "push".into(),
-        args: vec![Expr::Integer(5), Expr::Integer(5)],
+        args: vec![Expr::Integer(5).into(), Expr::Integer(5).into()],
     }
     .type_check(&mut env);
     assert!(invalid_identifier
@@ -566,7 +592,7 @@ fn fcall_remove_intarray() {
     let mut env = TEnvironment::new();
     let t1 = Expr::FCall {
         name: "remove".into(),
-        args: vec![Expr::Array(vec![Expr::Integer(5)]), Expr::Integer(5)],
+        args: vec![Expr::Array(vec![Expr::Integer(5).into()]).into(), Expr::Integer(5).into()],
     }
     .type_check(&mut env)
     .unwrap();
@@ -578,7 +604,7 @@ fn fcall_remove_empty_array() {
     let mut env = TEnvironment::new();
     let invalid_identifier = Expr::FCall {
         name: "remove".into(),
-        args: vec![Expr::Array(vec![]), Expr::Integer(5)],
+        args: vec![Expr::Array(vec![]).into(), Expr::Integer(5).into()],
     }
     .type_check(&mut env);
     assert!(invalid_identifier
@@ -592,7 +618,7 @@ fn fcall_remove_not_int_index() {
     let mut env = TEnvironment::new();
     let invalid_identifier = Expr::FCall {
         name: "remove".into(),
-        args: vec![Expr::Array(vec![Expr::Integer(5)]), Expr::Float(5.0)],
+        args: vec![Expr::Array(vec![Expr::Integer(5).into()]).into(), Expr::Float(5.0).into()],
     }
     .type_check(&mut env);
     assert!(invalid_identifier
@@ -612,7 +638,7 @@ fn scall() {
     );
     let t1 = Expr::SCall {
         name: Some("circle".into()),
-        args: [("radius".into(), Expr::Float(5.0))].into_iter().collect(),
+        args: [("radius".into(), Expr::Float(5.0).into())].into_iter().collect(),
         path_poly: None,
     }
     .type_check(&mut env)
@@ -633,7 +659,7 @@ fn scall_default_path() {
                 Expr::Integer(1).into(),
                 Expr::Integer(1).into(),
                 Expr::Integer(1).into(),
-            ),
+            ).into(),
         )]
         .into_iter()
         .collect(),
@@ -664,7 +690,7 @@ fn scall_default_polygon() {
                 Expr::Integer(1).into(),
                 Expr::Integer(1).into(),
                 Expr::Integer(1).into(),
-            ),
+            ).into(),
         )]
         .into_iter()
         .collect(),
@@ -690,8 +716,8 @@ fn scall_default_polygon() {
 fn scall_invalid_identifier() {
     let mut env = TEnvironment::new();
     let invalid_identifier = Expr::SCall {
-        name: Some("circle".into()),
-        args: [("radius".into(), Expr::Float(5.0))].into_iter().collect(),
+        name: Some("star".into()),
+        args: [("radius".into(), Expr::Float(5.0).into())].into_iter().collect(),
         path_poly: None,
     }
     .type_check(&mut env);
@@ -712,7 +738,7 @@ fn scall_parameters_notfound() {
     );
     let invalid_parameter = Expr::SCall {
         name: Some("circle".into()),
-        args: [("circumference".into(), Expr::Float(5.0))]
+        args: [("circumference".into(), Expr::Float(5.0).into())]
             .into_iter()
             .collect(),
         path_poly: None,
@@ -735,7 +761,7 @@ fn scall_parameters_incompatible() {
     );
     let incompatible_parameter = Expr::SCall {
         name: Some("circle".into()),
-        args: [("radius".into(), Expr::Boolean(true))]
+        args: [("radius".into(), Expr::Boolean(true).into())]
             .into_iter()
             .collect(),
         path_poly: None,
@@ -874,7 +900,7 @@ fn member_array_invalid() {
 #[test]
 fn member_invalid() {
     let mut env = TEnvironment::new();
-    env.vtable_set("x".to_string(), Type::Path);
+    env.vtable_set("x".to_string(), Type::Int);
     let t1 = Expr::Member {
         identifier: "x".to_string(),
         member_access: "size".to_string(),
@@ -894,6 +920,7 @@ fn scale() {
     let t1 = Expr::Scale {
         base_shape: Expr::Variable("x".into()).into(),
         factor: Expr::Variable("p".into()).into(),
+        anchor: None,
     }
     .type_check(&mut env)
     .unwrap();
@@ -903,10 +930,11 @@ fn scale() {
 fn scale_invalid() {
     let mut env = TEnvironment::new();
     env.vtable_set("x".to_string(), Type::Shape);
-    env.vtable_set("p".to_string(), Type::Point);
+    env.vtable_set("p".to_string(), Type::Bool);
     let t1 = Expr::Scale {
         base_shape: Expr::Variable("x".into()).into(),
         factor: Expr::Variable("p".into()).into(),
+        anchor: None,
     }
     .type_check(&mut env);
     assert!(t1
@@ -923,6 +951,7 @@ fn rotate() {
     let t1 = Expr::Rotate {
         base_shape: Expr::Variable("x".into()).into(),
         factor: Expr::Variable("p".into()).into(),
+        anchor: None,
     }
     .type_check(&mut env)
     .unwrap();
@@ -937,6 +966,7 @@ fn rotate_invalid() {
     let t1 = Expr::Rotate {
         base_shape: Expr::Variable("x".into()).into(),
         factor: Expr::Variable("p".into()).into(),
+        anchor: None,
     }
     .type_check(&mut env);
     assert!(t1
@@ -997,3 +1027,526 @@ fn place_invalid() {
         .downcast_ref::<errors::ManipulationPlaceTypeFault>()
         .is_some());
 }
+
+#[test]
+fn string() {
+    let mut env = TEnvironment::new();
+    let t1 = Expr::String("hello".into()).type_check(&mut env).unwrap();
+    assert_eq!(t1, Type::String)
+}
+
+#[test]
+fn string_concatenation() {
+    let mut env = TEnvironment::new();
+    let t1 = Expr::BinaryOperation {
+        lhs: Expr::String("hello".into()).into(),
+        rhs: Expr::String(" world".into()).into(),
+        operator: BinaryOperator::Add,
+    }
+    .type_check(&mut env)
+    .unwrap();
+    assert_eq!(t1, Type::String)
+}
+
+#[test]
+fn string_concatenation_invalid() {
+    let mut env = TEnvironment::new();
+    let t1 = Expr::BinaryOperation {
+        lhs: Expr::String("hello".into()).into(),
+        rhs: Expr::Integer(1).into(),
+        operator: BinaryOperator::Add,
+    }
+    .type_check(&mut env);
+    assert!(t1
+        .unwrap_err()
+        .downcast_ref::<errors::BinaryOperationTypeNotCompatible>()
+        .is_some());
+
+    let t2 = Expr::BinaryOperation {
+        lhs: Expr::String("hello".into()).into(),
+        rhs: Expr::String("world".into()).into(),
+        operator: BinaryOperator::Subtract,
+    }
+    .type_check(&mut env);
+    assert!(t2
+        .unwrap_err()
+        .downcast_ref::<errors::BinaryOperationTypeNotCompatible>()
+        .is_some());
+}
+
+#[test]
+fn string_size_and_index() {
+    let mut env = TEnvironment::new();
+    env.vtable_set("s".to_string(), Type::String);
+    let t1 = Expr::Member {
+        identifier: "s".to_string(),
+        member_access: "size".to_string(),
+    }
+    .type_check(&mut env)
+    .unwrap();
+    assert_eq!(t1, Type::Int);
+
+    let t2 = Expr::ArrayIndex {
+        identifier: Expr::Variable("s".into()).into(),
+        index: Expr::Integer(0).into(),
+    }
+    .type_check(&mut env)
+    .unwrap();
+    assert_eq!(t2, Type::String)
+}
+
+#[test]
+fn string_array() {
+    let mut env = TEnvironment::new();
+    let t1 = Expr::Array(vec![
+        Expr::String("a".into()).into(),
+        Expr::String("b".into()).into(),
+    ])
+    .type_check(&mut env)
+    .unwrap();
+    assert_eq!(t1, Type::StringArray)
+}
+
+#[test]
+fn scall_text() {
+    let mut env = TEnvironment::new();
+    let t1 = Expr::SCall {
+        name: Some("text".into()),
+        args: [
+            ("content".into(), Expr::String("hello".into()).into()),
+            ("size".into(), Expr::Integer(12).into()),
+        ]
+        .into_iter()
+        .collect(),
+        path_poly: None,
+    }
+    .type_check(&mut env)
+    .unwrap();
+    assert_eq!(t1, Type::Shape)
+}
+
+#[test]
+fn scall_text_without_content() {
+    let mut env = TEnvironment::new();
+    let t1 = Expr::SCall {
+        name: Some("text".into()),
+        args: [("size".into(), Expr::Integer(12).into())].into_iter().collect(),
+        path_poly: None,
+    }
+    .type_check(&mut env);
+    assert!(t1
+        .unwrap_err()
+        .downcast_ref::<errors::SCallParameterNotFound>()
+        .is_some());
+}
+
+#[test]
+fn math_builtin_int_overload() {
+    let mut env = TEnvironment::new();
+    let t1 = Expr::FCall {
+        name: "max".into(),
+        args: vec![Expr::Integer(1).into(), Expr::Integer(2).into()],
+    }
+    .type_check(&mut env)
+    .unwrap();
+    let t2 = Expr::FCall {
+        name: "max".into(),
+        args: vec![Expr::Integer(1).into(), Expr::Float(2.0).into()],
+    }
+    .type_check(&mut env)
+    .unwrap();
+    let t3 = Expr::FCall {
+        name: "floor".into(),
+        args: vec![Expr::Float(2.5).into()],
+    }
+    .type_check(&mut env)
+    .unwrap();
+    assert_eq!((t1, t2, t3), (Type::Int, Type::Float, Type::Int))
+}
+
+#[test]
+fn math_builtin_wrong_arguments() {
+    let mut env = TEnvironment::new();
+    let t1 = Expr::FCall {
+        name: "sqrt".into(),
+        args: vec![Expr::Boolean(true).into()],
+    }
+    .type_check(&mut env);
+    let t2 = Expr::FCall {
+        name: "hypot".into(),
+        args: vec![Expr::Float(1.0).into()],
+    }
+    .type_check(&mut env);
+    assert!(t1
+        .unwrap_err()
+        .downcast_ref::<errors::FCallParametersIncompatible>()
+        .is_some());
+    assert!(t2
+        .unwrap_err()
+        .downcast_ref::<errors::FCallParametersCountError>()
+        .is_some());
+}
+
+#[test]
+fn math_constant() {
+    let mut env = TEnvironment::new();
+    let t1 = Expr::Variable("PI".into()).type_check(&mut env).unwrap();
+    assert_eq!(t1, Type::Float)
+}
+
+#[test]
+fn fcall_push_shapearray() {
+    let mut env = TEnvironment::new();
+    env.vtable_set("shapes".into(), Type::ShapeArray);
+    env.vtable_set("s".into(), Type::Shape);
+    let t1 = Expr::FCall {
+        name: "push".into(),
+        args: vec![Expr::Variable("shapes".into()).into(), Expr::Variable("s".into()).into()],
+    }
+    .type_check(&mut env)
+    .unwrap();
+    assert_eq!(t1, Type::ShapeArray);
+}
+
+#[test]
+fn fcall_array_builtins() {
+    let mut env = TEnvironment::new();
+    env.vtable_set("points".into(), Type::PointArray);
+    let points = || Expr::Variable("points".into()).into();
+    let point = || Expr::Point(Expr::Integer(1).into(), Expr::Integer(2).into()).into();
+
+    let call = |name: &str, args| Expr::FCall {
+        name: name.into(),
+        args,
+    };
+    let types: Vec<Type> = [
+        call("insert", vec![points(), Expr::Integer(0).into(), point()]),
+        call("concat", vec![points(), Expr::Array(vec![]).into()]),
+        call("slice", vec![points(), Expr::Integer(0).into(), Expr::Integer(1).into()]),
+        call("reverse", vec![points()]),
+        call("contains", vec![points(), point()]),
+        call("index_of", vec![points(), point()]),
+        call("fill", vec![Expr::Integer(3).into(), point()]),
+    ]
+    .iter()
+    .map(|expr| expr.type_check(&mut env).unwrap())
+    .collect();
+    assert_eq!(
+        types,
+        vec![
+            Type::PointArray,
+            Type::PointArray,
+            Type::PointArray,
+            Type::PointArray,
+            Type::Bool,
+            Type::Int,
+            Type::PointArray
+        ]
+    );
+}
+
+#[test]
+fn fcall_sort_non_numeric() {
+    let mut env = TEnvironment::new();
+    env.vtable_set("points".into(), Type::PointArray);
+    let t1 = Expr::FCall {
+        name: "sort".into(),
+        args: vec![Expr::Variable("points".into()).into()],
+    }
+    .type_check(&mut env);
+    assert!(t1
+        .unwrap_err()
+        .downcast_ref::<errors::FCallParametersIncompatible>()
+        .is_some());
+}
+
+#[test]
+fn conversion_builtins() {
+    let mut env = TEnvironment::new();
+    let t1 = Expr::FCall {
+        name: "int".into(),
+        args: vec![Expr::Float(2.5).into()],
+    }
+    .type_check(&mut env)
+    .unwrap();
+    let t2 = Expr::FCall {
+        name: "float".into(),
+        args: vec![Expr::Integer(2).into()],
+    }
+    .type_check(&mut env)
+    .unwrap();
+    assert_eq!((t1, t2), (Type::Int, Type::Float))
+}
+
+#[test]
+fn combine_needs_shapes() {
+    let mut env = TEnvironment::new();
+    env.vtable_set("x".to_string(), Type::Shape);
+    env.vtable_set("p".to_string(), Type::Point);
+    let t1 = Expr::Combine {
+        base_shape: Expr::Variable("x".into()).into(),
+        second_shape: Expr::Variable("x".into()).into(),
+        operator: ShapeOperator::Xor,
+    }
+    .type_check(&mut env)
+    .unwrap();
+    let t2 = Expr::Combine {
+        base_shape: Expr::Variable("x".into()).into(),
+        second_shape: Expr::Variable("p".into()).into(),
+        operator: ShapeOperator::Union,
+    }
+    .type_check(&mut env);
+    assert_eq!(t1, Type::Shape);
+    assert!(t2
+        .unwrap_err()
+        .downcast_ref::<errors::ManipulationCombineTypeFault>()
+        .is_some());
+}
+
+#[test]
+fn mirror_needs_shape_and_points() {
+    let mut env = TEnvironment::new();
+    env.vtable_set("x".to_string(), Type::Shape);
+    env.vtable_set("p".to_string(), Type::Point);
+    let t1 = Expr::Mirror {
+        base_shape: Expr::Variable("x".into()).into(),
+        axis: MirrorAxis::Line(
+            Expr::Variable("p".into()).into(),
+            Expr::Variable("p".into()).into(),
+        ),
+    }
+    .type_check(&mut env)
+    .unwrap();
+    let t2 = Expr::Mirror {
+        base_shape: Expr::Variable("p".into()).into(),
+        axis: MirrorAxis::Vertical,
+    }
+    .type_check(&mut env);
+    let t3 = Expr::Mirror {
+        base_shape: Expr::Variable("x".into()).into(),
+        axis: MirrorAxis::Line(
+            Expr::Variable("p".into()).into(),
+            Expr::Variable("x".into()).into(),
+        ),
+    }
+    .type_check(&mut env);
+    assert_eq!(t1, Type::Shape);
+    for t in [t2, t3] {
+        assert!(t
+            .unwrap_err()
+            .downcast_ref::<errors::ManipulationMirrorTypeFault>()
+            .is_some());
+    }
+}
+
+#[test]
+fn move_needs_shape_and_point() {
+    let mut env = TEnvironment::new();
+    env.vtable_set("x".to_string(), Type::Shape);
+    env.vtable_set("p".to_string(), Type::Point);
+    let t1 = Expr::Move {
+        base_shape: Expr::Variable("x".into()).into(),
+        point: Expr::Variable("p".into()).into(),
+        absolute: true,
+    }
+    .type_check(&mut env)
+    .unwrap();
+    let t2 = Expr::Move {
+        base_shape: Expr::Variable("x".into()).into(),
+        point: Expr::Integer(3).into(),
+        absolute: false,
+    }
+    .type_check(&mut env);
+    assert_eq!(t1, Type::Shape);
+    assert!(t2
+        .unwrap_err()
+        .downcast_ref::<errors::ManipulationMoveTypeFault>()
+        .is_some());
+}
+
+#[test]
+fn scale_by_point_around_anchor() {
+    let mut env = TEnvironment::new();
+    env.vtable_set("x".to_string(), Type::Shape);
+    env.vtable_set("p".to_string(), Type::Point);
+    let t1 = Expr::Scale {
+        base_shape: Expr::Variable("x".into()).into(),
+        factor: Expr::Variable("p".into()).into(),
+        anchor: Some(Anchor::Point(Expr::Variable("p".into()).into())),
+    }
+    .type_check(&mut env)
+    .unwrap();
+    let t2 = Expr::Scale {
+        base_shape: Expr::Variable("x".into()).into(),
+        factor: Expr::Integer(2).into(),
+        anchor: Some(Anchor::Point(Expr::Integer(2).into())),
+    }
+    .type_check(&mut env);
+    assert_eq!(t1, Type::Shape);
+    assert!(t2
+        .unwrap_err()
+        .downcast_ref::<errors::ManipulationAnchorTypeFault>()
+        .is_some());
+}
+
+#[test]
+fn rotate_around_needs_point() {
+    let mut env = TEnvironment::new();
+    env.vtable_set("x".to_string(), Type::Shape);
+    let t1 = Expr::Rotate {
+        base_shape: Expr::Variable("x".into()).into(),
+        factor: Expr::Integer(90).into(),
+        anchor: Some(Anchor::BottomLeft),
+    }
+    .type_check(&mut env)
+    .unwrap();
+    let t2 = Expr::Rotate {
+        base_shape: Expr::Variable("x".into()).into(),
+        factor: Expr::Integer(90).into(),
+        anchor: Some(Anchor::Point(Expr::Variable("x".into()).into())),
+    }
+    .type_check(&mut env);
+    assert_eq!(t1, Type::Shape);
+    assert!(t2
+        .unwrap_err()
+        .downcast_ref::<errors::ManipulationAnchorTypeFault>()
+        .is_some());
+}
+
+#[test]
+fn transform_builtins_compose_and_apply() {
+    let mut env = TEnvironment::new();
+    env.vtable_set("x".to_string(), Type::Shape);
+    let call = |name: &str, args: Vec<Expr>| Expr::FCall {
+        name: name.into(),
+        args: args.into_iter().map(|arg| arg.into()).collect(),
+    };
+    let point = || Expr::Point(Expr::Integer(1).into(), Expr::Integer(2).into());
+    let composed = Expr::BinaryOperation {
+        lhs: call("scaling", vec![point()]).into(),
+        rhs: call("skew", vec![Expr::Integer(10), Expr::Float(0.)]).into(),
+        operator: BinaryOperator::Multiply,
+    };
+    let t1 = composed.type_check(&mut env).unwrap();
+    let t2 = Expr::Apply {
+        transform: composed.into(),
+        base_shape: Expr::Variable("x".into()).into(),
+    }
+    .type_check(&mut env)
+    .unwrap();
+    let t3 = call("rotation", vec![point()]).type_check(&mut env);
+    let t4 = Expr::Apply {
+        transform: call("translation", vec![point()]).into(),
+        base_shape: point().into(),
+    }
+    .type_check(&mut env);
+    assert_eq!((t1, t2), (Type::Transform, Type::Shape));
+    assert!(t3
+        .unwrap_err()
+        .downcast_ref::<errors::FCallParametersIncompatible>()
+        .is_some());
+    assert!(t4
+        .unwrap_err()
+        .downcast_ref::<errors::ManipulationApplyTypeFault>()
+        .is_some());
+}
+
+#[test]
+fn layout_builtins_take_shape_arrays() {
+    let mut env = TEnvironment::new();
+    env.vtable_set("shapes".to_string(), Type::ShapeArray);
+    env.vtable_set("points".to_string(), Type::PointArray);
+    let call = |name: &str, args: Vec<Expr>| Expr::FCall {
+        name: name.into(),
+        args: args.into_iter().map(|arg| arg.into()).collect(),
+    };
+    let shapes = || Expr::Variable("shapes".into());
+    let t1 = call("row", vec![shapes(), Expr::Integer(2), Expr::String("top".into())])
+        .type_check(&mut env)
+        .unwrap();
+    let t2 = call("grid", vec![shapes(), Expr::Integer(3), Expr::Float(1.5)])
+        .type_check(&mut env)
+        .unwrap();
+    let t3 = call("distribute", vec![Expr::Variable("points".into()), Expr::Integer(10)])
+        .type_check(&mut env);
+    let t4 = call("column", vec![shapes(), Expr::Integer(2)]).type_check(&mut env);
+    assert_eq!((t1, t2), (Type::Shape, Type::Shape));
+    assert!(t3
+        .unwrap_err()
+        .downcast_ref::<errors::FCallParametersIncompatible>()
+        .is_some());
+    assert!(t4
+        .unwrap_err()
+        .downcast_ref::<errors::FCallParametersCountError>()
+        .is_some());
+}
+
+#[test]
+fn geometry_members_and_builtins() {
+    let mut env = TEnvironment::new();
+    env.vtable_set("s".to_string(), Type::Shape);
+    env.vtable_set("p".to_string(), Type::Polygon);
+    env.vtable_set("ints".to_string(), Type::IntArray);
+    let member = |identifier: &str, member_access: &str| Expr::Member {
+        identifier: identifier.into(),
+        member_access: member_access.into(),
+    };
+    let call = |name: &str, args: Vec<Expr>| Expr::FCall {
+        name: name.into(),
+        args: args.into_iter().map(|arg| arg.into()).collect(),
+    };
+    let point = || Expr::Point(Expr::Integer(1).into(), Expr::Integer(2).into());
+
+    assert_eq!(member("s", "area").type_check(&mut env).unwrap(), Type::Float);
+    assert_eq!(member("p", "centroid").type_check(&mut env).unwrap(), Type::Point);
+    assert!(member("s", "size")
+        .type_check(&mut env)
+        .unwrap_err()
+        .downcast_ref::<errors::MemberAccessShape>()
+        .is_some());
+
+    let s = || Expr::Variable("s".into());
+    let t1 = call("contains", vec![s(), point()]).type_check(&mut env).unwrap();
+    let t2 = call("contains", vec![Expr::Variable("ints".into()), Expr::Integer(2)])
+        .type_check(&mut env)
+        .unwrap();
+    let t3 = call("overlaps", vec![s(), s()]).type_check(&mut env).unwrap();
+    let t4 = call("distance", vec![point(), point()]).type_check(&mut env).unwrap();
+    assert_eq!((t1, t2, t3, t4), (Type::Bool, Type::Bool, Type::Bool, Type::Float));
+    assert!(call("overlaps", vec![s(), point()])
+        .type_check(&mut env)
+        .unwrap_err()
+        .downcast_ref::<errors::FCallParametersIncompatible>()
+        .is_some());
+}
+
+#[test]
+fn scall_primitive_shapes() {
+    let mut env = TEnvironment::new();
+    let call = |name: &str, args: Vec<(&str, Expr)>| Expr::SCall {
+        name: Some(name.into()),
+        args: args.into_iter().map(|(key, arg)| (key.into(), arg.into())).collect(),
+        path_poly: None,
+    };
+    let t1 = call("rect", vec![("width", Expr::Float(2.)), ("height", Expr::Float(1.))])
+        .type_check(&mut env)
+        .unwrap();
+    //An int size is promoted, like in circle.dap from dap-lib
+    let t2 = call("circle", vec![("radius", Expr::Integer(2))]).type_check(&mut env).unwrap();
+    let t3 = call("ellipse", vec![("rx", Expr::Float(2.))]).type_check(&mut env);
+    let t5 = call("circle", vec![("radius", Expr::Boolean(true))]).type_check(&mut env);
+    assert_eq!((t1, t2), (Type::Shape, Type::Shape));
+    assert!(t5
+        .unwrap_err()
+        .downcast_ref::<errors::SCallParametersIncompatible>()
+        .is_some());
+    assert!(t3
+        .unwrap_err()
+        .downcast_ref::<errors::SCallParameterNotFound>()
+        .is_some());
+
+    //An imported shape with the same name is used instead
+    env.stable_set("circle".into(), [].into_iter().collect());
+    let t4 = call("circle", vec![]).type_check(&mut env).unwrap();
+    assert_eq!(t4, Type::Shape);
+}
@@ -1,5 +1,5 @@
 use crate::{
-    program::{expression::Expr, r#type::Type, statement::Stmt},
+    program::{errors::LocatedError, expression::Expr, r#type::Type, statement::Stmt},
     typechecker::{
         environment::{EType, TEnvironment},
         errors, TypeCheckS,
@@ -12,7 +12,7 @@ fn vardecl() {
     let t1 = Stmt::VarDecl {
         name: "x".into(),
         declared_type: Type::Int,
-        value: Expr::Integer(5),
+        value: Expr::Integer(5).into(),
     }
     .type_check(&mut env);
     assert!(t1.is_ok())
@@ -25,7 +25,7 @@ fn vardecl_identifier_already_declared() {
     let already_declared = Stmt::VarDecl {
         name: "x".into(),
         declared_type: Type::Int,
-        value: Expr::Integer(5),
+        value: Expr::Integer(5).into(),
     }
     .type_check(&mut env);
     assert!(already_declared
@@ -40,7 +40,7 @@ fn vardecl_expression_types_mismatch() {
     let type_mismatch = Stmt::VarDecl {
         name: "x".into(),
         declared_type: Type::Int,
-        value: Expr::Boolean(true),
+        value: Expr::Boolean(true).into(),
     }
     .type_check(&mut env);
     assert!(type_mismatch
@@ -55,7 +55,7 @@ fn assign() {
     env.vtable_set("x".into(), Type::Int);
     let t1 = Stmt::Assign {
         name: "x".into(),
-        value: Expr::Integer(1),
+        value: Expr::Integer(1).into(),
     }
     .type_check(&mut env);
     assert!(t1.is_ok())
@@ -67,7 +67,7 @@ fn assign_empty() {
     env.vtable_set("x".into(), Type::ShapeArray);
     let t1 = Stmt::Assign {
         name: "x".into(),
-        value: Expr::Array(vec![]),
+        value: Expr::Array(vec![]).into(),
     }
     .type_check(&mut env);
     assert!(t1.is_ok())
@@ -79,7 +79,7 @@ fn assign_error() {
     env.vtable_set("x".into(), Type::Float);
     let t1 = Stmt::Assign {
         name: "x".into(),
-        value: Expr::Integer(1),
+        value: Expr::Integer(1).into(),
     }
     .type_check(&mut env);
     assert!(t1
@@ -94,8 +94,8 @@ fn array_assign() {
     env.vtable_set("x".into(), Type::IntArray);
     let t1 = Stmt::ArrayAssign {
         name: "x".into(),
-        value: Expr::Integer(1),
-        index: Expr::Integer(5),
+        value: Expr::Integer(1).into(),
+        index: Expr::Integer(5).into(),
     }
     .type_check(&mut env);
     assert!(t1.is_ok())
@@ -107,8 +107,8 @@ fn array_assign_conflict_empty() {
     env.vtable_set("x".into(), Type::IntArray);
     let t1 = Stmt::ArrayAssign {
         name: "x".into(),
-        value: Expr::Float(5.2),
-        index: Expr::Integer(5),
+        value: Expr::Float(5.2).into(),
+        index: Expr::Integer(5).into(),
     }
     .type_check(&mut env);
     assert!(t1
@@ -123,8 +123,8 @@ fn array_assign_index_error() {
     env.vtable_set("x".into(), Type::IntArray);
     let t1 = Stmt::ArrayAssign {
         name: "x".into(),
-        value: Expr::Integer(5),
-        index: Expr::Float(5.2),
+        value: Expr::Integer(5).into(),
+        index: Expr::Float(5.2).into(),
     }
     .type_check(&mut env);
     assert!(t1
@@ -145,9 +145,9 @@ fn draw_with_point() {
     let t1 = Stmt::Draw {
         shape: Expr::SCall {
             name: Some("circle".into()),
-            args: [("radius".into(), Expr::Float(5.0))].into_iter().collect(),
+            args: [("radius".into(), Expr::Float(5.0).into())].into_iter().collect(),
             path_poly: None,
-        },
+        }.into(),
         point: Some(Expr::Point(Expr::Integer(1).into(), Expr::Integer(1).into()).into()),
     }
     .type_check(&mut env);
@@ -166,9 +166,9 @@ fn draw_without_point() {
     let t1 = Stmt::Draw {
         shape: Expr::SCall {
             name: Some("circle".into()),
-            args: [("radius".into(), Expr::Float(5.0))].into_iter().collect(),
+            args: [("radius".into(), Expr::Float(5.0).into())].into_iter().collect(),
             path_poly: None,
-        },
+        }.into(),
         point: None,
     }
     .type_check(&mut env);
@@ -179,7 +179,7 @@ fn draw_without_point() {
 fn draw_with_point_shape_error() {
     let mut env = TEnvironment::new();
     let type_mismatch = Stmt::Draw {
-        shape: Expr::Integer(1),
+        shape: Expr::Integer(1).into(),
         point: Some(Expr::Point(Expr::Integer(1).into(), Expr::Integer(1).into()).into()),
     }
     .type_check(&mut env);
@@ -202,10 +202,10 @@ fn draw_with_point_point_error() {
     let type_mismatch = Stmt::Draw {
         shape: Expr::SCall {
             name: Some("circle".into()),
-            args: [("radius".into(), Expr::Float(5.0))].into_iter().collect(),
+            args: [("radius".into(), Expr::Float(5.0).into())].into_iter().collect(),
             path_poly: None,
-        },
-        point: Some(Expr::Integer(1)),
+        }.into(),
+        point: Some(Expr::Integer(1).into()),
     }
     .type_check(&mut env);
 
@@ -219,7 +219,7 @@ fn draw_with_point_point_error() {
 fn draw_without_point_shape_error() {
     let mut env = TEnvironment::new();
     let type_mismatch = Stmt::Draw {
-        shape: Expr::Integer(1),
+        shape: Expr::Integer(1).into(),
         point: None,
     }
     .type_check(&mut env);
@@ -235,13 +235,13 @@ fn for_loop() {
     let mut env = TEnvironment::new();
     let t1 = Stmt::For {
         counter: "x".into(),
-        from: Expr::Integer(1),
-        to: Expr::Integer(10),
+        from: Expr::Integer(1).into(),
+        to: Expr::Integer(10).into(),
         body: vec![Stmt::VarDecl {
             name: "z".into(),
             declared_type: Type::Int,
-            value: Expr::Integer(2),
-        }],
+            value: Expr::Integer(2).into(),
+        }.into()],
     }
     .type_check(&mut env);
     assert!(t1.is_ok())
@@ -253,13 +253,13 @@ fn for_loop_counter_error() {
     env.vtable_set("x".into(), Type::Int);
     let type_mismatch = Stmt::For {
         counter: "x".into(),
-        from: Expr::Integer(1),
-        to: Expr::Integer(10),
+        from: Expr::Integer(1).into(),
+        to: Expr::Integer(10).into(),
         body: vec![Stmt::VarDecl {
             name: "z".into(),
             declared_type: Type::Int,
-            value: Expr::Integer(2),
-        }],
+            value: Expr::Integer(2).into(),
+        }.into()],
     }
     .type_check(&mut env);
 
@@ -274,13 +274,13 @@ fn for_loop_range_error() {
     let mut env = TEnvironment::new();
     let type_mismatch = Stmt::For {
         counter: "x".into(),
-        from: Expr::Float(1.2),
-        to: Expr::Integer(10),
+        from: Expr::Float(1.2).into(),
+        to: Expr::Integer(10).into(),
         body: vec![Stmt::VarDecl {
             name: "z".into(),
             declared_type: Type::Int,
-            value: Expr::Integer(2),
-        }],
+            value: Expr::Integer(2).into(),
+        }.into()],
     }
     .type_check(&mut env);
 
@@ -295,12 +295,12 @@ fn fork() {
     let mut env = TEnvironment::new();
     let t1 = Stmt::Fork {
         branches: vec![(
-            Expr::Boolean(true),
+            Expr::Boolean(true).into(),
             vec![Stmt::VarDecl {
                 name: "z".into(),
                 declared_type: Type::Int,
-                value: Expr::Integer(2),
-            }],
+                value: Expr::Integer(2).into(),
+            }.into()],
         )],
         otherwise: None,
     }
@@ -313,18 +313,18 @@ fn fork_with_otherwise() {
     let mut env = TEnvironment::new();
     let t1 = Stmt::Fork {
         branches: vec![(
-            Expr::Boolean(true),
+            Expr::Boolean(true).into(),
             vec![Stmt::VarDecl {
                 name: "z".into(),
                 declared_type: Type::Int,
-                value: Expr::Integer(2),
-            }],
+                value: Expr::Integer(2).into(),
+            }.into()],
         )],
         otherwise: Some(vec![Stmt::VarDecl {
             name: "z".into(),
             declared_type: Type::Int,
-            value: Expr::Integer(2),
-        }]),
+            value: Expr::Integer(2).into(),
+        }.into()]),
     }
     .type_check(&mut env);
 
@@ -336,12 +336,12 @@ fn fork_error() {
     let mut env = TEnvironment::new();
     let type_mismatch = Stmt::Fork {
         branches: vec![(
-            Expr::Integer(2),
+            Expr::Integer(2).into(),
             vec![Stmt::VarDecl {
                 name: "z".into(),
                 declared_type: Type::Int,
-                value: Expr::Integer(2),
-            }],
+                value: Expr::Integer(2).into(),
+            }.into()],
         )],
         otherwise: None,
     }
@@ -363,8 +363,8 @@ fn funcdecl() {
         statements: vec![Stmt::VarDecl {
             name: "z".into(),
             declared_type: Type::Int,
-            value: Expr::Integer(2),
-        }],
+            value: Expr::Integer(2).into(),
+        }.into()],
     }
     .type_check(&mut env);
     assert!(t1.is_ok())
@@ -390,7 +390,7 @@ fn funcdecl_identifier_already_declared() {
 #[test]
 fn r#return() {
     let mut env = TEnvironment::new();
-    let t1 = Stmt::Return(Expr::Integer(5)).type_check(&mut env);
+    let t1 = Stmt::Return(Expr::Integer(5).into()).type_check(&mut env);
     assert!(t1.is_ok())
 }
 
@@ -398,7 +398,7 @@ fn r#return() {
 fn return_mismatch() {
     let mut env = TEnvironment::new();
     env.return_set(Type::Bool);
-    let return_mismatch = Stmt::Return(Expr::Integer(5)).type_check(&mut env);
+    let return_mismatch = Stmt::Return(Expr::Integer(5).into()).type_check(&mut env);
     assert!(return_mismatch
         .unwrap_err()
         .downcast_ref::<errors::ReturnTypeNotMatch>()
@@ -411,7 +411,7 @@ fn decl() {
     let t1 = Stmt::Decl {
         name: "x".into(),
         declared_type: Type::Int,
-        value: Some(Expr::Integer(5)),
+        value: Some(Expr::Integer(5).into()),
     }
     .type_check(&mut env);
 
@@ -438,7 +438,7 @@ fn decl_identifier_already_declared() {
     let already_declared = Stmt::Decl {
         name: "x".into(),
         declared_type: Type::Int,
-        value: Some(Expr::Integer(5)),
+        value: Some(Expr::Integer(5).into()),
     }
     .type_check(&mut env);
 
@@ -454,7 +454,7 @@ fn decl_expression_types_mismatch() {
     let type_mismatch = Stmt::Decl {
         name: "x".into(),
         declared_type: Type::Int,
-        value: Some(Expr::Boolean(true)),
+        value: Some(Expr::Boolean(true).into()),
     }
     .type_check(&mut env);
     assert!(type_mismatch
@@ -496,9 +496,75 @@ fn import_typeerror() {
         name: "circle".into(),
         path: "./src/typechecker/tests/circle_error.testfile".into(),
     }
+    .type_check(&mut env)
+    .unwrap_err();
+    // Errors inside the imported file are reported at their position in that file
+    let located = variable_type_mismatch
+        .downcast_ref::<LocatedError>()
+        .unwrap();
+    assert_eq!(located.0.file.as_ref(), "./src/typechecker/tests/circle_error.testfile");
+    assert!(located
+        .1
+        .downcast_ref::<errors::VariableExpressionTypeNotMatch>()
+        .is_some());
+}
+
+#[test]
+fn while_loop() {
+    let mut env = TEnvironment::new();
+    let t1 = Stmt::While {
+        condition: Expr::Boolean(true).into(),
+        body: vec![Stmt::Break.into(), Stmt::Continue.into()],
+    }
     .type_check(&mut env);
-    assert!(variable_type_mismatch
+    assert!(t1.is_ok())
+}
+
+#[test]
+fn while_loop_condition_error() {
+    let mut env = TEnvironment::new();
+    let type_mismatch = Stmt::While {
+        condition: Expr::Integer(1).into(),
+        body: vec![Stmt::Break.into()],
+    }
+    .type_check(&mut env);
+
+    assert!(type_mismatch
         .unwrap_err()
-        .downcast_ref::<errors::VariableExpressionTypeNotMatch>()
+        .downcast_ref::<errors::WhileNotBooltypeError>()
+        .is_some());
+}
+
+#[test]
+fn break_outside_loop() {
+    let mut env = TEnvironment::new();
+    let outside = Stmt::Break.type_check(&mut env);
+
+    assert!(outside
+        .unwrap_err()
+        .downcast_ref::<errors::LoopControlOutsideLoop>()
+        .is_some());
+}
+
+#[test]
+fn continue_in_function_inside_loop() {
+    let mut env = TEnvironment::new();
+    let outside = Stmt::For {
+        counter: "x".into(),
+        from: Expr::Integer(1).into(),
+        to: Expr::Integer(10).into(),
+        body: vec![Stmt::FuncDecl {
+            name: "f".into(),
+            return_type: Type::Int,
+            parameters: vec![],
+            statements: vec![Stmt::Continue.into()],
+        }
+        .into()],
+    }
+    .type_check(&mut env);
+
+    assert!(outside
+        .unwrap_err()
+        .downcast_ref::<errors::LoopControlOutsideLoop>()
         .is_some());
 }